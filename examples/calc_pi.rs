@@ -1,5 +1,5 @@
-///! Calculate the value of PI using the Chudnovsky_algorithm.
-///!  cargo run --example calc_pi --release
+//! Calculate the value of PI using the Chudnovsky_algorithm.
+//!  cargo run --example calc_pi --release
 
 type FP = arpfloat::FP256;
 
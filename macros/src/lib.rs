@@ -0,0 +1,146 @@
+//! Macros that convert decimal literals to floats at compile time. The
+//! literal is parsed with the parser of arpfloat, and is correctly rounded to
+//! the format, to nearest with ties to even. The macro expands to a call to
+//! the const function `from_bits` with the encoding of the number, so there
+//! is no parsing at runtime, no rounding through f64, and the literals can
+//! initialize constants and statics. Invalid literals are reported as compile
+//! errors.
+//!```
+//!    use arpfloat::FP64;
+//!    use arpfloat_macros::{fp128, fp64};
+//!
+//!    let pi = fp128!("3.14159265358979323846264338327950288");
+//!    assert_eq!(pi.as_f64(), std::f64::consts::PI);
+//!    assert_eq!(pi.to_bits()[1], 0x4000_921f_b544_42d1);
+//!    const TENTH: FP64 = fp64!("-0.1");
+//!    assert_eq!(TENTH.as_f64(), -0.1);
+//!```
+//!
+//! The macros are in a separate crate, because a procedural macro crate can
+//! only export macros.
+//!```compile_fail
+//!    let x = arpfloat_macros::fp64!("1.2.3");
+//!```
+
+use arpfloat::{Float, ParseFloatError};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Parses `text` and returns the words of the encoding of the number in the
+/// format Float<E, M, P>.
+fn encode<const E: usize, const M: usize, const P: usize>(
+    text: &str,
+) -> Result<[u64; P], ParseFloatError> {
+    let val: Float<E, M, P> = text.parse()?;
+    Ok(val.to_bits())
+}
+
+/// Expands the literal in `input` to a number of the type `ty`, which is the
+/// path of the type Float<E, M, P>.
+fn expand<const E: usize, const M: usize, const P: usize>(
+    input: TokenStream,
+    ty: TokenStream2,
+) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    match encode::<E, M, P>(&lit.value()) {
+        Ok(words) => quote!(#ty::from_bits([#(#words),*])).into(),
+        Err(err) => syn::Error::new(lit.span(), err.to_string())
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Converts the decimal literal to FP16 at compile time.
+#[proc_macro]
+pub fn fp16(input: TokenStream) -> TokenStream {
+    expand::<5, 10, 1>(input, quote!(::arpfloat::FP16))
+}
+
+/// Converts the decimal literal to BF16 at compile time.
+#[proc_macro]
+pub fn bf16(input: TokenStream) -> TokenStream {
+    expand::<8, 7, 1>(input, quote!(::arpfloat::BF16))
+}
+
+/// Converts the decimal literal to TF32 at compile time.
+#[proc_macro]
+pub fn tf32(input: TokenStream) -> TokenStream {
+    expand::<8, 10, 1>(input, quote!(::arpfloat::TF32))
+}
+
+/// Converts the decimal literal to FP32 at compile time.
+#[proc_macro]
+pub fn fp32(input: TokenStream) -> TokenStream {
+    expand::<8, 23, 1>(input, quote!(::arpfloat::FP32))
+}
+
+/// Converts the decimal literal to FP64 at compile time.
+#[proc_macro]
+pub fn fp64(input: TokenStream) -> TokenStream {
+    expand::<11, 52, 2>(input, quote!(::arpfloat::FP64))
+}
+
+/// Converts the decimal literal to FP80 at compile time.
+#[proc_macro]
+pub fn fp80(input: TokenStream) -> TokenStream {
+    expand::<15, 63, 2>(input, quote!(::arpfloat::FP80))
+}
+
+/// Converts the decimal literal to FP128 at compile time.
+#[proc_macro]
+pub fn fp128(input: TokenStream) -> TokenStream {
+    expand::<15, 112, 4>(input, quote!(::arpfloat::FP128))
+}
+
+/// Converts the decimal literal to FP256 at compile time.
+#[proc_macro]
+pub fn fp256(input: TokenStream) -> TokenStream {
+    expand::<19, 236, 8>(input, quote!(::arpfloat::FP256))
+}
+
+#[test]
+fn test_encode() {
+    use arpfloat::{FP128, FP16, FP256, FP32, FP64};
+
+    // The encodings match the native types.
+    assert_eq!(encode::<11, 52, 2>("0.1"), Ok([0.1f64.to_bits(), 0]));
+    assert_eq!(
+        encode::<11, 52, 2>("-1e308"),
+        Ok([(-1e308f64).to_bits(), 0])
+    );
+    let x = encode::<8, 23, 1>("3.4028236e38").unwrap();
+    assert_eq!(x[0], f32::INFINITY.to_bits() as u64);
+    assert_eq!(FP32::from_bits(x), FP32::from_f32(f32::INFINITY));
+    assert_eq!(encode::<5, 10, 1>("65504"), Ok([0x7bff]));
+    assert!(FP16::from_bits(encode::<5, 10, 1>("nan").unwrap()).is_nan());
+
+    // The literals are rounded once, to the target format.
+    let digits = "3.14159265358979323846264338327950288419716939937510582";
+    let bits = 0x4000_921f_b544_42d1_8469_898c_c517_01b8u128;
+    let words = [bits as u64, (bits >> 64) as u64, 0, 0];
+    assert_eq!(encode::<15, 112, 4>(digits), Ok(words));
+    let pi = FP256::from_bits(encode::<19, 236, 8>(digits).unwrap());
+    let pi: FP128 = pi.cast();
+    assert_eq!(pi, FP128::from_bits(words));
+    let x = FP64::from_bits(
+        encode::<11, 52, 2>(
+            "1.00000000000000011102230246251565404236316680908203125",
+        )
+        .unwrap(),
+    );
+    assert_eq!(x.as_f64(), 1.);
+    let x = FP64::from_bits(
+        encode::<11, 52, 2>(
+            "1.00000000000000011102230246251565404236316680908203126",
+        )
+        .unwrap(),
+    );
+    assert_eq!(x.as_f64(), 1. + f64::EPSILON);
+
+    // Invalid literals are errors.
+    assert!(encode::<11, 52, 2>("").is_err());
+    assert!(encode::<11, 52, 2>("1.2.3").is_err());
+    assert!(encode::<11, 52, 2>("1e").is_err());
+}
@@ -1,10 +1,12 @@
 extern crate alloc;
 use crate::BigInt;
 
-use super::bigint::LossFraction;
-use core::ops::{Add, Div, Mul, Sub};
+use super::bigint::{DynBigInt, LossFraction};
+use super::float::{
+    shift_right_with_loss, ArithError, Category, Float, OpStatus, RoundingMode,
+};
 use core::cmp::Ordering;
-use super::float::{shift_right_with_loss, Category, Float, RoundingMode};
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
 #[cfg(test)]
 use crate::std::string::ToString;
@@ -85,17 +87,40 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
 
     /// Computes a+b using the rounding mode `rm`.
     pub fn add_with_rm(a: Self, b: Self, rm: RoundingMode) -> Self {
-        Self::add_sub(a, b, false, rm)
+        Self::add_sub(a, b, false, rm).0
     }
     /// Computes a-b using the rounding mode `rm`.
     pub fn sub_with_rm(a: Self, b: Self, rm: RoundingMode) -> Self {
+        Self::add_sub(a, b, true, rm).0
+    }
+    /// Computes a+b using the rounding mode `rm`, and returns the result
+    /// together with the exception flags that the operation raised.
+    pub fn add_with_status(
+        a: Self,
+        b: Self,
+        rm: RoundingMode,
+    ) -> (Self, OpStatus) {
+        Self::add_sub(a, b, false, rm)
+    }
+    /// Computes a-b using the rounding mode `rm`, and returns the result
+    /// together with the exception flags that the operation raised.
+    pub fn sub_with_status(
+        a: Self,
+        b: Self,
+        rm: RoundingMode,
+    ) -> (Self, OpStatus) {
         Self::add_sub(a, b, true, rm)
     }
 
-    fn add_sub(a: Self, b: Self, subtract: bool, rm: RoundingMode) -> Self {
+    fn add_sub(
+        a: Self,
+        b: Self,
+        subtract: bool,
+        rm: RoundingMode,
+    ) -> (Self, OpStatus) {
         // Table 8.2: Specification of addition for positive floating-point
         // data. Pg 247.
-        match (a.get_category(), b.get_category()) {
+        let res = match (a.get_category(), b.get_category()) {
             (Category::NaN, Category::Infinity)
             | (Category::NaN, Category::NaN)
             | (Category::NaN, Category::Normal)
@@ -106,32 +131,52 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
 
             (Category::Zero, Category::NaN)
             | (Category::Normal, Category::NaN)
-            | (Category::Infinity, Category::NaN) => Self::nan(b.get_sign()),
+            | (Category::Infinity, Category::NaN) => b,
 
             (Category::Normal, Category::Infinity)
             | (Category::Zero, Category::Infinity) => {
                 Self::inf(b.get_sign() ^ subtract)
             }
 
-            (Category::Zero, Category::Normal) => b,
+            (Category::Zero, Category::Normal) => {
+                if subtract {
+                    b.neg()
+                } else {
+                    b
+                }
+            }
 
             (Category::Zero, Category::Zero) => {
-                Self::zero(a.get_sign() && b.get_sign())
+                // Zeros with opposite signs add up to +0, except when
+                // rounding down.
+                if a.get_sign() == (b.get_sign() ^ subtract) {
+                    Self::zero(a.get_sign())
+                } else {
+                    Self::zero(matches!(rm, RoundingMode::Negative))
+                }
             }
 
             (Category::Infinity, Category::Infinity) => {
                 if a.get_sign() ^ b.get_sign() ^ subtract {
-                    return Self::nan(a.get_sign() ^ b.get_sign());
+                    let nan = Self::nan(a.get_sign() ^ b.get_sign());
+                    return (nan, OpStatus::INVALID_OP);
                 }
                 Self::inf(a.get_sign())
             }
 
             (Category::Normal, Category::Normal) => {
                 let mut res = Self::add_or_sub_normals(a, b, subtract);
-                res.0.normalize(rm, res.1);
-                res.0
+                let status = res.0.normalize(rm, res.1);
+                // IEEE 754-2019 section 6.3: An exact zero sum of operands
+                // with opposite signs is +0, except when rounding down.
+                if res.0.is_zero() {
+                    let sign = matches!(rm, RoundingMode::Negative);
+                    return (Self::zero(sign), status);
+                }
+                return (res.0, status);
             }
-        }
+        };
+        (res, OpStatus::OK)
     }
 }
 
@@ -267,6 +312,56 @@ fn add_special_values() {
     }
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn sub_special_values() {
+    use crate::utils;
+
+    // Test the subtraction of various irregular values.
+    let values = utils::get_special_test_values();
+
+    use super::float::FP64;
+
+    fn sub_f64(a: f64, b: f64) -> f64 {
+        let a = FP64::from_f64(a);
+        let b = FP64::from_f64(b);
+        FP64::sub(a, b).as_f64()
+    }
+
+    for v0 in values {
+        for v1 in values {
+            let r0 = sub_f64(v0, v1);
+            let r1 = v0 - v1;
+            assert_eq!(r0.is_nan(), r1.is_nan());
+            // Check that the results are bit identical, or are both NaN.
+            assert!(r0.is_nan() || r0.to_bits() == r1.to_bits());
+        }
+    }
+}
+
+#[test]
+fn test_sub_from_zero() {
+    use super::float::FP64;
+    let rm = RoundingMode::NearestTiesToEven;
+    let x = FP64::from_f64(1.5);
+
+    // 0 - x is -x, for both zeros.
+    assert_eq!((FP64::zero(false) - x).as_f64(), -1.5);
+    assert_eq!((FP64::zero(true) - x).as_f64(), -1.5);
+    assert_eq!((FP64::zero(false) - x.neg()).as_f64(), 1.5);
+    assert_eq!((FP64::zero(false) + x).as_f64(), 1.5);
+
+    // Exact cancellation gives +0, except when rounding down.
+    let zero = FP64::sub_with_rm(x.neg(), x.neg(), rm);
+    assert!(zero.is_zero() && !zero.is_negative());
+    let zero = FP64::sub_with_rm(x, x, RoundingMode::Negative);
+    assert!(zero.is_zero() && zero.is_negative());
+    let zero = FP64::sub_with_rm(FP64::zero(true), FP64::zero(true), rm);
+    assert!(!zero.is_negative());
+    let zero = FP64::sub_with_rm(FP64::zero(true), FP64::zero(false), rm);
+    assert!(zero.is_negative());
+}
+
 #[test]
 fn test_add_random_vals() {
     use crate::utils;
@@ -323,18 +418,28 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
 {
     /// Compute a*b using the rounding mode `rm`.
     pub fn mul_with_rm(a: Self, b: Self, rm: RoundingMode) -> Self {
+        Self::mul_with_status(a, b, rm).0
+    }
+
+    /// Compute a*b using the rounding mode `rm`, and returns the result
+    /// together with the exception flags that the operation raised.
+    pub fn mul_with_status(
+        a: Self,
+        b: Self,
+        rm: RoundingMode,
+    ) -> (Self, OpStatus) {
         let sign = a.get_sign() ^ b.get_sign();
 
         // Table 8.4: Specification of multiplication for floating-point data of
         // positive sign. Page 251.
-        match (a.get_category(), b.get_category()) {
+        let res = match (a.get_category(), b.get_category()) {
             (Category::Zero, Category::NaN)
             | (Category::Normal, Category::NaN)
-            | (Category::Infinity, Category::NaN) => Self::nan(b.get_sign()),
+            | (Category::Infinity, Category::NaN) => b,
             (Category::NaN, Category::Infinity)
             | (Category::NaN, Category::NaN)
             | (Category::NaN, Category::Normal)
-            | (Category::NaN, Category::Zero) => Self::nan(a.get_sign()),
+            | (Category::NaN, Category::Zero) => a,
             (Category::Normal, Category::Infinity)
             | (Category::Infinity, Category::Normal)
             | (Category::Infinity, Category::Infinity) => Self::inf(sign),
@@ -343,14 +448,17 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             | (Category::Zero, Category::Zero) => Self::zero(sign),
 
             (Category::Zero, Category::Infinity)
-            | (Category::Infinity, Category::Zero) => Self::nan(sign),
+            | (Category::Infinity, Category::Zero) => {
+                return (Self::nan(sign), OpStatus::INVALID_OP);
+            }
 
             (Category::Normal, Category::Normal) => {
                 let (mut res, loss) = Self::mul_normals(a, b, sign);
-                res.normalize(rm, loss);
-                res
+                let status = res.normalize(rm, loss);
+                return (res, status);
             }
-        }
+        };
+        (res, OpStatus::OK)
     }
 
     /// See Pg 251. 8.4 Floating-Point Multiplication
@@ -494,24 +602,39 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
 {
     /// Compute a/b, with the rounding mode `rm`.
     pub fn div_with_rm(a: Self, b: Self, rm: RoundingMode) -> Self {
+        Self::div_with_status(a, b, rm).0
+    }
+
+    /// Compute a/b, with the rounding mode `rm`, and returns the result
+    /// together with the exception flags that the operation raised.
+    pub fn div_with_status(
+        a: Self,
+        b: Self,
+        rm: RoundingMode,
+    ) -> (Self, OpStatus) {
         let sign = a.get_sign() ^ b.get_sign();
         // Table 8.5: Special values for x/y - Page 263.
-        match (a.get_category(), b.get_category()) {
-            (Category::NaN, _)
-            | (_, Category::NaN)
-            | (Category::Zero, Category::Zero)
-            | (Category::Infinity, Category::Infinity) => Self::nan(sign),
+        let res = match (a.get_category(), b.get_category()) {
+            (Category::NaN, _) => a,
+            (_, Category::NaN) => b,
+            (Category::Zero, Category::Zero)
+            | (Category::Infinity, Category::Infinity) => {
+                return (Self::nan(sign), OpStatus::INVALID_OP);
+            }
 
             (_, Category::Infinity) => Self::zero(sign),
             (Category::Zero, _) => Self::zero(sign),
-            (_, Category::Zero) => Self::inf(sign),
+            (Category::Normal, Category::Zero) => {
+                return (Self::inf(sign), OpStatus::DIV_BY_ZERO);
+            }
             (Category::Infinity, _) => Self::inf(sign),
             (Category::Normal, Category::Normal) => {
                 let (mut res, loss) = Self::div_normals(a, b);
-                res.normalize(rm, loss);
-                res
+                let status = res.normalize(rm, loss);
+                return (res, status);
             }
-        }
+        };
+        (res, OpStatus::OK)
     }
 
     /// Compute a/b, where both `a` and `b` are normals.
@@ -567,6 +690,27 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     }
 }
 
+#[test]
+fn div_denormals() {
+    use super::float::FP64;
+
+    let v0 = f64::from_bits(0x0000_0000_0010_0010);
+    let v1 = f64::from_bits(0x0000_0000_1001_0010);
+
+    fn div_f64(a: f64, b: f64) -> f64 {
+        let a0 = FP64::from_f64(a);
+        let b0 = FP64::from_f64(b);
+        FP64::div(a0, b0).as_f64()
+    }
+
+    // Divide denormals by denormals and by normal numbers.
+    assert_eq!(div_f64(v0, v1), v0 / v1);
+    assert_eq!(div_f64(v1, v0), v1 / v0);
+    assert_eq!(div_f64(v0, 2.), v0 / 2.);
+    assert_eq!(div_f64(v1, 0.125), v1 / 0.125);
+    assert_eq!(div_f64(10., v1), 10. / v1);
+}
+
 #[test]
 fn test_div_simple() {
     use super::float::FP64;
@@ -614,6 +758,374 @@ fn test_div_special_values() {
     }
 }
 
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Converts the result of an operation on `a` and `b` to a Result. The
+    /// operation fails if one of the operands is NaN, or if it raised the
+    /// invalid, division-by-zero or overflow flags.
+    fn check_status(
+        a: Self,
+        b: Self,
+        res: (Self, OpStatus),
+    ) -> Result<Self, ArithError> {
+        let (val, status) = res;
+        if a.is_nan() || b.is_nan() {
+            Err(ArithError::NanOperand)
+        } else if status.contains(OpStatus::INVALID_OP) {
+            Err(ArithError::InvalidOperation)
+        } else if status.contains(OpStatus::DIV_BY_ZERO) {
+            Err(ArithError::DivisionByZero)
+        } else if status.contains(OpStatus::OVERFLOW) {
+            Err(ArithError::Overflow)
+        } else {
+            Ok(val)
+        }
+    }
+
+    /// Compute a+b, or returns an error instead of producing NaN, or an
+    /// infinity from finite operands.
+    pub fn checked_add(a: Self, b: Self) -> Result<Self, ArithError> {
+        let rm = RoundingMode::NearestTiesToEven;
+        Self::check_status(a, b, Self::add_with_status(a, b, rm))
+    }
+
+    /// Compute a-b, or returns an error instead of producing NaN, or an
+    /// infinity from finite operands.
+    pub fn checked_sub(a: Self, b: Self) -> Result<Self, ArithError> {
+        let rm = RoundingMode::NearestTiesToEven;
+        Self::check_status(a, b, Self::sub_with_status(a, b, rm))
+    }
+
+    /// Compute a*b, or returns an error instead of producing NaN, or an
+    /// infinity from finite operands.
+    pub fn checked_mul(a: Self, b: Self) -> Result<Self, ArithError> {
+        let rm = RoundingMode::NearestTiesToEven;
+        Self::check_status(a, b, Self::mul_with_status(a, b, rm))
+    }
+
+    /// Compute a/b, or returns an error instead of producing NaN, or an
+    /// infinity from finite operands.
+    pub fn checked_div(a: Self, b: Self) -> Result<Self, ArithError> {
+        let rm = RoundingMode::NearestTiesToEven;
+        Self::check_status(a, b, Self::div_with_status(a, b, rm))
+    }
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    use super::float::FP64;
+    let one = FP64::one(false);
+    let zero = FP64::zero(false);
+    let inf = FP64::inf(false);
+    let nan = FP64::nan(false);
+    let max = FP64::largest(false);
+
+    assert_eq!(FP64::checked_add(one, one).unwrap().as_f64(), 2.);
+    assert_eq!(FP64::checked_sub(one, one).unwrap().as_f64(), 0.);
+    assert_eq!(FP64::checked_mul(one, one).unwrap().as_f64(), 1.);
+    assert_eq!(FP64::checked_div(one, one).unwrap().as_f64(), 1.);
+
+    // Infinite operands are allowed, if the result is meaningful.
+    assert!(FP64::checked_add(inf, one).unwrap().is_inf());
+    assert!(FP64::checked_div(one, inf).unwrap().is_zero());
+
+    assert_eq!(FP64::checked_add(nan, one), Err(ArithError::NanOperand));
+    assert_eq!(FP64::checked_mul(one, nan), Err(ArithError::NanOperand));
+    let invalid = Err(ArithError::InvalidOperation);
+    assert_eq!(FP64::checked_sub(inf, inf), invalid);
+    assert_eq!(FP64::checked_mul(zero, inf), invalid);
+    assert_eq!(FP64::checked_div(zero, zero), invalid);
+    let div_by_zero = Err(ArithError::DivisionByZero);
+    assert_eq!(FP64::checked_div(one, zero), div_by_zero);
+    assert_eq!(FP64::checked_add(max, max), Err(ArithError::Overflow));
+    assert_eq!(FP64::checked_mul(max, max), Err(ArithError::Overflow));
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Compute a*b in the wider format Float<E, M, P>, like the widening
+    /// multiplication instructions of some processors. The operands are
+    /// converted to the wider format exactly, and the product is exact if the
+    /// wider format has at least twice the precision, and the exponent range
+    /// holds the product (FP16 to FP32, FP32 to FP64 and FP64 to FP128).
+    /// Otherwise the product is rounded to nearest, ties to even.
+    pub fn mul_widening<const E: usize, const M: usize, const P: usize>(
+        a: Self,
+        b: Self,
+    ) -> Float<E, M, P> {
+        let rm = RoundingMode::NearestTiesToEven;
+        Float::<E, M, P>::mul_with_rm(a.cast(), b.cast(), rm)
+    }
+
+    /// Compute a+b in the wider format Float<E, M, P>. The operands are
+    /// converted to the wider format exactly, and the sum is exact if it fits
+    /// in the precision of the wider format. Otherwise the sum is rounded to
+    /// nearest, ties to even, once.
+    pub fn add_widening<const E: usize, const M: usize, const P: usize>(
+        a: Self,
+        b: Self,
+    ) -> Float<E, M, P> {
+        let rm = RoundingMode::NearestTiesToEven;
+        Float::<E, M, P>::add_with_rm(a.cast(), b.cast(), rm)
+    }
+
+    /// Compute a*b+c, where `c` and the result are in the wider accumulator
+    /// format Float<E, M, P>, with a single rounding in the rounding mode `rm`.
+    /// This models the multiply-accumulate units of matrix engines, that add
+    /// products of FP16 or BF16 numbers to an FP32 accumulator. Formats up to
+    /// FP128 are computed in FP256, and wider formats with exact integers,
+    /// which is slower.
+    pub fn mul_add_widening<const E: usize, const M: usize, const P: usize>(
+        a: Self,
+        b: Self,
+        c: Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Float<E, M, P> {
+        use super::float::FP256;
+        let narrow = EXPONENT <= 15 && E <= 15 && MANTISSA <= 112 && M <= 112;
+        if !narrow {
+            return Self::mul_add_exact(a, b, c, rm);
+        }
+
+        // The product is exact in FP256. The sum is rounded to odd: it is
+        // truncated, and the lowest bit is set if the sum is inexact. This
+        // keeps enough information to round the sum to the narrower format
+        // correctly.
+        let prod = FP256::mul_with_rm(a.cast(), b.cast(), rm);
+        let c: FP256 = c.cast();
+        let (sum, status) = FP256::add_with_status(prod, c, RoundingMode::Zero);
+        if sum.is_zero() {
+            // Compute the sign of an exact zero sum in the rounding mode.
+            return FP256::add_with_rm(prod, c, rm).cast();
+        }
+        if !sum.is_normal() || !status.contains(OpStatus::INEXACT) {
+            return sum.cast_with_rm(rm);
+        }
+        let mut mantissa = sum.get_mantissa();
+        if mantissa.is_even() {
+            mantissa.flip_bit(0);
+        }
+        FP256::new(sum.get_sign(), sum.get_exp(), mantissa).cast_with_rm(rm)
+    }
+
+    /// Compute a*b+c like `mul_add_widening`, by adding the exact product to
+    /// `c` with integers, and rounding the sum once.
+    fn mul_add_exact<const E: usize, const M: usize, const P: usize>(
+        a: Self,
+        b: Self,
+        c: Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Float<E, M, P> {
+        if !a.is_normal() || !b.is_normal() || !c.is_finite() {
+            // The special values and the zero products don't depend on the
+            // magnitude of the finite operands, so replace them with one,
+            // which is exact in the accumulator format.
+            let unit = |x: Self| {
+                if x.is_normal() {
+                    Self::one(x.get_sign())
+                } else {
+                    x
+                }
+            };
+            let prod = Float::mul_with_rm(unit(a).cast(), unit(b).cast(), rm);
+            return Float::<E, M, P>::add_with_rm(prod, c, rm);
+        }
+
+        // The terms are the integers m * 2^e, and the first term is the one
+        // with the highest bit.
+        let mut prod = DynBigInt::from_bigint(&a.get_mantissa());
+        prod.inplace_mul(&DynBigInt::from_bigint(&b.get_mantissa()));
+        let prod_exp = a.get_exp() + b.get_exp() - 2 * MANTISSA as i64;
+        let addend = DynBigInt::from_bigint(&c.get_mantissa());
+        let addend_exp = if c.is_zero() {
+            prod_exp
+        } else {
+            c.get_exp() - M as i64
+        };
+        let mut terms = [
+            (a.get_sign() ^ b.get_sign(), prod, prod_exp),
+            (c.get_sign(), addend, addend_exp),
+        ];
+        let top = |t: &(bool, DynBigInt, i64)| t.1.msb_index() as i64 + t.2;
+        if top(&terms[0]) < top(&terms[1]) {
+            terms.swap(0, 1);
+        }
+        let max_top = top(&terms[0]);
+        let [(mut sign, mut large, large_exp), mut small] = terms;
+
+        // A term that is far below the lowest bit of the other term, and
+        // below the rounding position, only acts as a sticky bit. Replace it
+        // with a single bit, so that the terms are close when aligned.
+        let cutoff = large_exp.min(max_top - M as i64 - 3) - 2;
+        if !small.1.is_zero() && top(&small) <= cutoff {
+            small = (small.0, DynBigInt::one(), cutoff - 1);
+        }
+        let (small_sign, mut small, small_exp) = small;
+
+        // Align the terms, and add or subtract them.
+        let exp = large_exp.min(small_exp);
+        large.shift_left((large_exp - exp) as usize);
+        small.shift_left((small_exp - exp) as usize);
+        if sign == small_sign {
+            large.inplace_add(&small);
+        } else if large >= small {
+            large.inplace_sub(&small);
+        } else {
+            small.inplace_sub(&large);
+            large = small;
+            sign = !sign;
+        }
+        if large.is_zero() {
+            return Float::zero(matches!(rm, RoundingMode::Negative));
+        }
+
+        // Keep a few bits more than the precision, and round with the bits
+        // that are shifted out.
+        let shift = large.msb_index().saturating_sub(M + 3);
+        let loss = large.get_loss_kind_for_bit(shift);
+        large.shift_right(shift);
+        let exp = M as i64 + exp + shift as i64;
+        let mut res = Float::<E, M, P>::new(sign, exp, large.to_bigint());
+        res.normalize(rm, loss);
+        res
+    }
+}
+
+#[test]
+fn test_widening() {
+    use super::float::{FP128, FP16, FP256, FP32, FP64};
+    use super::utils::Lfsr;
+
+    let a = FP32::from_f32(1. + f32::EPSILON);
+    let prod: FP64 = FP32::mul_widening(a, a);
+    assert_eq!(prod.as_f64(), (1. + f32::EPSILON as f64).powi(2));
+    assert_ne!((a * a).as_f32() as f64, prod.as_f64());
+    let sum: FP64 = FP32::add_widening(FP32::largest(false), a);
+    assert_eq!(sum.as_f64(), f32::MAX as f64 + a.as_f64());
+    let x = FP16::largest(false);
+    let prod: FP32 = FP16::mul_widening(x, x);
+    assert_eq!(prod.as_f32(), 65504. * 65504.);
+    let x = FP32::smallest(false);
+    let prod: FP64 = FP32::mul_widening(x, x);
+    assert_eq!(prod.as_f64(), 2f64.powi(-298));
+    let nan: FP64 = FP32::mul_widening(FP32::inf(false), FP32::zero(false));
+    assert!(nan.is_nan());
+
+    // The products are exact, so a dot product of FP32 numbers in [1, 2) that
+    // is accumulated in FP128 is exact.
+    let mut lfsr = Lfsr::new();
+    let mut sum = FP128::zero(false);
+    let mut exact = FP256::zero(false);
+    let mut gen = || {
+        let bits = lfsr.get64() as u32 & 0x807fffff | 0x3f800000;
+        FP32::from_f32(f32::from_bits(bits))
+    };
+    for _ in 0..100 {
+        let (a, b) = (gen(), gen());
+        let prod: FP64 = FP32::mul_widening(a, b);
+        assert_eq!(prod.as_f64(), a.as_f64() * b.as_f64());
+        sum = sum + prod.cast();
+        exact = exact + FP32::mul_widening(a, b);
+    }
+    assert_eq!(sum, exact.cast());
+}
+
+#[test]
+fn test_mul_add_widening() {
+    use super::float::{BF16, FP128, FP16, FP256, FP32, FP64};
+    use super::utils::Lfsr;
+    let rm = RoundingMode::NearestTiesToEven;
+
+    // The product is not rounded before the addition.
+    let a = FP32::from_f64(1. + 2f64.powi(-12));
+    let c = FP32::from_f64(-1.);
+    let res = FP32::mul_add_widening(a, a, c, rm);
+    assert_eq!(res.as_f64(), 2f64.powi(-11) + 2f64.powi(-24));
+    assert_eq!((a * a + c).as_f64(), 2f64.powi(-11));
+
+    // Exact zero sums, and special values.
+    let one = FP16::one(false);
+    let res = FP16::mul_add_widening(one, one, FP32::one(true), rm);
+    assert!(res.is_zero() && !res.get_sign());
+    let rm_neg = RoundingMode::Negative;
+    let res = FP16::mul_add_widening(one, one, FP32::one(true), rm_neg);
+    assert!(res.is_zero() && res.get_sign());
+    let inf = FP16::inf(false);
+    let res = FP16::mul_add_widening(inf, one, FP32::inf(true), rm);
+    assert!(res.is_nan());
+    let res = FP16::mul_add_widening(inf, one, FP32::one(true), rm);
+    assert!(res.is_inf() && !res.get_sign());
+
+    // Compare to the exact sum in FP256, rounded once.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..1000 {
+        let a = BF16::from_bits([lfsr.get64() & 0xbfff]);
+        let b = BF16::from_bits([lfsr.get64() & 0xbfff]);
+        let c = FP32::from_bits([lfsr.get64() & 0xbfffffff]);
+        if a.is_nan() || b.is_nan() || c.is_nan() {
+            continue;
+        }
+        for rm in [rm, RoundingMode::Positive, RoundingMode::Negative] {
+            let res = BF16::mul_add_widening(a, b, c, rm);
+            let prod = FP256::mul_with_rm(a.cast(), b.cast(), rm);
+            let exact = FP256::add_with_rm(prod, c.cast(), rm);
+            let expected: FP32 = exact.cast_with_rm(rm);
+            assert_eq!(res.to_bits(), expected.to_bits());
+        }
+    }
+
+    // The exact path of the wide formats matches the path through FP256.
+    use RoundingMode::*;
+    for i in 0..3000 {
+        let a = FP32::from_bits([lfsr.get64() & 0xffffffff]);
+        let b = FP32::from_bits([lfsr.get64() & 0xffffffff]);
+        let mut c = FP64::from_bits([lfsr.get64(), 0]);
+        if i % 2 == 0 {
+            // Sums with cancellation.
+            let prod = FP64::mul_with_rm(a.cast(), b.cast(), rm).neg();
+            c = prod + FP64::from_i64(i - 1500) * c.scale(-1100, rm);
+        }
+        for rm in [NearestTiesToEven, NearestTiesToAway, Zero, Positive] {
+            let res = FP32::mul_add_exact(a, b, c, rm);
+            let expected = FP32::mul_add_widening(a, b, c, rm);
+            if expected.is_nan() {
+                assert!(res.is_nan());
+            } else {
+                assert_eq!(res.to_bits(), expected.to_bits());
+            }
+        }
+    }
+    let res = FP16::mul_add_exact(inf, one, FP32::inf(true), rm);
+    assert!(res.is_nan());
+    let res =
+        FP16::mul_add_exact(inf, FP16::smallest(true), FP32::one(true), rm);
+    assert!(res.is_inf() && res.get_sign());
+    let res = FP16::mul_add_exact(one, one, FP32::one(true), rm_neg);
+    assert!(res.is_zero() && res.get_sign());
+    let x = FP16::from_f64(-1.5);
+    let res = FP16::mul_add_exact(x, x, FP32::zero(true), rm);
+    assert_eq!(res.as_f32(), 2.25);
+    let res = FP16::mul_add_exact(x, one, FP32::smallest(false), rm);
+    assert_eq!(res.as_f32(), -1.5);
+
+    // The formats that are wider than FP128.
+    let a = FP256::one(false) + FP256::from_f64(2f64.powi(-130));
+    let res = FP256::mul_add_widening(a, a, FP256::one(true), rm);
+    let low = FP256::from_f64(2f64.powi(-260));
+    assert_eq!(res, FP256::from_f64(2f64.powi(-129)) + low);
+    assert_eq!(a * a - FP256::one(false), FP256::from_f64(2f64.powi(-129)));
+    let a = FP128::largest(false);
+    let res = FP128::mul_add_widening(a, a, FP256::smallest(true), rm);
+    assert_eq!(res, FP256::mul_with_rm(a.cast(), a.cast(), rm));
+    let res = FP128::mul_add_widening(a, a, FP256::smallest(true), Zero);
+    assert!(res < FP256::mul_with_rm(a.cast(), a.cast(), rm));
+    let tiny = FP256::smallest(false);
+    assert!(FP256::mul_add_widening(tiny, tiny, tiny, Positive) > tiny);
+    assert_eq!(FP256::mul_add_widening(tiny, tiny, tiny, rm), tiny);
+}
+
 impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Add
     for Float<EXPONENT, MANTISSA, PARTS>
 {
@@ -653,6 +1165,100 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Div
     }
 }
 
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Rem
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        Self::rem(&self, rhs)
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Neg
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::neg(&self)
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Neg
+    for &Float<EXPONENT, MANTISSA, PARTS>
+{
+    type Output = Float<EXPONENT, MANTISSA, PARTS>;
+
+    fn neg(self) -> Self::Output {
+        Float::neg(self)
+    }
+}
+
+/// Implements the operator `$trait` for the combinations of owned and borrowed
+/// operands, like the operators of the primitive types.
+macro_rules! impl_ref_operator {
+    ($trait:ident, $method:ident) => {
+        impl<
+                'a,
+                const EXPONENT: usize,
+                const MANTISSA: usize,
+                const PARTS: usize,
+            > $trait<&'a Float<EXPONENT, MANTISSA, PARTS>>
+            for Float<EXPONENT, MANTISSA, PARTS>
+        {
+            type Output = Self;
+
+            fn $method(self, rhs: &'a Self) -> Self {
+                $trait::$method(self, *rhs)
+            }
+        }
+
+        impl<
+                'a,
+                const EXPONENT: usize,
+                const MANTISSA: usize,
+                const PARTS: usize,
+            > $trait<Float<EXPONENT, MANTISSA, PARTS>>
+            for &'a Float<EXPONENT, MANTISSA, PARTS>
+        {
+            type Output = Float<EXPONENT, MANTISSA, PARTS>;
+
+            fn $method(
+                self,
+                rhs: Float<EXPONENT, MANTISSA, PARTS>,
+            ) -> Self::Output {
+                $trait::$method(*self, rhs)
+            }
+        }
+
+        impl<
+                'a,
+                'b,
+                const EXPONENT: usize,
+                const MANTISSA: usize,
+                const PARTS: usize,
+            > $trait<&'b Float<EXPONENT, MANTISSA, PARTS>>
+            for &'a Float<EXPONENT, MANTISSA, PARTS>
+        {
+            type Output = Float<EXPONENT, MANTISSA, PARTS>;
+
+            fn $method(
+                self,
+                rhs: &'b Float<EXPONENT, MANTISSA, PARTS>,
+            ) -> Self::Output {
+                $trait::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+impl_ref_operator!(Add, add);
+impl_ref_operator!(Sub, sub);
+impl_ref_operator!(Mul, mul);
+impl_ref_operator!(Div, div);
+impl_ref_operator!(Rem, rem);
+
 #[test]
 fn test_operators() {
     use crate::FP64;
@@ -666,6 +1272,154 @@ fn test_operators() {
     assert_eq!(d.as_f64(), 6.0);
     assert_eq!(e.as_f64(), 16.0);
     assert_eq!(f.as_f64(), 4.0);
+
+    let g = FP64::from_f64(7.5) % FP64::from_f64(2.0);
+    let h = -a;
+    assert_eq!(g.as_f64(), 1.5);
+    assert_eq!(h.as_f64(), -8.0);
+    assert_eq!((-FP64::from_f64(-7.5) % -b).as_f64(), 1.5);
+    assert_eq!((-&b).as_f64(), -2.0);
+    assert!((-FP64::nan(false)).get_sign());
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn test_ref_operators() {
+    use crate::FP64;
+    let a = FP64::from_f32(8.0);
+    let b = FP64::from_f32(2.0);
+    for (x, expected) in [(&a + &b, 10.), (a - &b, 6.), (&a * b, 16.)] {
+        assert_eq!(x.as_f64(), expected);
+    }
+    assert_eq!((&a / &b).as_f64(), 4.0);
+    assert_eq!(&a / &b, a / b);
+    assert_eq!(&a - b, a - b);
+    assert_eq!((&a % &FP64::from_f64(3.0)).as_f64(), 2.0);
+
+    // References from iterators.
+    let vals = [a, b, FP64::from_f64(0.5)];
+    let sum = vals.iter().fold(FP64::zero(false), |acc, x| acc + x);
+    assert_eq!(sum.as_f64(), 10.5);
+    let prod = vals.iter().fold(FP64::one(false), |acc, x| &acc * x);
+    assert_eq!(prod.as_f64(), 8.0);
+}
+
+#[test]
+fn test_op_status() {
+    use crate::{FP32, FP64};
+    use RoundingMode::NearestTiesToEven as rm;
+    let one = FP64::one(false);
+    let zero = FP64::zero(false);
+    let inf = FP64::inf(false);
+    let nan = FP64::nan(false);
+    let three = FP64::from_u64(3);
+    let max = FP64::largest(false);
+    let tiny = FP64::smallest(false);
+    let half = FP64::from_f64(0.5);
+
+    assert_eq!(FP64::add_with_status(one, one, rm).1, OpStatus::OK);
+    let (r, s) = FP64::add_with_status(one, FP64::from_f64(1e-20), rm);
+    assert_eq!((r, s), (one, OpStatus::INEXACT));
+    let (r, s) = FP64::div_with_status(one, three, rm);
+    assert_eq!(r.as_f64(), 1. / 3.);
+    assert_eq!(s, OpStatus::INEXACT);
+
+    // Overflow.
+    let (r, s) = FP64::mul_with_status(max, three, rm);
+    assert!(r.is_inf());
+    assert_eq!(s, OpStatus::OVERFLOW | OpStatus::INEXACT);
+    let (r, s) = FP64::mul_with_status(max, three, RoundingMode::Zero);
+    assert_eq!(r, max);
+    assert!(s.contains(OpStatus::OVERFLOW));
+
+    // Underflow is only reported for inexact results.
+    let (r, s) = FP64::mul_with_status(FP64::smallest_normal(false), half, rm);
+    assert!(!r.is_zero());
+    assert_eq!(s, OpStatus::OK);
+    let (r, s) = FP64::mul_with_status(tiny, half, rm);
+    assert!(r.is_zero());
+    assert_eq!(s, OpStatus::UNDERFLOW | OpStatus::INEXACT);
+    let (r, s) = FP64::div_with_status(tiny, three, RoundingMode::Positive);
+    assert_eq!(r, tiny);
+    assert_eq!(s, OpStatus::UNDERFLOW | OpStatus::INEXACT);
+
+    // Invalid operations and division by zero.
+    let (r, s) = FP64::sub_with_status(inf, inf, rm);
+    assert!(r.is_nan());
+    assert_eq!(s, OpStatus::INVALID_OP);
+    assert_eq!(FP64::mul_with_status(zero, inf, rm).1, OpStatus::INVALID_OP);
+    assert_eq!(
+        FP64::div_with_status(zero, zero, rm).1,
+        OpStatus::INVALID_OP
+    );
+    assert_eq!(FP64::div_with_status(inf, inf, rm).1, OpStatus::INVALID_OP);
+    let (r, s) = FP64::div_with_status(one, zero, rm);
+    assert!(r.is_inf());
+    assert_eq!(s, OpStatus::DIV_BY_ZERO);
+    assert_eq!(FP64::div_with_status(inf, zero, rm).1, OpStatus::OK);
+
+    // NaNs are propagated quietly.
+    assert_eq!(FP64::add_with_status(nan, one, rm).1, OpStatus::OK);
+    assert_eq!(FP64::mul_with_status(nan, zero, rm).1, OpStatus::OK);
+    assert_eq!(FP64::div_with_status(one, nan, rm).1, OpStatus::OK);
+
+    // Conversions.
+    let third = FP64::from_f64(1. / 3.);
+    let (r, s): (FP32, _) = third.cast_with_status(rm);
+    assert_eq!(r.as_f32(), 1. / 3.);
+    assert_eq!(s, OpStatus::INEXACT);
+    let (_, s): (FP32, _) = half.cast_with_status(rm);
+    assert!(s.is_ok());
+    let (r, s): (FP32, _) = FP64::from_f64(1e300).cast_with_status(rm);
+    assert!(r.is_inf());
+    assert_eq!(s, OpStatus::OVERFLOW | OpStatus::INEXACT);
+    let (r, s): (FP32, _) = FP64::from_f64(1e-300).cast_with_status(rm);
+    assert!(r.is_zero());
+    assert_eq!(s, OpStatus::UNDERFLOW | OpStatus::INEXACT);
+
+    // A conversion that only changes the exponent range.
+    let (r, s): (Float<8, 52, 2>, _) =
+        FP64::from_f64(1e300).cast_with_status(rm);
+    assert!(r.is_inf());
+    assert_eq!(s, OpStatus::OVERFLOW | OpStatus::INEXACT);
+}
+
+#[test]
+fn test_ties_to_away() {
+    use crate::{FP16, FP64};
+    use RoundingMode::{NearestTiesToAway as away, NearestTiesToEven as even};
+    let one = FP64::one(false);
+    let half_ulp = FP64::epsilon().scale(-1, even);
+
+    // 1 + ulp/2 is exactly between 1 and the next number.
+    let (r, s) = FP64::add_with_status(one, half_ulp, away);
+    assert_eq!(r, one.next_up());
+    assert_eq!(s, OpStatus::INEXACT);
+    assert_eq!(FP64::add_with_rm(one, half_ulp, even), one);
+    let r = FP64::sub_with_rm(one.neg(), half_ulp, away);
+    assert_eq!(r, one.next_up().neg());
+
+    // 1.5 * (1 + 3ulp) = 1.5 + 4.5ulp is a tie. Division can't produce ties.
+    let a = FP64::from_f64(1.5);
+    let b = one.next_up().next_up().next_up();
+    assert_eq!(FP64::mul_with_rm(a, b, away).as_f64(), 1.500000000000001);
+    assert_eq!(FP64::mul_with_rm(a, b, even).as_f64(), 1.5000000000000009);
+
+    // Casts: 2049 is a tie between 2048 and 2050 in FP16.
+    let x = FP64::from_u64(2049);
+    let r: FP16 = x.cast_with_rm(away);
+    assert_eq!(r.as_f64(), 2050.);
+    let r: FP16 = x.cast_with_rm(even);
+    assert_eq!(r.as_f64(), 2048.);
+    let r: FP16 = x.neg().cast_with_rm(away);
+    assert_eq!(r.as_f64(), -2050.);
+    let r: FP16 = FP64::from_u64(2051).cast_with_rm(away);
+    assert_eq!(r.as_f64(), 2052.);
+
+    // Integer conversions.
+    assert_eq!(FP64::from_f64(2.5).to_i64(away), 3);
+    assert_eq!(FP64::from_f64(2.5).to_i64(even), 2);
+    assert_eq!(FP64::from_f64(-3.5).to_i64(away), -4);
 }
 
 #[test]
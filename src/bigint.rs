@@ -1,14 +1,13 @@
 extern crate alloc;
 
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter, Write};
 use core::ops::{Add, Div, Mul, Sub};
-#[cfg(test)]
-use alloc::vec::Vec;
-use alloc::string::String;
 
 #[cfg(feature = "std")]
-use std::{print, println};
-
+use std::println;
 
 /// Reports the kind of values that are lost when we shift right bits. In some
 /// context this used as the two guard bits.
@@ -51,14 +50,14 @@ impl LossFraction {
 }
 /// This is a fixed-size big int implementation that's used to represent the
 /// significand part of the floating point number.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct BigInt<const PARTS: usize> {
     parts: [u64; PARTS],
 }
 
 impl<const PARTS: usize> BigInt<PARTS> {
     /// Create a new zero big int number.
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         BigInt { parts: [0; PARTS] }
     }
 
@@ -267,7 +266,7 @@ impl<const PARTS: usize> BigInt<PARTS> {
         panic!("Expected a non-zero number");
     }
 
-    pub fn from_parts(parts: &[u64; PARTS]) -> Self {
+    pub const fn from_parts(parts: &[u64; PARTS]) -> Self {
         BigInt { parts: *parts }
     }
 
@@ -490,19 +489,45 @@ impl<const PARTS: usize> BigInt<PARTS> {
         v
     }
 
+    /// \return the integer square root of the number, floor(sqrt(self)).
+    pub fn isqrt(&self) -> Self {
+        if self.is_zero() {
+            return *self;
+        }
+        // Start above the root, at 2^ceil(bits/2). Newton's method on
+        // integers then decreases monotonically until it reaches the root.
+        let mut x = Self::one_hot(self.msb_index().div_ceil(2));
+        loop {
+            let mut y = x + *self / x;
+            y.shift_right(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
     /// \return the word at idx `idx`.
     pub fn get_part(&self, idx: usize) -> u64 {
         self.parts[idx]
     }
 
-    #[cfg(feature = "std")]
-    pub fn dump(&self) {
-        print!("[");
+    /// Writes the parts of the number in binary to `w`, starting at the most
+    /// significant part. This works without the standard library.
+    pub fn write_dump(&self, w: &mut impl Write) -> core::fmt::Result {
+        w.write_char('[')?;
         for i in (0..PARTS).rev() {
             let width = u64::BITS as usize;
-            print!("|{:0width$b}", self.parts[i]);
+            write!(w, "|{:0width$b}", self.parts[i])?;
         }
-        println!("]");
+        w.write_char(']')
+    }
+
+    #[cfg(feature = "std")]
+    pub fn dump(&self) {
+        let mut s = String::new();
+        self.write_dump(&mut s).unwrap();
+        println!("{}", s);
     }
 }
 
@@ -512,6 +537,398 @@ impl<const PARTS: usize> Default for BigInt<PARTS> {
     }
 }
 
+/// Prints the parts in hex, starting at the most significant part, like
+/// "[0x0000000000000001, 0x8000000000000000]".
+impl<const PARTS: usize> Debug for BigInt<PARTS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("[")?;
+        for (i, part) in self.parts.iter().rev().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:#018x}", part)?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// An unsigned integer that grows as needed. The conversions between binary
+/// and decimal numbers scale by powers of ten that don't fit in a BigInt,
+/// like 10^4932 for the largest FP128 numbers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DynBigInt {
+    // The words of the number, starting at the least significant word. There
+    // are no leading zero words, so zero has no words.
+    parts: Vec<u64>,
+}
+
+impl DynBigInt {
+    /// Create a new zero number.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Create a new number with the value 1.
+    pub fn one() -> Self {
+        Self::from_u64(1)
+    }
+
+    /// Create a number with the value `val`.
+    pub fn from_u64(val: u64) -> Self {
+        Self::from_words(alloc::vec![val])
+    }
+
+    /// Create a number with the value `val`.
+    pub fn from_u128(val: u128) -> Self {
+        Self::from_words(alloc::vec![val as u64, (val >> 64) as u64])
+    }
+
+    /// Create a number with the value of `val`.
+    pub fn from_bigint<const P: usize>(val: &BigInt<P>) -> Self {
+        Self::from_words(val.parts.to_vec())
+    }
+
+    fn from_words(parts: Vec<u64>) -> Self {
+        let mut x = DynBigInt { parts };
+        x.trim();
+        x
+    }
+
+    /// Removes the leading zero words.
+    fn trim(&mut self) {
+        while self.parts.last() == Some(&0) {
+            self.parts.pop();
+        }
+    }
+
+    /// Converts the number to a BigInt. The number must fit.
+    pub fn to_bigint<const P: usize>(&self) -> BigInt<P> {
+        debug_assert!(self.parts.len() <= P, "losing information");
+        let mut n = BigInt::<P>::zero();
+        for (to, from) in n.parts.iter_mut().zip(self.parts.iter()) {
+            *to = *from;
+        }
+        n
+    }
+
+    /// Returns the number, which must fit in 64 bits.
+    pub fn as_u64(&self) -> u64 {
+        debug_assert!(self.parts.len() <= 1);
+        self.parts.first().copied().unwrap_or(0)
+    }
+
+    /// \return true if the number is equal to zero.
+    pub fn is_zero(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Returns true if this number is odd.
+    pub fn is_odd(&self) -> bool {
+        self.parts.first().is_some_and(|p| p & 1 == 1)
+    }
+
+    /// Returns the index of the most significant bit, like
+    /// [`BigInt::msb_index`].
+    pub fn msb_index(&self) -> usize {
+        match self.parts.last() {
+            Some(p) => self.parts.len() * 64 - p.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Returns the index of the first '1' in the number. The number must not
+    /// be a zero.
+    pub fn trailing_zeros(&self) -> usize {
+        debug_assert!(!self.is_zero());
+        let i = self.parts.iter().position(|p| *p != 0).unwrap_or(0);
+        i * 64 + self.parts[i].trailing_zeros() as usize
+    }
+
+    /// Flip the `bit_num` bit.
+    pub fn flip_bit(&mut self, bit_num: usize) {
+        let word = bit_num / 64;
+        if word >= self.parts.len() {
+            self.parts.resize(word + 1, 0);
+        }
+        self.parts[word] ^= 1 << (bit_num % 64);
+        self.trim();
+    }
+
+    /// Zero out all of the bits above `bits`.
+    pub fn mask(&mut self, bits: usize) {
+        self.parts.truncate(bits.div_ceil(64));
+        // The last word is partial if the number of bits is not a multiple
+        // of the word size.
+        if let Some(p) = self.parts.get_mut(bits / 64) {
+            *p &= (1 << (bits % 64)) - 1;
+        }
+        self.trim();
+    }
+
+    /// Returns the fractional part that's lost during truncation at `bit`.
+    pub fn get_loss_kind_for_bit(&self, bit: usize) -> LossFraction {
+        let mut a = self.clone();
+        a.mask(bit);
+        if a.is_zero() {
+            return LossFraction::ExactlyZero;
+        }
+        let mut half = Self::zero();
+        half.flip_bit(bit - 1);
+        match a.cmp(&half) {
+            Ordering::Less => LossFraction::LessThanHalf,
+            Ordering::Equal => LossFraction::ExactlyHalf,
+            Ordering::Greater => LossFraction::MoreThanHalf,
+        }
+    }
+
+    /// Shift the bits in the numbers `bits` to the left.
+    pub fn shift_left(&mut self, bits: usize) {
+        if self.is_zero() {
+            return;
+        }
+        let bits_in_word = bits % 64;
+        if bits_in_word != 0 {
+            let mut carry = 0;
+            for p in self.parts.iter_mut() {
+                let next = *p >> (64 - bits_in_word);
+                *p = *p << bits_in_word | carry;
+                carry = next;
+            }
+            self.parts.push(carry);
+        }
+        let words = core::iter::repeat_n(0, bits / 64);
+        self.parts.splice(0..0, words);
+        self.trim();
+    }
+
+    /// Shift the bits in the numbers `bits` to the right.
+    pub fn shift_right(&mut self, bits: usize) {
+        let words = bits / 64;
+        if words >= self.parts.len() {
+            self.parts.clear();
+            return;
+        }
+        self.parts.drain(..words);
+        let bits_in_word = bits % 64;
+        if bits_in_word != 0 {
+            let mut carry = 0;
+            for p in self.parts.iter_mut().rev() {
+                let next = *p << (64 - bits_in_word);
+                *p = *p >> bits_in_word | carry;
+                carry = next;
+            }
+        }
+        self.trim();
+    }
+
+    /// Add `rhs` to self.
+    pub fn inplace_add(&mut self, rhs: &Self) {
+        if self.parts.len() < rhs.parts.len() {
+            self.parts.resize(rhs.parts.len(), 0);
+        }
+        let mut carry = false;
+        for (i, p) in self.parts.iter_mut().enumerate() {
+            let r = rhs.parts.get(i).copied().unwrap_or(0);
+            let first = p.overflowing_add(r);
+            let second = first.0.overflowing_add(carry as u64);
+            carry = first.1 || second.1;
+            *p = second.0;
+        }
+        if carry {
+            self.parts.push(1);
+        }
+    }
+
+    /// Subtract `rhs` from self. The number must not be smaller than `rhs`.
+    pub fn inplace_sub(&mut self, rhs: &Self) {
+        debug_assert!(*self >= *rhs);
+        let mut borrow = false;
+        for (i, p) in self.parts.iter_mut().enumerate() {
+            let r = rhs.parts.get(i).copied().unwrap_or(0);
+            let first = p.overflowing_sub(r);
+            let second = first.0.overflowing_sub(borrow as u64);
+            borrow = first.1 || second.1;
+            *p = second.0;
+        }
+        self.trim();
+    }
+
+    /// Multiply `rhs` to self.
+    pub fn inplace_mul(&mut self, rhs: &Self) {
+        if self.is_zero() || rhs.is_zero() {
+            self.parts.clear();
+            return;
+        }
+        let mut parts = alloc::vec![0; self.parts.len() + rhs.parts.len()];
+        for (i, a) in self.parts.iter().enumerate() {
+            let mut carry = 0;
+            for (j, b) in rhs.parts.iter().enumerate() {
+                let t = *a as u128 * *b as u128 + parts[i + j] as u128 + carry;
+                parts[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            parts[i + rhs.parts.len()] = carry as u64;
+        }
+        self.parts = parts;
+        self.trim();
+    }
+
+    /// Divide self by `divisor`, and return the reminder. Dividing by a
+    /// multi-word number takes a step for each bit of the quotient, which is
+    /// short in the conversions.
+    pub fn inplace_div(&mut self, divisor: &Self) -> Self {
+        assert!(!divisor.is_zero(), "division by zero");
+        if divisor.parts.len() == 1 {
+            let d = divisor.parts[0] as u128;
+            let mut rem = 0;
+            for p in self.parts.iter_mut().rev() {
+                let cur = rem << 64 | *p as u128;
+                *p = (cur / d) as u64;
+                rem = cur % d;
+            }
+            self.trim();
+            return Self::from_u64(rem as u64);
+        }
+
+        let mut rem = core::mem::take(self);
+        if *divisor > rem {
+            return rem;
+        }
+        // Align the first bit of the divisor with the first bit of the
+        // dividend, and perform the long division.
+        let bits = rem.msb_index() - divisor.msb_index();
+        let mut divisor = divisor.clone();
+        divisor.shift_left(bits);
+        for i in (0..bits + 1).rev() {
+            if rem >= divisor {
+                rem.inplace_sub(&divisor);
+                self.flip_bit(i);
+            }
+            divisor.shift_right(1);
+        }
+        rem
+    }
+
+    /// \return raise this number to the power of `exp`.
+    pub fn powi(&self, mut exp: u64) -> Self {
+        let mut v = Self::one();
+        let mut base = self.clone();
+        loop {
+            if exp & 0x1 == 1 {
+                v.inplace_mul(&base);
+            }
+            exp >>= 1;
+            if exp == 0 {
+                break;
+            }
+            let b = base.clone();
+            base.inplace_mul(&b);
+        }
+        v
+    }
+}
+
+impl PartialOrd for DynBigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DynBigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.parts.len().cmp(&other.parts.len());
+        len.then_with(|| self.parts.iter().rev().cmp(other.parts.iter().rev()))
+    }
+}
+
+#[test]
+fn test_dyn_bigint() {
+    use super::utils::Lfsr;
+
+    // Compare the operations with the operations of BigInt.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..200 {
+        let mut a = BigInt::<8>::zero();
+        let mut b = BigInt::<8>::zero();
+        for i in 0..3 {
+            a.parts[i] = lfsr.get64();
+            b.parts[i] = lfsr.get64() >> (lfsr.get64() % 64);
+        }
+        b.parts[0] |= 1;
+        b.parts[2] = 0;
+        let (x, y) = (DynBigInt::from_bigint(&a), DynBigInt::from_bigint(&b));
+        assert_eq!(x.msb_index(), a.msb_index());
+        assert_eq!(x.cmp(&y), a.cmp(&b));
+
+        let mut sum = x.clone();
+        sum.inplace_add(&y);
+        assert_eq!(sum.to_bigint::<8>(), a + b);
+        let mut prod = x.clone();
+        prod.inplace_mul(&y);
+        assert_eq!(prod.to_bigint::<8>(), a * b);
+        let mut quot = x.clone();
+        let rem = quot.inplace_div(&y);
+        let mut q = a;
+        let r = q.inplace_div(b);
+        assert_eq!((quot.to_bigint::<8>(), rem.to_bigint::<8>()), (q, r));
+        let mut quot = x.clone();
+        let rem = quot.inplace_div(&DynBigInt::from_u64(b.parts[0] | 1));
+        let mut q = a;
+        let r = q.inplace_div(BigInt::from_u64(b.parts[0] | 1));
+        assert_eq!((quot.to_bigint::<8>(), rem.to_bigint::<8>()), (q, r));
+
+        let shift = (lfsr.get64() % 200) as usize;
+        let (mut l, mut r) = (x.clone(), x.clone());
+        let (mut l2, mut r2) = (a, a);
+        l.shift_left(shift);
+        l2.shift_left(shift);
+        r.shift_right(shift);
+        r2.shift_right(shift);
+        assert_eq!(l.to_bigint::<8>(), l2);
+        assert_eq!(r.to_bigint::<8>(), r2);
+        assert!(l.is_zero() || l.trailing_zeros() >= shift);
+        let mut m = x.clone();
+        m.mask(shift);
+        let mut m2 = a;
+        m2.mask(shift);
+        assert_eq!(m.to_bigint::<8>(), m2);
+        let kind = |l: LossFraction| {
+            (l.is_exactly_zero(), l.is_lt_half(), l.is_exactly_half())
+        };
+        let loss = x.get_loss_kind_for_bit(shift);
+        assert_eq!(kind(loss), kind(a.get_loss_kind_for_bit(shift)));
+    }
+
+    // Numbers grow past any fixed size.
+    let five = DynBigInt::from_u64(5);
+    let mut x = five.powi(10000);
+    assert_eq!(x.msb_index(), 23220);
+    let rem = x.inplace_div(&five.powi(9999));
+    assert!(rem.is_zero());
+    assert_eq!(x.as_u64(), 5);
+    let mut x = DynBigInt::one();
+    x.shift_left(100000);
+    x.shift_right(99999);
+    assert_eq!(x.as_u64(), 2);
+}
+
+#[test]
+fn test_isqrt() {
+    for i in 0..1000_u64 {
+        let x = BigInt::<2>::from_u64(i * i);
+        assert_eq!(x.isqrt().as_u64(), i);
+        if i > 0 {
+            let y = BigInt::<2>::from_u64(i * i - 1);
+            assert_eq!(y.isqrt().as_u64(), i - 1);
+        }
+    }
+    let x = BigInt::<4>::one_hot(201);
+    let root = x.isqrt();
+    assert!(root * root <= x);
+    let next = root + BigInt::one();
+    assert!(next * next > x);
+}
+
 #[test]
 fn test_powi5() {
     let lookup = [1, 5, 25, 125, 625, 3125, 15625, 78125];
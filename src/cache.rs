@@ -0,0 +1,70 @@
+//! A cache for values that are expensive to compute, like constants that are
+//! needed by many functions. Rust does not have generic statics, so the values
+//! of all of the float types are stored in one table, keyed by the type and
+//! the name of the value. The cache requires the standard library, and without
+//! it the values are recomputed on every call.
+
+#[cfg(feature = "std")]
+use std::{
+    any::{Any, TypeId},
+    boxed::Box,
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+#[cfg(feature = "std")]
+type Table = HashMap<(TypeId, &'static str), Box<dyn Any + Send + Sync>>;
+
+/// Returns the value named `name` of type T, and calls `compute` to create it
+/// the first time that it is requested.
+#[cfg(feature = "std")]
+pub(crate) fn memoize<T, F>(name: &'static str, compute: F) -> T
+where
+    T: Copy + Send + Sync + 'static,
+    F: FnOnce() -> T,
+{
+    static TABLE: OnceLock<Mutex<Table>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| Mutex::new(HashMap::new()));
+    let key = (TypeId::of::<T>(), name);
+    if let Some(val) = table.lock().unwrap().get(&key) {
+        return *val.downcast_ref::<T>().unwrap();
+    }
+
+    // Don't hold the lock while computing the value, because the computation
+    // may need other cached values.
+    let val = compute();
+    table.lock().unwrap().insert(key, Box::new(val));
+    val
+}
+
+/// Returns the value that `compute` creates. Without the standard library
+/// there is no cache.
+#[cfg(not(feature = "std"))]
+pub(crate) fn memoize<T, F>(_name: &'static str, compute: F) -> T
+where
+    T: Copy + Send + Sync + 'static,
+    F: FnOnce() -> T,
+{
+    compute()
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_memoize() {
+    use crate::{FP128, FP64};
+    let mut calls = 0;
+    let a = memoize("test_memoize", || {
+        calls += 1;
+        FP64::from_u64(3)
+    });
+    let b = memoize("test_memoize", || {
+        calls += 1;
+        FP64::from_u64(4)
+    });
+    assert_eq!(calls, 1);
+    assert_eq!(a, b);
+
+    // Values of different types are stored separately.
+    let c = memoize("test_memoize", || FP128::from_u64(5));
+    assert_eq!(c.as_f64(), 5.);
+}
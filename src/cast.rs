@@ -1,7 +1,11 @@
 use super::bigint::BigInt;
 use super::bigint::LossFraction;
 use super::float::{self, Category};
-use super::float::{Float, RoundingMode, FP32, FP64};
+use super::float::{
+    ArithError, Float, OpStatus, RoundingMode, TryFromFloatError, BF16, FP32,
+    FP64, FP80, TF32,
+};
+#[cfg(test)]
 use super::utils;
 use super::utils::mask;
 
@@ -29,151 +33,465 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         Self::from_u64(val as u64)
     }
 
-    /// Converts and returns the rounded integral part.
-    pub fn to_i64(&self, rm: RoundingMode) -> i64 {
-        if self.is_nan() || self.is_zero() {
-            return 0;
+    /// Load the integer `val` into the float. Notice that the number may
+    /// overflow, or rounded to the nearest even integer.
+    pub fn from_u128(val: u128) -> Self {
+        if let Ok(val) = u64::try_from(val) {
+            return Self::from_u64(val);
         }
 
-        if self.is_inf() {
-            if self.get_sign() {
-                return i64::MIN;
-            } else {
-                return i64::MAX;
+        if PARTS >= 2 {
+            let val = BigInt::from_u128(val);
+            let mut a = Self::new(false, MANTISSA as i64, val);
+            let rm = RoundingMode::NearestTiesToEven;
+            a.normalize(rm, LossFraction::ExactlyZero);
+            return a;
+        }
+
+        // The mantissa of narrow formats can't hold 128 bits. Keep the top 64
+        // bits of the integer, and round with the loss of the low bits. The
+        // mantissa of these formats is narrower than 64 bits.
+        let shift = 64 - val.leading_zeros() as usize;
+        let loss = BigInt::<2>::from_u128(val).get_loss_kind_for_bit(shift);
+        let top = BigInt::from_u64((val >> shift) as u64);
+        let mut a = Self::new(false, (MANTISSA + shift) as i64, top);
+        a.normalize(RoundingMode::NearestTiesToEven, loss);
+        a
+    }
+
+    /// Load the integer `val` into the float. Notice that the number may
+    /// overflow, or rounded to the nearest even integer.
+    pub fn from_i128(val: i128) -> Self {
+        let mut a = Self::from_u128(val.unsigned_abs());
+        a.set_sign(val < 0);
+        a
+    }
+
+    /// Converts the ratio `numerator / denominator` to the nearest float,
+    /// rounded with the rounding mode `rm`. A zero denominator returns
+    /// infinity with the sign of the numerator, or NaN if the numerator is
+    /// also zero.
+    pub fn from_rational(
+        numerator: i128,
+        denominator: i128,
+        rm: RoundingMode,
+    ) -> Self {
+        let sign = (numerator < 0) != (denominator < 0);
+        let n = numerator.unsigned_abs();
+        let d = denominator.unsigned_abs();
+        if d == 0 {
+            if n == 0 {
+                return Self::nan(false);
             }
+            return Self::inf(numerator < 0);
+        }
+        if n == 0 {
+            return Self::zero(sign);
         }
 
-        let val = self.convert_normal_to_integer(rm);
-        if self.get_sign() {
-            -(val.as_u64() as i64)
-        } else {
-            val.as_u64() as i64
+        // Long division, one bit at a time. Collect two guard bits beyond the
+        // precision, and fold the remainder into a sticky bit, that is all
+        // that rounding needs to know about the rest of the quotient. The
+        // remainder is smaller than `d`, so doubling it can't overflow.
+        let bits = Self::get_precision() + 2;
+        let mut mantissa = BigInt::zero();
+        let mut collected = 0;
+        let mut rem: u128 = 0;
+        // The weight of the next quotient bit is 2^pos.
+        let mut pos: i64 = 127;
+        while collected < bits {
+            let next = if pos >= 0 { (n >> pos) & 1 } else { 0 };
+            rem = (rem << 1) | next;
+            let bit = rem >= d;
+            if bit {
+                rem -= d;
+            }
+            if collected > 0 || bit {
+                mantissa.shift_left(1);
+                if bit {
+                    mantissa.flip_bit(0);
+                }
+                collected += 1;
+            }
+            pos -= 1;
         }
-    }
 
-    /// Returns a value that is rounded to the nearest integer that's not larger
-    /// in magnitude than this float.
-    pub fn trunc(&self) -> Self {
-        // Only handle normal numbers (don't do anything to NaN, Inf, Zero).
-        if !self.is_normal() {
-            return *self;
+        // The numerator bits that were not consumed are part of the
+        // remainder.
+        let rest = pos >= 0 && n & ((1 << (pos + 1)) - 1) != 0;
+        mantissa.shift_left(1);
+        if rem != 0 || rest {
+            mantissa.flip_bit(0);
         }
+        let mut a = Self::new(sign, MANTISSA as i64 + pos, mantissa);
+        a.normalize(rm, LossFraction::ExactlyZero);
+        a
+    }
 
-        let exp = self.get_exp();
+    /// Returns the exact value of the float as a ratio of two integers, like
+    /// Python's `float.as_integer_ratio`. Returns the sign, the numerator and
+    /// the denominator, which is a power of two, in lowest terms. Returns None
+    /// for infinity and NaN, and if the integers don't fit in `P` parts.
+    pub fn as_integer_ratio<const P: usize>(
+        &self,
+    ) -> Option<(bool, BigInt<P>, BigInt<P>)> {
+        if !self.is_finite() {
+            return None;
+        }
+        let sign = self.get_sign();
+        if self.is_zero() {
+            return Some((sign, BigInt::zero(), BigInt::one()));
+        }
 
-        if exp > MANTISSA as i64 {
-            // Already an integer.
-            return *self;
+        let mut mantissa = self.get_mantissa();
+        let zeros = mantissa.trailing_zeros();
+        mantissa.shift_right(zeros);
+        let exp = self.get_exp() - MANTISSA as i64 + zeros as i64;
+        let bits = P * 64;
+        let shift = exp.unsigned_abs() as usize;
+        if mantissa.msb_index() > bits {
+            return None;
         }
 
-        // Numbers that are smaller than 1 are rounded to zero.
-        if exp < -1 {
-            return Self::zero(self.get_sign());
+        let mut num: BigInt<P> = mantissa.cast();
+        let mut den = BigInt::one();
+        if exp >= 0 {
+            if num.msb_index() + shift > bits {
+                return None;
+            }
+            num.shift_left(shift);
+        } else {
+            if shift >= bits {
+                return None;
+            }
+            den = BigInt::one_hot(shift);
         }
+        Some((sign, num, den))
+    }
 
-        // This is a fraction. Figure out which bits represent values over one
-        // and clear out the values that represent the fraction.
-        let trim = (MANTISSA as i64 - exp) as usize;
-        let mut m = self.get_mantissa();
-        m.shift_right(trim);
-        m.shift_left(trim);
-        Self::new(self.get_sign(), self.get_exp(), m)
+    /// Converts and returns the rounded integral part. Numbers that are out
+    /// of range, and infinities, saturate, and NaN is converted to zero.
+    pub fn to_i64(&self, rm: RoundingMode) -> i64 {
+        self.to_i64_with_status(rm).0
     }
 
-    /// Returns a number rounded to nearest integer, away from zero.
-    pub fn round(&self) -> Self {
-        use crate::float::shift_right_with_loss;
+    /// Converts and returns the rounded integral part. Numbers that are out
+    /// of range, and infinities, saturate, and NaN is converted to zero.
+    pub fn to_u64(&self, rm: RoundingMode) -> u64 {
+        self.to_u64_with_status(rm).0
+    }
 
-        // Only handle normal numbers (don't do anything to NaN, Inf, Zero).
-        if !self.is_normal() {
-            return *self;
-        }
+    /// Converts and returns the rounded integral part. Numbers that are out
+    /// of range, and infinities, saturate, and NaN is converted to zero.
+    pub fn to_i128(&self, rm: RoundingMode) -> i128 {
+        self.to_i128_with_status(rm).0
+    }
 
-        let exp = self.get_exp();
+    /// Converts and returns the rounded integral part. Numbers that are out
+    /// of range, and infinities, saturate, and NaN is converted to zero.
+    pub fn to_u128(&self, rm: RoundingMode) -> u128 {
+        self.to_u128_with_status(rm).0
+    }
 
-        if exp > MANTISSA as i64 {
-            // Already an integer.
-            return *self;
-        }
+    /// Converts the number to an integer, like `to_i64`, and returns the
+    /// result together with the exception flags. The invalid flag is raised
+    /// for NaN and for numbers that are out of range, and the inexact flag is
+    /// raised for numbers that had a fraction.
+    pub fn to_i64_with_status(&self, rm: RoundingMode) -> (i64, OpStatus) {
+        let limits = (1 << 63, i64::MAX as u128);
+        let (val, status) = self.round_to_integer(rm, limits);
+        let val = match val {
+            Some(val) if self.get_sign() => (val as i64).wrapping_neg(),
+            Some(val) => val as i64,
+            None if self.is_nan() => 0,
+            None if self.get_sign() => i64::MIN,
+            None => i64::MAX,
+        };
+        (val, status)
+    }
+
+    /// Converts the number to an integer, like `to_u64`, and returns the
+    /// result together with the exception flags. See `to_i64_with_status`.
+    pub fn to_u64_with_status(&self, rm: RoundingMode) -> (u64, OpStatus) {
+        let limits = (0, u64::MAX as u128);
+        let (val, status) = self.round_to_integer(rm, limits);
+        let val = match val {
+            Some(val) => val as u64,
+            None if self.is_nan() || self.get_sign() => 0,
+            None => u64::MAX,
+        };
+        (val, status)
+    }
+
+    /// Converts the number to an integer, like `to_i128`, and returns the
+    /// result together with the exception flags. See `to_i64_with_status`.
+    pub fn to_i128_with_status(&self, rm: RoundingMode) -> (i128, OpStatus) {
+        let limits = (1 << 127, i128::MAX as u128);
+        let (val, status) = self.round_to_integer(rm, limits);
+        let val = match val {
+            Some(val) if self.get_sign() => (val as i128).wrapping_neg(),
+            Some(val) => val as i128,
+            None if self.is_nan() => 0,
+            None if self.get_sign() => i128::MIN,
+            None => i128::MAX,
+        };
+        (val, status)
+    }
 
-        // Numbers that are between 0.5 and 1.0 are rounded to 1.0.
-        if exp == -1 {
-            return Self::one(self.get_sign());
+    /// Converts the number to an integer, like `to_u128`, and returns the
+    /// result together with the exception flags. See `to_i64_with_status`.
+    pub fn to_u128_with_status(&self, rm: RoundingMode) -> (u128, OpStatus) {
+        let limits = (0, u128::MAX);
+        let (val, status) = self.round_to_integer(rm, limits);
+        let val = match val {
+            Some(val) => val,
+            None if self.is_nan() || self.get_sign() => 0,
+            None => u128::MAX,
+        };
+        (val, status)
+    }
+
+    /// Converts the number to a big integer, by truncating the fraction.
+    /// Returns the sign and the magnitude of the integer, together with the
+    /// exception flags. The invalid flag is raised for NaN, infinity and
+    /// integers that don't fit in `P` parts, and the inexact flag is raised
+    /// for numbers that had a fraction.
+    pub fn to_bigint<const P: usize>(
+        &self,
+    ) -> (Option<(bool, BigInt<P>)>, OpStatus) {
+        let sign = self.get_sign();
+        match self.get_category() {
+            Category::NaN | Category::Infinity => {
+                return (None, OpStatus::INVALID_OP);
+            }
+            Category::Zero => {
+                return (Some((sign, BigInt::zero())), OpStatus::OK)
+            }
+            Category::Normal => {}
         }
 
-        // Numbers below 0.5 are rounded to zero.
-        if exp < -2 {
-            return Self::zero(self.get_sign());
+        let mut mantissa = self.get_mantissa();
+        let i_exp = self.get_exp() - MANTISSA as i64;
+        let mut loss = LossFraction::ExactlyZero;
+        if i_exp < 0 {
+            (mantissa, loss) =
+                float::shift_right_with_loss(mantissa, -i_exp as u64);
+        }
+        let shift = i_exp.max(0) as usize;
+        if mantissa.msb_index() + shift > P * 64 {
+            return (None, OpStatus::INVALID_OP);
         }
 
-        // This is a fraction. Figure out which bits represent values over one
-        // and clear out the values that represent the fraction.
-        let trim = (MANTISSA as i64 - exp) as usize;
-        let (mut m, loss) =
-            shift_right_with_loss(self.get_mantissa(), trim as u64);
-        m.shift_left(trim);
-        let t = Self::new(self.get_sign(), self.get_exp(), m);
-
-        if loss.is_lt_half() {
-            t
-        } else if self.get_sign() {
-            t - Self::one(false)
+        let mut val: BigInt<P> = mantissa.cast();
+        val.shift_left(shift);
+        let status = if loss.is_exactly_zero() {
+            OpStatus::OK
         } else {
-            t + Self::one(false)
-        }
+            OpStatus::INEXACT
+        };
+        (Some((sign, val)), status)
     }
 
-    fn convert_normal_to_integer(&self, rm: RoundingMode) -> BigInt<PARTS> {
-        // We are converting to integer, so set the center point of the exponent
-        // to the lsb instead of the msb.
-        let i_exp = self.get_exp() - MANTISSA as i64;
-        if i_exp < 0 {
-            let (mut m, loss) = float::shift_right_with_loss(
+    /// Implements the IEEE 754 operation 'convertToInteger'. Rounds the
+    /// number to an integer with the rounding mode `rm`, and returns its
+    /// magnitude if it is in the range of the integer type. The `limits` are
+    /// the largest magnitudes of the negative and of the positive integers.
+    pub(crate) fn round_to_integer(
+        &self,
+        rm: RoundingMode,
+        limits: (u128, u128),
+    ) -> (Option<u128>, OpStatus) {
+        let limit = if self.get_sign() { limits.0 } else { limits.1 };
+        match self.get_category() {
+            Category::NaN | Category::Infinity => {
+                return (None, OpStatus::INVALID_OP);
+            }
+            Category::Zero => return (Some(0), OpStatus::OK),
+            Category::Normal => {}
+        }
+
+        // Numbers at or above 2^128 are out of the range of all of the
+        // integer types.
+        let exp = self.get_exp();
+        if exp >= 128 {
+            return (None, OpStatus::INVALID_OP);
+        }
+
+        // We are converting to integer, so set the center point of the
+        // exponent to the lsb instead of the msb.
+        let i_exp = exp - MANTISSA as i64;
+        let (val, loss) = if i_exp >= 0 {
+            let val = self.get_mantissa().as_u128() << i_exp;
+            (Some(val), LossFraction::ExactlyZero)
+        } else {
+            let (m, loss) = float::shift_right_with_loss(
                 self.get_mantissa(),
                 -i_exp as u64,
             );
 
-            if self.need_round_away_from_zero(rm, loss) {
-                let _ = m.inplace_add(&BigInt::one());
+            // The mantissa of 't' holds the integer, which decides the
+            // rounding of ties to even.
+            let t = Self::new(self.get_sign(), MANTISSA as i64, m);
+            let mut val = Some(m.as_u128());
+            if !loss.is_exactly_zero() && t.need_round_away_from_zero(rm, loss)
+            {
+                val = val.and_then(|v| v.checked_add(1));
+            }
+            (val, loss)
+        };
+
+        match val {
+            Some(val) if val <= limit => {
+                if loss.is_exactly_zero() {
+                    (Some(val), OpStatus::OK)
+                } else {
+                    (Some(val), OpStatus::INEXACT)
+                }
             }
-            m
+            _ => (None, OpStatus::INVALID_OP),
+        }
+    }
+
+    /// Rounds the normal number to an integral value, using the rounding mode
+    /// `rm`. The fraction is cleared by masking off the low bits of the
+    /// mantissa. Returns the result and the kind of loss that the fraction
+    /// represents.
+    fn round_to_integral_with_loss(
+        &self,
+        rm: RoundingMode,
+    ) -> (Self, LossFraction) {
+        use crate::float::shift_right_with_loss;
+        debug_assert!(self.is_normal());
+
+        let exp = self.get_exp();
+        let sign = self.get_sign();
+
+        if exp > MANTISSA as i64 {
+            // Already an integer.
+            return (*self, LossFraction::ExactlyZero);
+        }
+
+        // This is a fraction. Figure out which bits represent values over one
+        // and drop the values that represent the fraction. Numbers below 0.5
+        // don't have any integral bits.
+        let (integer, loss) = if exp < -1 {
+            (BigInt::zero(), LossFraction::LessThanHalf)
         } else {
-            let mut m = self.get_mantissa();
-            m.shift_left(i_exp as usize);
-            m
+            let trim = (MANTISSA as i64 - exp) as u64;
+            shift_right_with_loss(self.get_mantissa(), trim)
+        };
+
+        // The mantissa holds the integral value, which decides the rounding
+        // of ties to even.
+        let mut t = Self::new(sign, MANTISSA as i64, integer);
+        let away =
+            !loss.is_exactly_zero() && t.need_round_away_from_zero(rm, loss);
+        t.normalize(RoundingMode::NearestTiesToEven, LossFraction::ExactlyZero);
+        if away {
+            t = t + Self::one(sign);
         }
+        (t, loss)
     }
 
-    fn from_bits(float: u64) -> Self {
-        // Extract the biased exponent (wipe the sign and mantissa).
-        let biased_exp = ((float >> MANTISSA) & mask(EXPONENT) as u64) as i64;
-        // Wipe the original exponent and mantissa.
-        let sign = (float >> (EXPONENT + MANTISSA)) & 1;
-        // Wipe the sign and exponent.
-        let mut mantissa = float & mask(MANTISSA) as u64;
+    /// Returns a value that is rounded to the nearest integer that's not larger
+    /// in magnitude than this float.
+    pub fn trunc(&self) -> Self {
+        // Only handle normal numbers (don't do anything to NaN, Inf, Zero).
+        if !self.is_normal() {
+            return *self;
+        }
+        self.round_to_integral_with_loss(RoundingMode::Zero).0
+    }
 
-        let sign = sign == 1;
+    /// Returns a number rounded to nearest integer, away from zero.
+    pub fn round(&self) -> Self {
+        // Only handle normal numbers (don't do anything to NaN, Inf, Zero).
+        if !self.is_normal() {
+            return *self;
+        }
+        self.round_to_integral_with_loss(RoundingMode::NearestTiesToAway)
+            .0
+    }
 
-        // Check for NaN/Inf
-        if biased_exp == mask(EXPONENT) as i64 {
-            if mantissa == 0 {
-                return Self::inf(sign);
-            }
-            return Self::nan(sign);
+    /// Returns the largest integer that is less than or equal to the number.
+    pub fn floor(&self) -> Self {
+        // Only handle normal numbers (don't do anything to NaN, Inf, Zero).
+        if !self.is_normal() {
+            return *self;
         }
+        self.round_to_integral_with_loss(RoundingMode::Negative).0
+    }
 
-        let mut exp = biased_exp - Self::get_bias();
+    /// Returns the smallest integer that is greater than or equal to the
+    /// number.
+    pub fn ceil(&self) -> Self {
+        // Only handle normal numbers (don't do anything to NaN, Inf, Zero).
+        if !self.is_normal() {
+            return *self;
+        }
+        self.round_to_integral_with_loss(RoundingMode::Positive).0
+    }
 
-        // Add the implicit bit for normal numbers.
-        if biased_exp != 0 {
-            mantissa += 1u64 << MANTISSA;
+    /// Returns a number rounded to nearest integer, with ties rounded to the
+    /// even integer.
+    pub fn round_ties_even(&self) -> Self {
+        self.round_to_integral_exact(RoundingMode::NearestTiesToEven)
+            .0
+    }
+
+    /// Implements the IEEE 754 operation 'roundToIntegralExact'. Rounds the
+    /// number to an integral value using the rounding mode `rm`, and returns
+    /// the result and a flag that is set if the result is inexact (the
+    /// number had a fraction). NaN, Inf and Zero are returned as is.
+    pub fn round_to_integral_exact(&self, rm: RoundingMode) -> (Self, bool) {
+        if !self.is_normal() {
+            return (*self, false);
+        }
+        let (t, loss) = self.round_to_integral_with_loss(rm);
+        (t, !loss.is_exactly_zero())
+    }
+
+    pub(crate) fn from_native_float(float: u64) -> Self {
+        Self::from_ieee_bits(BigInt::from_u64(float))
+    }
+
+    /// Loads a float from the bits of its IEEE754 interchange encoding. The
+    /// encoding of a format with wide mantissas, like FP256, does not fit in
+    /// a native integer, so the bits are stored in a BigInt.
+    pub fn from_ieee_bits(bits: BigInt<PARTS>) -> Self {
+        Self::from_bits(core::array::from_fn(|i| bits.get_part(i)))
+    }
+
+    /// Cast to another float, if the number can be represented exactly in the
+    /// other format. Returns an error that describes the loss otherwise.
+    pub fn try_cast<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Result<Float<E, M, P>, ArithError> {
+        let rm = RoundingMode::NearestTiesToEven;
+        let (x, status) = self.cast_with_status(rm);
+        if status.contains(OpStatus::OVERFLOW) {
+            Err(ArithError::Overflow)
+        } else if status.contains(OpStatus::UNDERFLOW) {
+            Err(ArithError::Underflow)
+        } else if status.contains(OpStatus::INEXACT) {
+            Err(ArithError::Inexact)
         } else {
-            // Handle denormals, adjust the exponent to the legal range.
-            exp += 1;
+            Ok(x)
         }
+    }
 
-        let mantissa = BigInt::from_u64(mantissa);
-        Self::new(sign, exp, mantissa)
+    /// Cast to another float, and convert finite numbers that overflow to the
+    /// largest finite number, instead of to infinity. Infinities and NaN are
+    /// preserved. This is the conversion that is used for quantization. See
+    /// `E4M3::from_float` for formats without infinities.
+    pub fn cast_saturating<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        let x: Float<E, M, P> = self.cast();
+        if x.is_inf() && !self.is_inf() {
+            return Float::largest(self.get_sign());
+        }
+        x
     }
 
     /// Cast to another float using the rounding mode `rm`.
@@ -181,6 +499,42 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         &self,
         rm: RoundingMode,
     ) -> Float<E, M, P> {
+        self.cast_with_status(rm).0
+    }
+
+    /// Cast to another float using the rounding mode `rm`, and returns the
+    /// result together with the exception flags that the conversion raised.
+    /// The conversion is exact if the INEXACT flag is not raised, which makes
+    /// it possible to emulate conversion instructions with directed rounding.
+    ///```
+    ///    use arpfloat::{OpStatus, RoundingMode, FP16, FP32};
+    ///    let x = FP32::from_f64(2049.);
+    ///    let rm = RoundingMode::Positive;
+    ///    let (y, status): (FP16, _) = x.cast_with_status(rm);
+    ///    assert_eq!(y.as_f64(), 2050.);
+    ///    assert!(status.contains(OpStatus::INEXACT));
+    ///    let (y, status): (FP16, _) = y.cast_with_status(RoundingMode::Zero);
+    ///    assert_eq!(y.as_f64(), 2050.);
+    ///    assert!(status.is_ok());
+    ///```
+    pub fn cast_with_status<const E: usize, const M: usize, const P: usize>(
+        &self,
+        rm: RoundingMode,
+    ) -> (Float<E, M, P>, OpStatus) {
+        if let Some(mut payload) = self.get_nan_payload() {
+            // Keep the most significant bits of the payload, like hardware
+            // conversions do.
+            if MANTISSA > M {
+                payload.shift_right(MANTISSA - M);
+            }
+            let mut payload: BigInt<P> = payload.cast();
+            if M > MANTISSA {
+                payload.shift_left(M - MANTISSA);
+            }
+            let nan = Float::nan_with_payload(self.get_sign(), payload);
+            return (nan, OpStatus::OK);
+        }
+
         let mut loss = LossFraction::ExactlyZero;
         let exp_delta = MANTISSA as i64 - M as i64;
         let mut temp = *self;
@@ -198,10 +552,11 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             temp.get_category(),
         );
         // Don't normalize if this is a nop conversion.
-        if E != EXPONENT && M != MANTISSA {
-            x.normalize(rm, loss);
+        let mut status = OpStatus::OK;
+        if E != EXPONENT || M != MANTISSA {
+            status = x.normalize(rm, loss);
         }
-        x
+        (x, status)
     }
     /// Convert from one float format to another.
     pub fn cast<const E: usize, const M: usize, const P: usize>(
@@ -210,44 +565,172 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         self.cast_with_rm(RoundingMode::NearestTiesToEven)
     }
 
-    fn as_native_float(&self) -> u64 {
+    pub(crate) fn as_native_float(&self) -> u64 {
+        self.as_ieee_bits().as_u64()
+    }
+
+    /// Returns the IEEE754 interchange encoding of the float, as 64-bit words
+    /// with the least significant word first. The encoding uses the low
+    /// 1 + EXPONENT + MANTISSA bits, and the other bits are zero.
+    ///```
+    ///    use arpfloat::FP128;
+    ///    let bits = FP128::from_u64(1).to_bits();
+    ///    assert_eq!(bits, [0, 0x3fff_0000_0000_0000, 0, 0]);
+    ///    assert_eq!(FP128::from_bits(bits), FP128::from_u64(1));
+    ///```
+    pub fn to_bits(&self) -> [u64; PARTS] {
+        let bits = self.as_ieee_bits();
+        core::array::from_fn(|i| bits.get_part(i))
+    }
+
+    /// Loads a float from the IEEE754 interchange encoding in `parts`, with
+    /// the least significant word first. See `to_bits`. This is a const
+    /// function, so floats can be loaded in constants.
+    ///```
+    ///    use arpfloat::FP16;
+    ///    const ONE: FP16 = FP16::from_bits([0x3c00]);
+    ///    assert_eq!(ONE, FP16::from_u64(1));
+    ///```
+    pub const fn from_bits(parts: [u64; PARTS]) -> Self {
+        assert!(1 + EXPONENT + MANTISSA <= PARTS * 64);
+        // Extract the sign, the biased exponent and the mantissa. The fields
+        // are read from the words directly, because the BigInt operations
+        // can't be used in constants.
+        let biased_exp = Self::get_bit_field(&parts, MANTISSA, EXPONENT);
+        let sign = Self::get_bit_field(&parts, MANTISSA + EXPONENT, 1) == 1;
+        let mut mantissa = [0; PARTS];
+        let mut is_zero = true;
+        let mut i = 0;
+        while i < PARTS {
+            if (i + 1) * 64 <= MANTISSA {
+                mantissa[i] = parts[i];
+            } else if i * 64 < MANTISSA {
+                mantissa[i] = parts[i] & (u64::MAX >> (64 - MANTISSA % 64));
+            }
+            is_zero &= mantissa[i] == 0;
+            i += 1;
+        }
+
+        // Check for NaN/Inf. Signaling NaNs become quiet, and the payload is
+        // stored below the quiet bit.
+        let zero = BigInt::zero();
+        if biased_exp == (1 << EXPONENT) - 1 {
+            if is_zero {
+                return Self::raw(sign, 0, zero, Category::Infinity);
+            }
+            let quiet = MANTISSA - 1;
+            mantissa[quiet / 64] &= !(1 << (quiet % 64));
+            let payload = BigInt::from_parts(&mantissa);
+            return Self::raw(sign, 0, payload, Category::NaN);
+        }
+        if is_zero && biased_exp == 0 {
+            return Self::raw(sign, 0, zero, Category::Zero);
+        }
+
+        // Add the implicit bit for normal numbers, and adjust the exponent of
+        // the subnormals to the legal range.
+        let mut exp = biased_exp as i64 - Self::get_bias();
+        if biased_exp != 0 {
+            mantissa[MANTISSA / 64] |= 1 << (MANTISSA % 64);
+        } else {
+            exp += 1;
+        }
+        Self::raw(sign, exp, BigInt::from_parts(&mantissa), Category::Normal)
+    }
+
+    /// Returns the `len` bits of `parts` that start at bit `lo`, where `len`
+    /// is at most 64.
+    const fn get_bit_field(parts: &[u64; PARTS], lo: usize, len: usize) -> u64 {
+        let (word, offset) = (lo / 64, lo % 64);
+        let mut val = parts[word] >> offset;
+        if offset + len > 64 {
+            val |= parts[word + 1] << (64 - offset);
+        }
+        val & (u64::MAX >> (64 - len))
+    }
+
+    /// Returns the interchange encoding of the float as a byte array in
+    /// little-endian order. The size of the array must match the size of the
+    /// encoding, like 2 bytes for FP16 and 16 bytes for FP128.
+    ///```
+    ///    use arpfloat::FP128;
+    ///    let bytes: [u8; 16] = FP128::from_u64(1).to_le_bytes();
+    ///    assert_eq!(bytes[15], 0x3f);
+    ///    assert_eq!(FP128::from_le_bytes(bytes), FP128::from_u64(1));
+    ///```
+    pub fn to_le_bytes<const N: usize>(&self) -> [u8; N] {
+        assert_eq!(N * 8, 1 + EXPONENT + MANTISSA, "Invalid number of bytes");
+        let parts = self.to_bits();
+        core::array::from_fn(|i| (parts[i / 8] >> ((i % 8) * 8)) as u8)
+    }
+
+    /// Returns the interchange encoding of the float as a byte array in
+    /// big-endian order. See `to_le_bytes`.
+    pub fn to_be_bytes<const N: usize>(&self) -> [u8; N] {
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Loads a float from its interchange encoding, in a byte array in
+    /// little-endian order. See `to_le_bytes`.
+    pub fn from_le_bytes<const N: usize>(bytes: [u8; N]) -> Self {
+        assert_eq!(N * 8, 1 + EXPONENT + MANTISSA, "Invalid number of bytes");
+        let mut parts = [0; PARTS];
+        for (i, byte) in bytes.iter().enumerate() {
+            parts[i / 8] |= (*byte as u64) << ((i % 8) * 8);
+        }
+        Self::from_bits(parts)
+    }
+
+    /// Loads a float from its interchange encoding, in a byte array in
+    /// big-endian order. See `to_le_bytes`.
+    pub fn from_be_bytes<const N: usize>(mut bytes: [u8; N]) -> Self {
+        bytes.reverse();
+        Self::from_le_bytes(bytes)
+    }
+
+    /// Returns the bits of the IEEE754 interchange encoding of the float.
+    /// See `from_ieee_bits`.
+    pub fn as_ieee_bits(&self) -> BigInt<PARTS> {
         // https://en.wikipedia.org/wiki/IEEE_754
-        let mantissa: u64;
+        debug_assert!(1 + EXPONENT + MANTISSA <= PARTS * 64);
+        let mantissa: BigInt<PARTS>;
         let mut exp: u64;
         match self.get_category() {
             Category::Infinity => {
-                mantissa = 0;
+                mantissa = BigInt::zero();
                 exp = mask(EXPONENT) as u64;
             }
             Category::NaN => {
-                mantissa = 1 << (MANTISSA - 1);
+                let payload = self.get_nan_payload().unwrap();
+                mantissa = BigInt::one_hot(MANTISSA - 1) + payload;
                 exp = mask(EXPONENT) as u64;
             }
             Category::Zero => {
-                mantissa = 0;
+                mantissa = BigInt::zero();
                 exp = 0;
             }
             Category::Normal => {
                 exp = (self.get_exp() + Self::get_bias()) as u64;
                 debug_assert!(exp > 0);
-                let m = self.get_mantissa().as_u64();
+                let mut m = self.get_mantissa();
                 // Encode denormals. If the exponent is the minimum value and we
                 // don't have a leading integer bit (in the form 1.mmmm) then
                 // this is a denormal value and we need to encode it as such.
-                if (exp == 1) && ((m >> MANTISSA) == 0) {
+                if (exp == 1) && (m.msb_index() <= MANTISSA) {
                     exp = 0;
                 }
-                mantissa = m & utils::mask(MANTISSA) as u64;
+                m.mask(MANTISSA);
+                mantissa = m;
             }
         }
 
-        let mut bits: u64 = self.get_sign() as u64;
-        bits <<= EXPONENT;
-        bits |= exp;
-        bits <<= MANTISSA;
-        debug_assert!(mantissa <= 1 << MANTISSA);
-        bits |= mantissa;
-        bits
+        let mut bits = BigInt::from_u64(self.get_sign() as u64);
+        bits.shift_left(EXPONENT);
+        bits = bits + BigInt::from_u64(exp);
+        bits.shift_left(MANTISSA);
+        bits + mantissa
     }
     // Convert this float to fp32. Notice that the number may overflow or
     // rounded to the nearest even (see cast and cast_with_rm).
@@ -264,19 +747,196 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         f64::from_bits(bits)
     }
 
+    /// Convert this float to bfloat16, and returns the bits of the encoding.
+    /// Notice that the number may overflow or rounded to the nearest even.
+    pub fn as_bf16_bits(&self) -> u16 {
+        let b: BF16 = self.cast();
+        b.as_native_float() as u16
+    }
+
+    /// Loads and converts a bfloat16 value from the bits of its encoding.
+    /// Notice that the number may overflow or rounded to the nearest even.
+    pub fn from_bf16_bits(bits: u16) -> Self {
+        BF16::from_native_float(bits as u64).cast()
+    }
+
+    /// Convert this float to the x87 extended precision format, and returns
+    /// the 80 bits of the encoding. The integer bit is stored explicitly, and
+    /// is clear only for zeros and denormals.
+    pub fn as_fp80_bits(&self) -> u128 {
+        let x: FP80 = self.cast();
+        let (exp, mantissa): (u128, u64) = match x.get_category() {
+            Category::Infinity => (0x7fff, 1 << 63),
+            Category::NaN => {
+                let payload = x.get_nan_payload().unwrap().as_u64();
+                (0x7fff, (3 << 62) | payload)
+            }
+            Category::Zero => (0, 0),
+            Category::Normal => {
+                let m = x.get_mantissa().as_u64();
+                if m >> 63 == 0 {
+                    (0, m) // Denormal.
+                } else {
+                    ((x.get_exp() + FP80::get_bias()) as u128, m)
+                }
+            }
+        };
+        ((x.get_sign() as u128) << 79) | (exp << 64) | mantissa as u128
+    }
+
+    /// Loads and converts an x87 extended precision value from the 80 bits
+    /// of its encoding. Pseudo-denormals (a zero exponent with the integer bit
+    /// set) are loaded with the smallest normal exponent, like the x87 does.
+    /// Unnormals, pseudo-infinities and pseudo-NaNs (a non-zero exponent with
+    /// a clear integer bit) are invalid operands on the 80387 and later, and
+    /// are loaded as NaN.
+    pub fn from_fp80_bits(bits: u128) -> Self {
+        let sign = (bits >> 79) & 1 == 1;
+        let biased_exp = ((bits >> 64) & 0x7fff) as i64;
+        let mantissa = bits as u64;
+        let integer_bit = mantissa >> 63 == 1;
+
+        let x = if biased_exp == 0 {
+            // Zeros, denormals and pseudo-denormals.
+            let exp = 1 - FP80::get_bias();
+            FP80::new(sign, exp, BigInt::from_u64(mantissa))
+        } else if !integer_bit {
+            FP80::nan(sign)
+        } else if biased_exp == 0x7fff {
+            let fraction = mantissa & mask(63) as u64;
+            if fraction == 0 {
+                FP80::inf(sign)
+            } else {
+                FP80::nan_with_payload(sign, BigInt::from_u64(fraction))
+            }
+        } else {
+            let exp = biased_exp - FP80::get_bias();
+            FP80::new(sign, exp, BigInt::from_u64(mantissa))
+        };
+        x.cast()
+    }
+
+    /// Convert this float to TF32 by dropping the low mantissa bits, like the
+    /// tensor cores do when they are given FP32 inputs.
+    pub fn truncate_to_tf32(&self) -> TF32 {
+        self.cast_with_rm(RoundingMode::Zero)
+    }
+
+    /// Convert this float to TF32 with rounding to nearest, and ties away
+    /// from zero, like the 'cvt.rna.tf32.f32' instruction.
+    pub fn round_to_tf32(&self) -> TF32 {
+        self.cast_with_rm(RoundingMode::NearestTiesToAway)
+    }
+
     // Loads and converts a native fp32 value. Notice that the number may overflow or
     // rounded to the nearest even (see cast and cast_with_rm).
     pub fn from_f32(float: f32) -> Self {
-        FP32::from_bits(float.to_bits() as u64).cast()
+        FP32::from_native_float(float.to_bits() as u64).cast()
     }
 
     // Loads and converts a native fp64 value. Notice that the number may overflow or
     // rounded to the nearest even (see cast and cast_with_rm).
     pub fn from_f64(float: f64) -> Self {
-        FP64::from_bits(float.to_bits()).cast()
+        FP64::from_native_float(float.to_bits()).cast()
+    }
+}
+
+/// Implements From for native numbers, with the function that loads them.
+/// Notice that the number may overflow, or rounded to the nearest even.
+macro_rules! impl_from_native {
+    ($($ty:ty => $func:ident),*) => {
+        $(
+            impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+                From<$ty> for Float<EXPONENT, MANTISSA, PARTS>
+            {
+                fn from(val: $ty) -> Self {
+                    Self::$func(val.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_native!(
+    f32 => from_f32, f64 => from_f64,
+    u8 => from_u64, u16 => from_u64, u32 => from_u64, u64 => from_u64,
+    i8 => from_i64, i16 => from_i64, i32 => from_i64, i64 => from_i64,
+    u128 => from_u128, i128 => from_i128
+);
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    TryFrom<Float<EXPONENT, MANTISSA, PARTS>> for f64
+{
+    type Error = TryFromFloatError;
+
+    /// Converts the float to f64, if the conversion is exact. NaN is always
+    /// converted.
+    fn try_from(
+        val: Float<EXPONENT, MANTISSA, PARTS>,
+    ) -> Result<Self, Self::Error> {
+        let rm = RoundingMode::NearestTiesToEven;
+        let (x, status): (FP64, _) = val.cast_with_status(rm);
+        if !status.is_ok() {
+            return Err(TryFromFloatError(()));
+        }
+        Ok(x.as_f64())
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    TryFrom<Float<EXPONENT, MANTISSA, PARTS>> for f32
+{
+    type Error = TryFromFloatError;
+
+    /// Converts the float to f32, if the conversion is exact. NaN is always
+    /// converted.
+    fn try_from(
+        val: Float<EXPONENT, MANTISSA, PARTS>,
+    ) -> Result<Self, Self::Error> {
+        let rm = RoundingMode::NearestTiesToEven;
+        let (x, status): (FP32, _) = val.cast_with_status(rm);
+        if !status.is_ok() {
+            return Err(TryFromFloatError(()));
+        }
+        Ok(x.as_f32())
     }
 }
 
+/// Implements TryFrom for the native integers, with the conversion `$func`
+/// to the widest integer of the same signedness. The conversion succeeds if
+/// the float is an integer in the range of the type.
+macro_rules! impl_try_from_float {
+    ($($ty:ty => $func:ident),*) => {
+        $(
+            impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+                TryFrom<Float<EXPONENT, MANTISSA, PARTS>> for $ty
+            {
+                type Error = TryFromFloatError;
+
+                fn try_from(
+                    val: Float<EXPONENT, MANTISSA, PARTS>,
+                ) -> Result<Self, Self::Error> {
+                    let rm = RoundingMode::Zero;
+                    let (x, status) = val.$func(rm);
+                    if !status.is_ok() {
+                        return Err(TryFromFloatError(()));
+                    }
+                    <$ty>::try_from(x).map_err(|_| TryFromFloatError(()))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_float!(
+    u8 => to_u128_with_status, u16 => to_u128_with_status,
+    u32 => to_u128_with_status, u64 => to_u128_with_status,
+    u128 => to_u128_with_status,
+    i8 => to_i128_with_status, i16 => to_i128_with_status,
+    i32 => to_i128_with_status, i64 => to_i128_with_status,
+    i128 => to_i128_with_status
+);
+
 #[test]
 fn test_rounding_to_integer() {
     // Test the low integers with round-to-zero.
@@ -394,6 +1054,50 @@ fn test_cast_from_integers() {
     }
 }
 
+#[test]
+fn test_nan_payload() {
+    let payload = |x: FP64| x.get_nan_payload().unwrap().as_u64();
+    let quiet = 0x7ff8_0000_0000_0000_u64;
+
+    let a = FP64::nan_with_payload(false, BigInt::from_u64(0x1234));
+    assert_eq!(payload(a), 0x1234);
+    assert_eq!(a.as_f64().to_bits(), quiet | 0x1234);
+    assert!(FP64::one(false).get_nan_payload().is_none());
+    assert_eq!(payload(FP64::nan(true)), 0);
+
+    // Bits that don't fit below the quiet bit are dropped.
+    let b = FP64::nan_with_payload(true, BigInt::from_u64((1 << 51) | 5));
+    assert_eq!(payload(b), 5);
+    assert_eq!(b.as_f64().to_bits(), (1 << 63) | quiet | 5);
+
+    // Native NaNs keep their payload, and signaling NaNs become quiet.
+    let c = FP64::from_f64(f64::from_bits(quiet | 0xabc));
+    assert_eq!(payload(c), 0xabc);
+    let d = FP64::from_f64(f64::from_bits(0x7ff0_0000_0000_0001));
+    assert_eq!(d.as_f64().to_bits(), quiet | 1);
+
+    // Arithmetic propagates the NaN operand, or the first one.
+    let one = FP64::one(false);
+    assert_eq!(payload(a + one), 0x1234);
+    assert_eq!(payload(one - c), 0xabc);
+    assert_eq!(payload(one * a), 0x1234);
+    assert_eq!(payload(a / c), 0x1234);
+    assert_eq!(payload(c * a), 0xabc);
+    assert_eq!(payload(a.neg()), 0x1234);
+    let inf = FP64::inf(false);
+    assert_eq!(payload(inf - inf), 0);
+
+    // Casts keep the most significant bits of the payload.
+    let e = FP64::nan_with_payload(false, BigInt::from_u64(0x1234 << 29));
+    let e32: FP32 = e.cast();
+    assert_eq!(e32.get_nan_payload().unwrap().as_u64(), 0x1234);
+    assert_eq!(e.as_f32().to_bits(), 0x7fc0_1234);
+    let f: FP32 = a.cast();
+    assert_eq!(f.get_nan_payload().unwrap().as_u64(), 0);
+    let g: FP64 = e32.cast();
+    assert_eq!(payload(g), 0x1234 << 29);
+}
+
 #[test]
 fn test_cast_zero_nan_inf() {
     assert!(FP64::nan(true).as_f64().is_nan());
@@ -518,6 +1222,103 @@ fn test_trunc() {
     }
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_floor_ceil() {
+    use super::utils::Lfsr;
+    assert_eq!(FP64::from_f64(2.0).floor().as_f64(), 2.0);
+    assert_eq!(FP64::from_f64(2.5).floor().as_f64(), 2.0);
+    assert_eq!(FP64::from_f64(-2.5).floor().as_f64(), -3.0);
+    assert_eq!(FP64::from_f64(0.4).floor().as_f64(), 0.);
+    assert_eq!(FP64::from_f64(-0.4).floor().as_f64(), -1.);
+    assert_eq!(FP64::from_f64(2.0).ceil().as_f64(), 2.0);
+    assert_eq!(FP64::from_f64(2.5).ceil().as_f64(), 3.0);
+    assert_eq!(FP64::from_f64(-2.5).ceil().as_f64(), -2.0);
+    assert_eq!(FP64::from_f64(0.4).ceil().as_f64(), 1.);
+    assert!(FP64::from_f64(-0.4).ceil().is_negative());
+
+    // Test random values.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..5000 {
+        let v0 = f64::from_bits(lfsr.get64());
+        for (t0, t1) in [
+            (FP64::from_f64(v0).floor().as_f64(), v0.floor()),
+            (FP64::from_f64(v0).ceil().as_f64(), v0.ceil()),
+        ] {
+            assert_eq!(t0.is_nan(), t1.is_nan());
+            if !t1.is_nan() {
+                assert_eq!(t0, t1);
+                assert_eq!(t0.is_sign_negative(), t1.is_sign_negative());
+            }
+        }
+    }
+
+    // Test special values.
+    for val in utils::get_special_test_values() {
+        for (t0, t1) in [
+            (FP64::from_f64(val).floor().as_f64(), val.floor()),
+            (FP64::from_f64(val).ceil().as_f64(), val.ceil()),
+        ] {
+            assert_eq!(t0.is_nan(), t1.is_nan());
+            if !t1.is_nan() {
+                assert_eq!(t0, t1);
+                assert_eq!(t0.is_sign_negative(), t1.is_sign_negative());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_round_to_integral_exact() {
+    use super::utils::Lfsr;
+    use RoundingMode::*;
+
+    let check = |v: f64, rm: RoundingMode, res: f64, inexact: bool| {
+        let (r, flag) = FP64::from_f64(v).round_to_integral_exact(rm);
+        assert_eq!(r.as_f64(), res);
+        assert_eq!(r.is_negative(), res.is_sign_negative());
+        assert_eq!(flag, inexact);
+    };
+    check(2.5, NearestTiesToEven, 2., true);
+    check(3.5, NearestTiesToEven, 4., true);
+    check(-0.5, NearestTiesToEven, -0., true);
+    check(-1.5, NearestTiesToEven, -2., true);
+    check(2.5, NearestTiesToAway, 3., true);
+    check(2.1, Positive, 3., true);
+    check(-2.1, Positive, -2., true);
+    check(2.9, Negative, 2., true);
+    check(-0.1, Negative, -1., true);
+    check(-2.9, Zero, -2., true);
+    check(7.0, Positive, 7., false);
+    check(-7.0, Negative, -7., false);
+    check(1e300, Positive, 1e300, false);
+
+    // Compare round_ties_even to the rust implementation.
+    let mut lfsr = Lfsr::new();
+    for i in 0..5000 {
+        let v0 = if i % 2 == 0 {
+            f64::from_bits(lfsr.get64())
+        } else {
+            (lfsr.get64() % 2000) as f64 / 4. - 250.
+        };
+        let t0 = FP64::from_f64(v0).round_ties_even().as_f64();
+        let t1 = v0.round_ties_even();
+        assert_eq!(t0.is_nan(), t1.is_nan());
+        if !t1.is_nan() {
+            assert_eq!(t0, t1);
+        }
+    }
+
+    // Test the conversion to integers with the different rounding modes.
+    let x = FP64::from_f64(3.5);
+    assert_eq!(x.to_i64(NearestTiesToEven), 4);
+    assert_eq!(FP64::from_f64(1.5).to_i64(NearestTiesToEven), 2);
+    assert_eq!(FP64::from_f64(2.0).to_i64(Positive), 2);
+    assert_eq!(FP64::from_f64(-2.0).to_i64(Negative), -2);
+    assert_eq!(FP64::from_f64(-2.1).to_i64(Negative), -3);
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn test_round() {
@@ -560,6 +1361,26 @@ fn test_round() {
     }
 }
 
+#[test]
+fn test_cast_one_width() {
+    // Casts that change only the mantissa or only the exponent width must
+    // still round, and check the range of the target format.
+    type FP64M23 = Float<11, 23, 1>;
+    type FP64E8 = Float<8, 52, 2>;
+    let third = FP64::from_f64(1. / 3.);
+    let x: FP64M23 = third.cast();
+    assert_eq!(x.as_f64(), (1f32 / 3.) as f64);
+    let big = 2f64.powi(1000);
+    let x: FP64M23 = FP64::from_f64(big * (1. + 2f64.powi(-30))).cast();
+    assert_eq!(x.as_f64(), big);
+    let x: FP64E8 = FP64::from_f64(1e300).cast();
+    assert!(x.is_inf());
+    let x: FP64E8 = FP64::from_f64(-1e-300).cast();
+    assert!(x.is_zero() && x.is_negative());
+    let x: FP64E8 = third.cast();
+    assert_eq!(x.as_f64(), 1. / 3.);
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn test_cast_sizes() {
@@ -597,3 +1418,679 @@ fn test_cast_sizes() {
         assert_eq!(wide.to_i64(RoundingMode::NearestTiesToEven), 50);
     }
 }
+
+#[test]
+fn test_cast_directed_rounding() {
+    use super::utils::Lfsr;
+    use RoundingMode::{Negative, Positive, Zero};
+    let mut lfsr = Lfsr::new();
+
+    // Cast random f64 values, in the normal, subnormal and overflow ranges
+    // of f32, and check that the directed roundings bracket the value.
+    for i in 0..2000 {
+        let exp: u64 = [0x380, 0x368, 0x47e, 0x3ff][i % 4];
+        let bits = (exp << 52) | (lfsr.get64() & ((1 << 52) - 1));
+        let sign = if i % 8 < 4 { 0 } else { 1 << 63 };
+        let val = f64::from_bits(bits | sign);
+        let a = FP64::from_f64(val);
+        let down: FP32 = a.cast_with_rm(Negative);
+        let up: FP32 = a.cast_with_rm(Positive);
+        let zero: FP32 = a.cast_with_rm(Zero);
+        assert!(down.as_f64() <= val && val <= up.as_f64());
+        if down == up {
+            assert_eq!(down.as_f64(), val);
+        } else {
+            assert_eq!(down.next_up(), up);
+        }
+        let toward_zero = if val < 0. { up } else { down };
+        assert_eq!(zero, toward_zero);
+    }
+}
+
+#[test]
+fn test_bf16() {
+    use super::utils::Lfsr;
+
+    // Every bfloat16 value is the upper half of an f32 value, and converts
+    // exactly in both directions.
+    for bits in 0..=u16::MAX {
+        let f = f32::from_bits((bits as u32) << 16);
+        let a = BF16::from_bf16_bits(bits);
+        assert_eq!(a.is_nan(), f.is_nan());
+        if !f.is_nan() {
+            assert_eq!(a.as_f32().to_bits(), f.to_bits());
+            assert_eq!(BF16::from_f32(f).as_bf16_bits(), bits);
+        }
+    }
+
+    // Conversions from f32 round to nearest even. This is the rounding that
+    // is usually implemented by adding 0x7fff and the lowest kept bit.
+    let round_bf16 = |f: f32| {
+        let bits = f.to_bits();
+        ((bits + 0x7fff + ((bits >> 16) & 1)) >> 16) as u16
+    };
+    let mut lfsr = Lfsr::new();
+    for i in 0..5000 {
+        let mut bits = lfsr.get();
+        if i % 4 == 0 {
+            // Ties between two bfloat16 values.
+            bits = (bits & 0xffff_0000) | 0x8000;
+        }
+        let f = f32::from_bits(bits);
+        if f.is_nan() {
+            continue;
+        }
+        assert_eq!(BF16::from_f32(f).as_bf16_bits(), round_bf16(f));
+        assert_eq!(FP32::from_f32(f).as_bf16_bits(), round_bf16(f));
+    }
+
+    // The exponent range is the same as in f32.
+    let max = f32::from_bits(0x7f7f_0000);
+    assert_eq!(BF16::largest(false).as_f32(), max);
+    let tiny = f32::from_bits(0x0001_0000);
+    assert_eq!(BF16::smallest(false).as_f32(), tiny);
+    assert!(BF16::from_f32(f32::MAX).is_inf());
+    assert_eq!(BF16::from_f32(1e-39).as_f32(), f32::from_bits(0x000b_0000));
+}
+
+#[test]
+fn test_tf32() {
+    use super::utils::Lfsr;
+    assert_eq!(TF32::MANTISSA_DIGITS, 11);
+    assert_eq!(TF32::MAX_EXP, FP32::MAX_EXP);
+
+    // TF32 values are stored in FP32 registers, and the low 13 bits of the
+    // mantissa are dropped, or rounded with ties away from zero.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..5000 {
+        let f = f32::from_bits(lfsr.get());
+        if f.is_nan() {
+            continue;
+        }
+        let x = FP32::from_f32(f);
+        let truncated = f32::from_bits(f.to_bits() & 0xffff_e000);
+        assert_eq!(x.truncate_to_tf32().as_f32(), truncated);
+        let rounded = f32::from_bits((f.to_bits() + 0x1000) & 0xffff_e000);
+        assert_eq!(x.round_to_tf32().as_f32(), rounded);
+    }
+
+    // Ties round away from zero.
+    let tie = f32::from_bits(0x3f80_1000);
+    let tf = FP32::from_f32(tie).round_to_tf32();
+    assert_eq!(tf.as_f32(), f32::from_bits(0x3f80_2000));
+    let tf = FP32::from_f32(-tie).round_to_tf32();
+    assert_eq!(tf.as_f32(), f32::from_bits(0xbf80_2000));
+}
+
+#[test]
+fn test_fp80() {
+    use super::utils::Lfsr;
+    assert_eq!(FP80::MANTISSA_DIGITS, 64);
+    assert_eq!(FP80::MIN_EXP, -16381);
+    assert_eq!(FP80::MAX_EXP, 16384);
+
+    // Known encodings.
+    let one = FP64::one(false);
+    assert_eq!(one.as_fp80_bits(), 0x3fff_8000_0000_0000_0000);
+    let two = FP64::from_f64(-2.);
+    assert_eq!(two.as_fp80_bits(), 0xc000_8000_0000_0000_0000);
+    let pi = FP64::from_f64(core::f64::consts::PI);
+    assert_eq!(pi.as_fp80_bits(), 0x4000_c90f_daa2_2168_c000);
+    let inf = FP64::inf(true);
+    assert_eq!(inf.as_fp80_bits(), 0xffff_8000_0000_0000_0000);
+    assert_eq!(FP64::nan(false).as_fp80_bits(), 0x7fff_c000_0000_0000_0000);
+    assert_eq!(FP80::smallest(false).as_fp80_bits(), 1);
+    let max = FP80::largest(false).as_fp80_bits();
+    assert_eq!(max, 0x7ffe_ffff_ffff_ffff_ffff);
+
+    // Every f64 value is exact in FP80, and converts in both directions.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..2000 {
+        let f = f64::from_bits(lfsr.get64());
+        let bits = FP64::from_f64(f).as_fp80_bits();
+        let x = FP64::from_fp80_bits(bits);
+        assert!(x.as_f64().to_bits() == f.to_bits() || f.is_nan());
+        let bits = FP80::from_fp80_bits(bits).as_fp80_bits();
+        let y = FP80::from_fp80_bits(bits);
+        assert!(y.as_f64().to_bits() == f.to_bits() || f.is_nan());
+    }
+
+    // Denormals have a clear integer bit.
+    let denormal = FP80::from_fp80_bits(0x0000_4000_0000_0000_0000);
+    assert!(denormal.is_subnormal());
+    assert_eq!(denormal.as_fp80_bits(), 0x0000_4000_0000_0000_0000);
+
+    // A pseudo-denormal is the same number as the smallest normal exponent,
+    // and is stored in the normal encoding.
+    let pseudo = FP80::from_fp80_bits(0x0000_c000_0000_0000_0000);
+    let normal = FP80::from_fp80_bits(0x0001_c000_0000_0000_0000);
+    assert!(pseudo.is_normal() && !pseudo.is_subnormal());
+    assert_eq!(pseudo, normal);
+    assert_eq!(pseudo.as_fp80_bits(), 0x0001_c000_0000_0000_0000);
+
+    // Unnormals, pseudo-infinities and pseudo-NaNs are invalid.
+    assert!(FP80::from_fp80_bits(0x3fff_4000_0000_0000_0000).is_nan());
+    assert!(FP80::from_fp80_bits(0x7fff_0000_0000_0000_0000).is_nan());
+    assert!(FP80::from_fp80_bits(0x7fff_0000_0000_0000_0001).is_nan());
+
+    // NaN payloads are preserved.
+    let nan = FP80::from_fp80_bits(0x7fff_c000_0000_0000_1234);
+    assert_eq!(nan.as_fp80_bits(), 0x7fff_c000_0000_0000_1234);
+    // Signaling NaNs become quiet.
+    let nan = FP80::from_fp80_bits(0xffff_8000_0000_0000_0001);
+    assert_eq!(nan.as_fp80_bits(), 0xffff_c000_0000_0000_0001);
+
+    // Arithmetic is rounded to 64 bits of precision.
+    let third = FP80::one(false) / FP80::from_u64(3);
+    assert_eq!(third.as_fp80_bits(), 0x3ffd_aaaa_aaaa_aaaa_aaab);
+}
+
+#[test]
+fn test_fp256_bits() {
+    use super::utils::Lfsr;
+    use crate::FP256;
+    let bits =
+        |p: [u64; 4]| BigInt::from_parts(&[p[0], p[1], p[2], p[3], 0, 0, 0, 0]);
+
+    // Values from mpmath, rounded to 237 bits, in the binary256 encoding.
+    let third = bits([
+        0x5555555555555555,
+        0x5555555555555555,
+        0x5555555555555555,
+        0x3fffd55555555555,
+    ]);
+    let sqrt2 = bits([
+        0x75099da2f590b066,
+        0x57d3e3adec175127,
+        0xcc908b2fb1366ea9,
+        0x3ffff6a09e667f3b,
+    ]);
+    let neg_pi = bits([
+        0xf98e804177d4c762,
+        0x839a252049c1114c,
+        0x18469898cc51701b,
+        0xc0000921fb54442d,
+    ]);
+    let sum = bits([
+        0x3333333333333334,
+        0x3333333333333333,
+        0x3333333333333333,
+        0x3fffd33333333333,
+    ]);
+    let one = FP256::one(false);
+    let ten = FP256::from_u64(10);
+    assert_eq!((one / FP256::from_u64(3)).as_ieee_bits(), third);
+    assert_eq!(FP256::from_u64(2).sqrt().as_ieee_bits(), sqrt2);
+    let pi = FP256::pi_with_rm(RoundingMode::NearestTiesToEven);
+    assert_eq!(pi.neg().as_ieee_bits(), neg_pi);
+    let tenth = one / ten;
+    let fifth = FP256::from_u64(2) / ten;
+    assert_eq!((tenth + fifth).as_ieee_bits(), sum);
+    assert_eq!(FP256::from_ieee_bits(sqrt2), FP256::from_u64(2).sqrt());
+
+    // Special values.
+    assert_eq!(one.as_ieee_bits(), bits([0, 0, 0, 0x3ffff00000000000]));
+    let inf = FP256::inf(true).as_ieee_bits();
+    assert_eq!(inf, bits([0, 0, 0, 0xfffff00000000000]));
+    let nan = FP256::nan(false).as_ieee_bits();
+    assert_eq!(nan, bits([0, 0, 0, 0x7ffff80000000000]));
+    assert!(FP256::from_ieee_bits(nan).is_nan());
+    let tiny = FP256::smallest(false);
+    assert_eq!(tiny.as_ieee_bits(), BigInt::one());
+    assert_eq!(FP256::from_ieee_bits(BigInt::one()), tiny);
+
+    // Random encodings, including denormals, convert back to themselves.
+    let mut lfsr = Lfsr::new();
+    for i in 0..500 {
+        let mut b = bits([
+            lfsr.get64(),
+            lfsr.get64(),
+            lfsr.get64(),
+            lfsr.get64() & 0x80000fffffffffff,
+        ]);
+        if i % 2 == 0 {
+            // Set a random exponent.
+            let exp = lfsr.get64() % 0x7ffff;
+            b = b + bits([0, 0, 0, exp << 44]);
+        }
+        let x = FP256::from_ieee_bits(b);
+        assert_eq!(x.as_ieee_bits(), b);
+        assert!(i % 2 == 0 || x.is_subnormal());
+    }
+
+    // The encoding of the native types is the same.
+    let f = 1.5e-310;
+    let bits = FP64::from_f64(f).as_ieee_bits();
+    assert_eq!(bits.as_u64(), f64::to_bits(f));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_from_bits() {
+    use crate::{FP128, FP16};
+
+    // Known encodings.
+    assert_eq!(FP16::from_f64(1.).to_bits(), [0x3c00]);
+    assert_eq!(FP16::from_f64(-2.).to_bits(), [0xc000]);
+    assert_eq!(FP16::inf(false).to_bits(), [0x7c00]);
+    assert_eq!(FP16::largest(false).to_bits(), [0x7bff]);
+    let third = FP128::one(false) / FP128::from_u64(3);
+    let bits = [0x5555_5555_5555_5555, 0x3ffd_5555_5555_5555, 0, 0];
+    assert_eq!(third.to_bits(), bits);
+    assert_eq!(FP128::from_bits(bits), third);
+    let bits = [0, 0x8000_0000_0000_0000, 0, 0];
+    assert!(FP128::from_bits(bits).is_zero());
+    assert!(FP128::from_bits(bits).is_negative());
+
+    // The encoding of the native types.
+    for f in utils::get_special_test_values() {
+        let bits = FP64::from_f64(f).to_bits();
+        assert_eq!(bits[0], f.to_bits());
+        assert_eq!(bits[1], 0);
+        let x = FP64::from_bits(bits);
+        assert!(x.as_f64().to_bits() == f.to_bits() || f.is_nan());
+        let bits = FP32::from_f32(f as f32).to_bits();
+        assert_eq!(bits[0], (f as f32).to_bits() as u64);
+    }
+
+    // A custom format converts every encoding back to itself, except for
+    // signaling NaNs, which become quiet.
+    type FP16E6 = Float<6, 9, 1>;
+    for i in 0..(1 << 16) {
+        let x = FP16E6::from_bits([i]);
+        let quiet = if x.is_nan() { 1 << 8 } else { 0 };
+        assert_eq!(x.to_bits(), [i | quiet]);
+    }
+
+    // The encodings can be loaded in constants.
+    const SUBNORMAL: FP16 = FP16::from_bits([0x0001]);
+    const NAN: FP128 = FP128::from_bits([7, 0x7fff_0000_0000_0000, 0, 0]);
+    assert_eq!(SUBNORMAL.as_f64(), 2f64.powi(-24));
+    assert_eq!(NAN.get_nan_payload(), Some(BigInt::from_u64(7)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bytes() {
+    use crate::{FP128, FP16};
+
+    // The native types have the same encodings.
+    for f in utils::get_special_test_values() {
+        let x = FP64::from_f64(f);
+        assert_eq!(x.to_le_bytes(), f.to_le_bytes());
+        assert_eq!(x.to_be_bytes(), f.to_be_bytes());
+        let y = FP64::from_be_bytes(f.to_be_bytes());
+        assert!(y.as_f64().to_bits() == f.to_bits() || f.is_nan());
+        let f = f as f32;
+        let x = FP32::from_f32(f);
+        assert_eq!(x.to_le_bytes(), f.to_le_bytes());
+        let y = FP32::from_le_bytes(f.to_le_bytes());
+        assert!(y.as_f32().to_bits() == f.to_bits() || f.is_nan());
+    }
+
+    let x = FP16::from_f64(-2.5);
+    assert_eq!(x.to_le_bytes(), [0x00, 0xc1]);
+    assert_eq!(x.to_be_bytes(), [0xc1, 0x00]);
+    assert_eq!(FP16::from_be_bytes([0xc1, 0x00]), x);
+
+    // binary128.
+    let third = FP128::one(false) / FP128::from_u64(3);
+    let bytes: [u8; 16] = third.to_be_bytes();
+    let expected = 0x3ffd_5555_5555_5555_5555_5555_5555_5555_u128;
+    assert_eq!(bytes, expected.to_be_bytes());
+    assert_eq!(FP128::from_be_bytes(bytes), third);
+    let bytes: [u8; 16] = third.to_le_bytes();
+    assert_eq!(bytes, expected.to_le_bytes());
+    assert_eq!(FP128::from_le_bytes(bytes), third);
+}
+
+#[test]
+#[should_panic]
+fn test_bytes_size_panics() {
+    let _: [u8; 4] = FP64::from_f64(1.).to_le_bytes();
+}
+
+#[test]
+fn test_from_u128_i128() {
+    use crate::{FP128, FP16};
+    use utils::Lfsr;
+
+    // Integers that fit in the mantissa are exact.
+    let big = (1u128 << 112) + 12345;
+    assert_eq!(FP128::from_u128(big).to_bits()[0], 12345);
+    let x = FP128::from_i128(-(big as i128));
+    assert!(x.is_negative());
+    assert_eq!(x.neg(), FP128::from_u128(big));
+    assert_eq!(FP128::from_u128(u128::MAX), FP128::from_u128(u128::MAX - 1));
+    let min = FP128::from_i128(i128::MIN);
+    assert_eq!(min, FP128::one(true).scale(127, RoundingMode::Zero));
+
+    // The rounding is the same as in the native conversions.
+    let mut lfsr = Lfsr::new();
+    for i in 0..2000 {
+        let val = (((lfsr.get64() as u128) << 64) | lfsr.get64() as u128)
+            >> (i % 128);
+        assert_eq!(FP64::from_u128(val).as_f64(), val as f64);
+        assert_eq!(FP32::from_u128(val).as_f32(), val as f32);
+        let val = val as i128;
+        assert_eq!(FP64::from_i128(val).as_f64(), val as f64);
+        assert_eq!(FP32::from_i128(val).as_f32(), val as f32);
+    }
+
+    // Ties round to even.
+    let tie = (1u128 << 100) + (1 << 47);
+    assert_eq!(FP64::from_u128(tie).as_f64(), (1u128 << 100) as f64);
+    let tie = (1u128 << 100) + (3 << 47);
+    assert_eq!(FP64::from_u128(tie).as_f64(), tie as f64);
+    assert!(FP16::from_u128(1 << 100).is_inf());
+}
+
+#[test]
+fn test_from_rational() {
+    use crate::FP16;
+    use utils::Lfsr;
+    use RoundingMode::*;
+
+    let x = FP64::from_rational(1, 3, NearestTiesToEven);
+    assert_eq!(x.as_f64(), 1. / 3.);
+    let x = FP64::from_rational(-2, 3, Zero);
+    assert_eq!(x.as_f64(), -2. / 3.);
+    let x = FP64::from_rational(i128::MIN, 1, NearestTiesToEven);
+    assert_eq!(x, FP64::from_i128(i128::MIN));
+    let x = FP64::from_rational(i128::MAX, i128::MIN, NearestTiesToEven);
+    assert_eq!(x.as_f64(), -1.0);
+    let x = FP64::from_rational(-7, -1 << 100, NearestTiesToEven);
+    assert_eq!(x.as_f64(), 7. / (1u128 << 100) as f64);
+
+    // Division by zero, overflow and underflow.
+    assert!(FP16::from_rational(0, 0, NearestTiesToEven).is_nan());
+    let x = FP16::from_rational(-1, 0, NearestTiesToEven);
+    assert!(x.is_inf() && x.is_negative());
+    assert!(FP16::from_rational(1 << 16, 1, NearestTiesToEven).is_inf());
+    assert!(FP16::from_rational(1 << 16, 1, Zero).is_normal());
+    let x = FP16::from_rational(1, 1 << 26, NearestTiesToEven);
+    assert!(x.is_zero());
+    let x = FP16::from_rational(1, 1 << 26, Positive);
+    assert_eq!(x, FP16::from_rational(1, 1 << 24, Positive));
+    assert!(FP16::from_rational(3, 1 << 26, NearestTiesToEven).is_subnormal());
+
+    // The ratio is rounded like a correctly rounded division of the exact
+    // operands.
+    let mut lfsr = Lfsr::new();
+    for i in 0..2000 {
+        let a = (lfsr.get64() >> (11 + i % 50)) as i64;
+        let b = (lfsr.get64() >> (11 + i % 53)) as i64 | 1;
+        let (a, b) = if i % 3 == 0 { (-a, b) } else { (a, b) };
+        for rm in [NearestTiesToEven, NearestTiesToAway, Zero, Positive] {
+            let expected =
+                FP64::div_with_rm(FP64::from_i64(a), FP64::from_i64(b), rm);
+            let x = FP64::from_rational(a as i128, b as i128, rm);
+            assert_eq!(x, expected);
+            let (a, b) = (a >> 40, b >> 40 | 1);
+            let (x, y) = (FP32::from_i64(a), FP32::from_i64(b));
+            let expected = FP32::div_with_rm(x, y, rm);
+            assert_eq!(FP32::from_rational(a as i128, b as i128, rm), expected);
+        }
+    }
+}
+
+#[test]
+fn test_as_integer_ratio() {
+    use crate::FP128;
+    use utils::Lfsr;
+
+    let ratio = FP64::from_f64(-0.75).as_integer_ratio::<1>().unwrap();
+    assert_eq!(ratio, (true, BigInt::from_u64(3), BigInt::from_u64(4)));
+    let ratio = FP64::from_f64(-0.0).as_integer_ratio::<1>().unwrap();
+    assert_eq!(ratio, (true, BigInt::zero(), BigInt::one()));
+    let ratio = FP64::from_f64(24.0).as_integer_ratio::<1>().unwrap();
+    assert_eq!(ratio, (false, BigInt::from_u64(24), BigInt::one()));
+    assert!(FP64::inf(false).as_integer_ratio::<4>().is_none());
+    assert!(FP64::nan(false).as_integer_ratio::<4>().is_none());
+
+    // Integers that don't fit.
+    let big = FP64::from_f64(2f64.powi(1000));
+    assert!(big.as_integer_ratio::<15>().is_none());
+    let (_, num, den) = big.as_integer_ratio::<16>().unwrap();
+    assert_eq!((num, den), (BigInt::one_hot(1000), BigInt::one()));
+    let tiny = FP64::from_f64(f64::from_bits(1));
+    assert!(tiny.as_integer_ratio::<16>().is_none());
+    let (_, num, den) = tiny.as_integer_ratio::<17>().unwrap();
+    assert_eq!((num, den), (BigInt::one(), BigInt::one_hot(1074)));
+    let x = FP128::from_u128(u128::MAX);
+    assert!(x.as_integer_ratio::<2>().is_none());
+    let (_, num, den) = x.as_integer_ratio::<3>().unwrap();
+    assert_eq!((num, den), (BigInt::one_hot(128), BigInt::one()));
+
+    // The ratio converts back to the same value.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..1000 {
+        let x = FP64::from_f64(f64::from_bits(lfsr.get64()));
+        if !x.is_normal() {
+            continue;
+        }
+        let exp = (lfsr.get64() % 60) as i64 - 30 - x.get_exp();
+        let x = x.scale(exp, RoundingMode::Zero);
+        let (sign, num, den) = x.as_integer_ratio::<2>().unwrap();
+        assert!(num.is_odd() || den.as_u128() == 1);
+        let num = num.as_u128() as i128;
+        let num = if sign { -num } else { num };
+        let rm = RoundingMode::Zero;
+        let y = FP64::from_rational(num, den.as_u128() as i128, rm);
+        assert_eq!(y, x);
+    }
+}
+
+#[test]
+fn test_to_bigint() {
+    use crate::FP128;
+    use utils::Lfsr;
+    let ok = OpStatus::OK;
+    let inexact = OpStatus::INEXACT;
+    let invalid = OpStatus::INVALID_OP;
+
+    let x = FP64::from_f64(-2.75).to_bigint::<1>();
+    assert_eq!(x, (Some((true, BigInt::from_u64(2))), inexact));
+    let x = FP64::from_f64(0.5).to_bigint::<1>();
+    assert_eq!(x, (Some((false, BigInt::zero())), inexact));
+    let x = FP64::from_f64(-0.0).to_bigint::<1>();
+    assert_eq!(x, (Some((true, BigInt::zero())), ok));
+    assert_eq!(FP64::nan(false).to_bigint::<4>(), (None, invalid));
+    assert_eq!(FP64::inf(true).to_bigint::<4>(), (None, invalid));
+
+    // Values beyond the range of u64 and u128.
+    let x = FP128::from_f64(1e300);
+    assert_eq!(x.to_bigint::<15>(), (None, invalid));
+    let (val, status) = x.to_bigint::<16>();
+    let (sign, val) = val.unwrap();
+    assert!(!sign && status.is_ok());
+    let mut val = val;
+    assert_eq!(val.msb_index(), 997);
+    val.shift_right(997 - 53);
+    let bits = 1e300f64.to_bits();
+    assert_eq!(val.as_u64(), (bits & ((1 << 52) - 1)) | (1 << 52));
+    let x = FP128::from_u128(u128::MAX - 0xffff).neg();
+    let (val, status) = x.to_bigint::<2>();
+    assert_eq!(val, Some((true, BigInt::from_u128(u128::MAX - 0xffff))));
+    assert!(status.is_ok());
+    let x =
+        FP128::from_f64(2f64.powi(100) + 2f64.powi(50)) + FP128::from_f64(0.5);
+    assert_eq!(x.to_bigint::<1>(), (None, invalid));
+
+    // Compare to the conversion to i128 that rounds toward zero.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..1000 {
+        let x = FP128::from_f64(f64::from_bits(lfsr.get64()));
+        let (expected, status) = x.to_i128_with_status(RoundingMode::Zero);
+        if status.contains(invalid) {
+            continue;
+        }
+        let (val, status2) = x.to_bigint::<2>();
+        let (sign, val) = val.unwrap();
+        assert_eq!(status, status2);
+        assert_eq!(val.as_u128(), expected.unsigned_abs());
+        assert_eq!(sign, x.is_negative());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_integer_with_status() {
+    use crate::{FP128, FP16, FP256};
+    use RoundingMode::*;
+    let ok = OpStatus::OK;
+    let inexact = OpStatus::INEXACT;
+    let invalid = OpStatus::INVALID_OP;
+
+    let x = FP64::from_f64(2.5);
+    assert_eq!(x.to_i64_with_status(NearestTiesToEven), (2, inexact));
+    assert_eq!(x.to_i64_with_status(NearestTiesToAway), (3, inexact));
+    assert_eq!(x.to_u64_with_status(Positive), (3, inexact));
+    assert_eq!(x.neg().to_i128_with_status(Negative), (-3, inexact));
+    assert_eq!(x.neg().to_i128_with_status(Zero), (-2, inexact));
+    let x = FP64::from_f64(-7.);
+    assert_eq!(x.to_i64_with_status(Zero), (-7, ok));
+    assert_eq!(x.to_u64_with_status(Zero), (0, invalid));
+
+    // Negative numbers that round to zero are in the range of u64.
+    let x = FP64::from_f64(-0.25);
+    assert_eq!(x.to_u64_with_status(NearestTiesToEven), (0, inexact));
+    assert_eq!(x.to_u64_with_status(Negative), (0, invalid));
+
+    // The limits of the integer types.
+    let x = FP64::from_f64(9223372036854775807.);
+    assert_eq!(x.to_i64_with_status(Zero), (i64::MAX, invalid));
+    assert_eq!(x.neg().to_i64_with_status(Zero), (i64::MIN, ok));
+    assert_eq!(x.to_u64_with_status(Zero), (1 << 63, ok));
+    let x = FP128::from_u64(u64::MAX);
+    assert_eq!(x.to_u64_with_status(Zero), (u64::MAX, ok));
+    let x = FP256::from_u128(u128::MAX >> 1);
+    assert_eq!(x.to_i128_with_status(Zero), (i128::MAX, ok));
+    let x = (x + FP256::one(false)).neg();
+    assert_eq!(x.to_i128_with_status(Zero), (i128::MIN, ok));
+    assert_eq!(
+        x.scale(1, Zero).to_i128_with_status(Zero),
+        (i128::MIN, invalid)
+    );
+    let x = FP256::from_u128(u128::MAX >> 1) + FP256::from_f64(0.75);
+    assert_eq!(x.to_i128_with_status(Zero), (i128::MAX, inexact));
+    assert_eq!(x.to_i128_with_status(Positive), (i128::MAX, invalid));
+
+    // Special values saturate, and NaN is converted to zero.
+    assert_eq!(
+        FP16::inf(false).to_i64_with_status(Zero),
+        (i64::MAX, invalid)
+    );
+    assert_eq!(FP16::inf(true).to_u64_with_status(Zero), (0, invalid));
+    assert_eq!(FP16::nan(false).to_i128_with_status(Zero), (0, invalid));
+    assert_eq!(FP16::zero(true).to_i64_with_status(Zero), (0, ok));
+    assert_eq!(FP64::from_f64(1e300).to_i128(Zero), i128::MAX);
+    assert_eq!(FP64::from_f64(-1e300).to_u64(Zero), 0);
+    assert_eq!(FP64::from_f64(1e300).to_u128(Zero), u128::MAX);
+    let x = FP256::from_u128(u128::MAX) + FP256::from_f64(0.5);
+    assert_eq!(x.to_u128_with_status(Zero), (u128::MAX, inexact));
+    assert_eq!(x.to_u128_with_status(Positive), (u128::MAX, invalid));
+    assert_eq!(
+        FP16::from_f64(-0.25).to_u128_with_status(Zero),
+        (0, inexact)
+    );
+
+    // The conversions agree with the saturating native conversions.
+    for f in utils::get_special_test_values() {
+        let x = FP64::from_f64(f);
+        assert_eq!(x.to_i64(Zero), f as i64);
+        assert_eq!(x.to_u64(Zero), f as u64);
+        assert_eq!(x.to_i128(Zero), f as i128);
+        assert_eq!(x.to_u128(Zero), f as u128);
+    }
+}
+
+#[test]
+fn test_from_try_from() {
+    use crate::{FP128, FP16, FP256};
+
+    let x: FP64 = 2.5f64.into();
+    assert_eq!(x.as_f64(), 2.5);
+    let x = FP128::from(-1.25f32);
+    assert_eq!(x.as_f64(), -1.25);
+    assert_eq!(FP64::from(200u8).as_f64(), 200.);
+    assert_eq!(FP64::from(-30000i16).as_f64(), -30000.);
+    assert_eq!(FP64::from(u64::MAX).as_f64(), u64::MAX as f64);
+    assert_eq!(FP128::from(i128::MIN).as_f64(), i128::MIN as f64);
+    assert!(FP16::from(1u32 << 20).is_inf());
+
+    // Conversions to native floats must be exact.
+    let third = FP128::one(false) / FP128::from_u64(3);
+    assert!(f64::try_from(third).is_err());
+    assert_eq!(f64::try_from(FP128::from(0.75)), Ok(0.75));
+    assert_eq!(f32::try_from(FP64::from(0.75)), Ok(0.75));
+    assert!(f32::try_from(FP64::from(1e300)).is_err());
+    assert!(f32::try_from(FP64::from(1e-300)).is_err());
+    assert!(f64::try_from(FP16::nan(false)).unwrap().is_nan());
+    assert_eq!(f64::try_from(FP16::inf(true)), Ok(f64::NEG_INFINITY));
+
+    // Conversions to integers must be exact and in range.
+    assert_eq!(i64::try_from(FP64::from(-42.)), Ok(-42));
+    assert_eq!(u8::try_from(FP64::from(255.)), Ok(255));
+    assert!(u8::try_from(FP64::from(256.)).is_err());
+    assert!(u32::try_from(FP64::from(-1.)).is_err());
+    assert!(i32::try_from(FP64::from(0.5)).is_err());
+    assert!(i64::try_from(FP64::nan(false)).is_err());
+    assert!(i64::try_from(FP64::inf(false)).is_err());
+    assert_eq!(i128::try_from(FP128::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(u128::try_from(FP256::from(u128::MAX)), Ok(u128::MAX));
+    let x = FP256::from(u128::MAX) + FP256::one(false);
+    assert!(u128::try_from(x).is_err());
+    let big = u128::MAX >> 113 << 113;
+    assert_eq!(u128::try_from(FP128::from(big)), Ok(big));
+    assert_eq!(u128::try_from(FP128::from(1u128 << 127)), Ok(1 << 127));
+    assert!(u128::try_from(FP128::from(u128::MAX)).is_err());
+    assert!(u128::try_from(FP128::from(-1.)).is_err());
+    assert!(u128::try_from(FP64::from(2f64.powi(128))).is_err());
+    assert_eq!(u128::try_from(FP64::from(-0.)), Ok(0));
+    assert!(u128::try_from(FP64::from(-0.5)).is_err());
+    assert_eq!(u64::try_from(FP64::zero(true)), Ok(0));
+}
+
+#[test]
+fn test_try_cast() {
+    use crate::{FP128, FP16};
+    let x = FP64::from_f64(1.5);
+    let y: FP16 = x.try_cast().unwrap();
+    assert_eq!(y.as_f64(), 1.5);
+    let third = FP128::one(false) / FP128::from_u64(3);
+    assert_eq!(third.try_cast::<11, 52, 2>(), Err(ArithError::Inexact));
+    let big = FP64::from_f64(1e10);
+    assert_eq!(big.try_cast::<5, 10, 1>(), Err(ArithError::Overflow));
+    let tiny = FP64::from_f64(1e-10);
+    assert_eq!(tiny.try_cast::<5, 10, 1>(), Err(ArithError::Underflow));
+
+    // Subnormals, zeros, infinities and NaN are exact.
+    let x = FP64::from_f64(2f64.powi(-24));
+    assert!(x.try_cast::<5, 10, 1>().unwrap().is_subnormal());
+    assert!(FP64::zero(true).try_cast::<5, 10, 1>().unwrap().is_zero());
+    assert!(FP64::inf(false).try_cast::<5, 10, 1>().unwrap().is_inf());
+    assert!(FP64::nan(false).try_cast::<5, 10, 1>().unwrap().is_nan());
+}
+
+#[test]
+fn test_cast_saturating() {
+    use crate::{E4M3, FP16};
+    let max = FP16::largest(false);
+    let big = FP64::from_f64(1e10);
+    let y: FP16 = big.cast_saturating();
+    assert_eq!(y, max);
+    let y: FP16 = big.neg().cast_saturating();
+    assert_eq!(y, max.neg());
+    let y: FP16 = FP64::from_f64(1.5).cast_saturating();
+    assert_eq!(y.as_f64(), 1.5);
+    let y: FP16 = FP64::inf(true).cast_saturating();
+    assert!(y.is_inf() && y.is_negative());
+    let y: FP16 = FP64::nan(false).cast_saturating();
+    assert!(y.is_nan());
+
+    // Formats without infinities saturate infinities too.
+    let rm = RoundingMode::NearestTiesToEven;
+    let y = E4M3::from_float(&FP64::inf(false), rm, true);
+    assert_eq!(y.to_bits(), E4M3::MAX.to_bits());
+}
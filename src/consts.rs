@@ -0,0 +1,243 @@
+//! Mathematical constants, like the ones in `core::f64::consts`.
+//!
+//! The constants are computed at the precision of the requested format, and
+//! are not cast from f64. Generic constants can't be Rust constants, so each
+//! constant is a function, and the format is selected by the return type:
+//!```
+//!    use arpfloat::{consts, FP128};
+//!    let pi: FP128 = consts::pi();
+//!    let ln_2 = consts::ln_2::<15, 112, 4>();
+//!```
+
+use super::float::{Float, RoundingMode};
+use super::functions::WideFloat;
+
+/// Defines a public function that returns a constant that is derived from
+/// other constants. The constant is evaluated in the wide format, when it is
+/// available, and rounded to the target format. The extra bits of the wide
+/// format make the result correctly rounded, unless it is very close to a
+/// rounding boundary.
+macro_rules! define_constant {
+    ($(#[$attr:meta])* $name:ident, $value:expr) => {
+        $(#[$attr])*
+        pub fn $name<
+            const EXPONENT: usize,
+            const MANTISSA: usize,
+            const PARTS: usize,
+        >() -> Float<EXPONENT, MANTISSA, PARTS> {
+            if Float::<EXPONENT, MANTISSA, PARTS>::has_wide_float() {
+                let wide: WideFloat = $value;
+                return wide.cast();
+            }
+            $value
+        }
+    };
+}
+
+/// Defines a public function that returns a constant that is computed
+/// correctly rounded in every format, or is a power of two multiple of one.
+/// Rounding a wide value again could round it incorrectly, so these are
+/// computed in the target format.
+macro_rules! define_rounded_constant {
+    ($(#[$attr:meta])* $name:ident, $value:expr) => {
+        $(#[$attr])*
+        pub fn $name<
+            const EXPONENT: usize,
+            const MANTISSA: usize,
+            const PARTS: usize,
+        >() -> Float<EXPONENT, MANTISSA, PARTS> {
+            $value
+        }
+    };
+}
+
+const RM: RoundingMode = RoundingMode::NearestTiesToEven;
+
+define_rounded_constant!(
+    /// Archimedes' constant (π)
+    pi,
+    Float::pi_with_rm(RM)
+);
+
+define_rounded_constant!(
+    /// The full circle constant (τ = 2π)
+    tau,
+    Float::pi_with_rm(RM).scale(1, RM)
+);
+
+define_rounded_constant!(
+    /// Euler's number (e)
+    e,
+    Float::e_with_rm(RM)
+);
+
+define_constant!(
+    /// The Euler-Mascheroni constant (γ)
+    egamma,
+    Float::euler_gamma()
+);
+
+define_constant!(
+    /// Catalan's constant (G)
+    catalan,
+    Float::catalan()
+);
+
+define_constant!(
+    /// Apery's constant (ζ(3))
+    zeta3,
+    Float::zeta3()
+);
+
+define_rounded_constant!(
+    /// π/2
+    frac_pi_2,
+    Float::pi_with_rm(RM).scale(-1, RM)
+);
+
+define_constant!(
+    /// π/3
+    frac_pi_3,
+    Float::pi() / Float::from_u64(3)
+);
+
+define_rounded_constant!(
+    /// π/4
+    frac_pi_4,
+    Float::pi_with_rm(RM).scale(-2, RM)
+);
+
+define_constant!(
+    /// π/6
+    frac_pi_6,
+    Float::pi() / Float::from_u64(6)
+);
+
+define_rounded_constant!(
+    /// π/8
+    frac_pi_8,
+    Float::pi_with_rm(RM).scale(-3, RM)
+);
+
+define_constant!(
+    /// 1/π
+    frac_1_pi,
+    Float::one(false) / Float::pi()
+);
+
+define_constant!(
+    /// 2/π
+    frac_2_pi,
+    Float::from_u64(2) / Float::pi()
+);
+
+define_constant!(
+    /// 2/sqrt(π)
+    frac_2_sqrt_pi,
+    Float::from_u64(2) / Float::pi().sqrt()
+);
+
+define_constant!(
+    /// sqrt(2)
+    sqrt_2,
+    Float::from_u64(2).sqrt()
+);
+
+define_constant!(
+    /// 1/sqrt(2)
+    frac_1_sqrt_2,
+    Float::from_u64(2).sqrt().scale(-1, RoundingMode::Zero)
+);
+
+define_rounded_constant!(
+    /// ln(2)
+    ln_2,
+    Float::ln2_with_rm(RM)
+);
+
+define_rounded_constant!(
+    /// ln(10)
+    ln_10,
+    Float::ln10_with_rm(RM)
+);
+
+define_constant!(
+    /// log2(e)
+    log2_e,
+    Float::one(false) / Float::from_u64(2).ln()
+);
+
+define_constant!(
+    /// log10(e)
+    log10_e,
+    Float::one(false) / Float::from_u64(10).ln()
+);
+
+define_constant!(
+    /// log2(10)
+    log2_10,
+    Float::from_u64(10).ln() / Float::from_u64(2).ln()
+);
+
+define_constant!(
+    /// log10(2)
+    log10_2,
+    Float::from_u64(2).ln() / Float::from_u64(10).ln()
+);
+
+#[test]
+fn test_f64_consts() {
+    use crate::FP64;
+    use core::f64::consts;
+
+    // The f64 constants are correctly rounded.
+    let values: [(FP64, f64); 20] = [
+        (pi(), consts::PI),
+        (tau(), consts::TAU),
+        (e(), consts::E),
+        (egamma(), 0.5772156649015329),
+        (frac_pi_2(), consts::FRAC_PI_2),
+        (frac_pi_3(), consts::FRAC_PI_3),
+        (frac_pi_4(), consts::FRAC_PI_4),
+        (frac_pi_6(), consts::FRAC_PI_6),
+        (frac_pi_8(), consts::FRAC_PI_8),
+        (frac_1_pi(), consts::FRAC_1_PI),
+        (frac_2_pi(), consts::FRAC_2_PI),
+        (frac_2_sqrt_pi(), consts::FRAC_2_SQRT_PI),
+        (sqrt_2(), consts::SQRT_2),
+        (frac_1_sqrt_2(), consts::FRAC_1_SQRT_2),
+        (ln_2(), consts::LN_2),
+        (ln_10(), consts::LN_10),
+        (log2_e(), consts::LOG2_E),
+        (log10_e(), consts::LOG10_E),
+        (log2_10(), consts::LOG2_10),
+        (log10_2(), consts::LOG10_2),
+    ];
+    for (v, expected) in values {
+        assert_eq!(v.as_f64(), expected);
+    }
+}
+
+#[test]
+fn test_fp128_consts() {
+    use crate::FP128;
+
+    // Values from mpmath, rounded to 113 bits: (value, exponent, mantissa).
+    let values: [(FP128, i64, u128); 11] = [
+        (pi(), 1, 0x1921fb54442d18469898cc51701b8),
+        (tau(), 2, 0x1921fb54442d18469898cc51701b8),
+        (e(), 1, 0x15bf0a8b1457695355fb8ac404e7a),
+        (egamma(), -1, 0x12788cfc6fb618f49a37c7f0202a6),
+        (catalan(), -1, 0x1d4f9713e8135d08a42b045c6fa66),
+        (zeta3(), 0, 0x133ba004f0062138371715c59e690),
+        (frac_pi_3(), 0, 0x10c152382d73658465bb32e0f567b),
+        (frac_2_sqrt_pi(), 0, 0x120dd750429b6d11ae3a914fed7fe),
+        (frac_1_sqrt_2(), -1, 0x16a09e667f3bcc908b2fb1366ea95),
+        (ln_10(), 1, 0x126bb1bbb5551582dd4adac5705a6),
+        (log10_2(), -2, 0x134413509f79fef311f12b35816f9),
+    ];
+    for (v, exp, mantissa) in values {
+        assert_eq!(v.get_exp(), exp);
+        assert_eq!(v.get_mantissa().as_u128(), mantissa);
+    }
+}
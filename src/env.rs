@@ -0,0 +1,159 @@
+use super::float::{Float, OpStatus, RoundingMode};
+
+/// A floating point environment, that carries the rounding mode for a
+/// sequence of operations, and accumulates the exception flags that the
+/// operations raise. The flags are sticky, and stay raised until they are
+/// cleared.
+/// See IEEE754-2019 Section 8.1 Alternate exception handling attributes.
+///
+///```
+///    use arpfloat::{FpEnv, OpStatus, RoundingMode, FP64};
+///
+///    let mut env = FpEnv::new(RoundingMode::Zero);
+///    let one = FP64::from_u64(1);
+///    let three = FP64::from_u64(3);
+///    let x = env.div(one, three);
+///    let y = env.mul(x, three);
+///    assert!(y < one);
+///    assert_eq!(env.get_flags(), OpStatus::INEXACT);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FpEnv {
+    rm: RoundingMode,
+    flags: OpStatus,
+}
+
+impl Default for FpEnv {
+    fn default() -> Self {
+        Self::new(RoundingMode::NearestTiesToEven)
+    }
+}
+
+impl FpEnv {
+    /// Creates a new environment with the rounding mode `rm`, and with no
+    /// raised flags.
+    pub fn new(rm: RoundingMode) -> Self {
+        FpEnv {
+            rm,
+            flags: OpStatus::OK,
+        }
+    }
+
+    /// Returns the rounding mode of the environment.
+    pub fn get_rounding_mode(&self) -> RoundingMode {
+        self.rm
+    }
+
+    /// Sets the rounding mode for the following operations.
+    pub fn set_rounding_mode(&mut self, rm: RoundingMode) {
+        self.rm = rm;
+    }
+
+    /// Returns the flags that were raised since the flags were cleared.
+    pub fn get_flags(&self) -> OpStatus {
+        self.flags
+    }
+
+    /// Lowers all of the flags.
+    pub fn clear_flags(&mut self) {
+        self.flags = OpStatus::OK;
+    }
+
+    /// Records the flags of an operation, and returns its result.
+    fn record<T>(&mut self, res: (T, OpStatus)) -> T {
+        self.flags |= res.1;
+        res.0
+    }
+
+    /// Computes a+b.
+    pub fn add<const E: usize, const M: usize, const P: usize>(
+        &mut self,
+        a: Float<E, M, P>,
+        b: Float<E, M, P>,
+    ) -> Float<E, M, P> {
+        self.record(Float::add_with_status(a, b, self.rm))
+    }
+
+    /// Computes a-b.
+    pub fn sub<const E: usize, const M: usize, const P: usize>(
+        &mut self,
+        a: Float<E, M, P>,
+        b: Float<E, M, P>,
+    ) -> Float<E, M, P> {
+        self.record(Float::sub_with_status(a, b, self.rm))
+    }
+
+    /// Computes a*b.
+    pub fn mul<const E: usize, const M: usize, const P: usize>(
+        &mut self,
+        a: Float<E, M, P>,
+        b: Float<E, M, P>,
+    ) -> Float<E, M, P> {
+        self.record(Float::mul_with_status(a, b, self.rm))
+    }
+
+    /// Computes a/b.
+    pub fn div<const E: usize, const M: usize, const P: usize>(
+        &mut self,
+        a: Float<E, M, P>,
+        b: Float<E, M, P>,
+    ) -> Float<E, M, P> {
+        self.record(Float::div_with_status(a, b, self.rm))
+    }
+
+    /// Converts `a` to another float format.
+    pub fn cast<
+        const E: usize,
+        const M: usize,
+        const P: usize,
+        const E2: usize,
+        const M2: usize,
+        const P2: usize,
+    >(
+        &mut self,
+        a: Float<E, M, P>,
+    ) -> Float<E2, M2, P2> {
+        self.record(a.cast_with_status(self.rm))
+    }
+}
+
+#[test]
+fn test_env_flags() {
+    use crate::{FP32, FP64};
+    let mut env = FpEnv::default();
+    let one = FP64::one(false);
+    let two = FP64::from_u64(2);
+
+    // Exact operations don't raise flags.
+    let three = env.add(one, two);
+    let six = env.mul(three, two);
+    assert_eq!(env.sub(six, three), three);
+    assert_eq!(env.div(six, two), three);
+    assert!(env.get_flags().is_ok());
+
+    // The flags are sticky.
+    let third = env.div(one, three);
+    assert_eq!(third.as_f64(), 1. / 3.);
+    assert_eq!(env.get_flags(), OpStatus::INEXACT);
+    env.div(one, FP64::zero(false));
+    env.add(one, one);
+    assert_eq!(env.get_flags(), OpStatus::INEXACT | OpStatus::DIV_BY_ZERO);
+    env.clear_flags();
+    assert!(env.get_flags().is_ok());
+
+    // Conversions.
+    let big: FP32 = env.cast(FP64::from_f64(1e300));
+    assert!(big.is_inf());
+    assert!(env.get_flags().contains(OpStatus::OVERFLOW));
+
+    // The rounding mode applies to all of the operations.
+    env.set_rounding_mode(RoundingMode::Negative);
+    assert!(matches!(env.get_rounding_mode(), RoundingMode::Negative));
+    let third_down = env.div(one, three);
+    env.set_rounding_mode(RoundingMode::Positive);
+    let third_up = env.div(one, three);
+    assert_eq!(third_down.next_up(), third_up);
+    env.set_rounding_mode(RoundingMode::Negative);
+    let third_down: FP32 = env.cast(third_down);
+    assert!(third_down.as_f32() < 1. / 3.);
+}
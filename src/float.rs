@@ -2,25 +2,169 @@ extern crate alloc;
 use super::bigint::BigInt;
 use super::bigint::LossFraction;
 use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter, Write};
+use core::num::FpCategory;
 
 #[cfg(feature = "std")]
-use std::println;
+use std::{println, string::String};
 
 /// Defines the supported rounding modes.
 /// See IEEE754-2019 Section 4.3 Rounding-direction attributes
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RoundingMode {
+    /// Round to the nearest number, and break ties to the even number.
     NearestTiesToEven,
+    /// Round to the nearest number, and break ties away from zero.
     NearestTiesToAway,
+    /// Round toward zero (truncate).
     Zero,
+    /// Round toward positive infinity.
     Positive,
+    /// Round toward negative infinity.
     Negative,
 }
 
+/// The IEEE754 exception flags that an operation can raise. The flags can be
+/// combined with the '|' operator, like in APFloat's opStatus.
+/// See IEEE754-2019 Section 7 Default exception handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OpStatus(u8);
+
+impl OpStatus {
+    /// The operation was exact.
+    pub const OK: Self = OpStatus(0);
+    /// The operation has no meaningful result, like inf - inf or 0 * inf.
+    pub const INVALID_OP: Self = OpStatus(1);
+    /// A finite non-zero number was divided by zero.
+    pub const DIV_BY_ZERO: Self = OpStatus(2);
+    /// The rounded result is too large to be represented as a finite number.
+    pub const OVERFLOW: Self = OpStatus(4);
+    /// The rounded result is inexact and below the normal range (tininess is
+    /// detected after rounding).
+    pub const UNDERFLOW: Self = OpStatus(8);
+    /// The result was rounded.
+    pub const INEXACT: Self = OpStatus(16);
+
+    /// Returns true if no flag is raised.
+    pub fn is_ok(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if all of the flags in `other` are raised.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for OpStatus {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        OpStatus(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for OpStatus {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// The reason that a checked operation failed. See the `checked_*` methods,
+/// like [`Float::checked_add`], and [`Float::try_cast`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithError {
+    /// One of the operands is NaN.
+    NanOperand,
+    /// The operation has no meaningful result, like inf - inf, 0 * inf or the
+    /// square root of a negative number.
+    InvalidOperation,
+    /// A finite non-zero number was divided by zero.
+    DivisionByZero,
+    /// The result is too large to be represented as a finite number.
+    Overflow,
+    /// The result is inexact, and below the normal range.
+    Underflow,
+    /// The result had to be rounded.
+    Inexact,
+}
+
+impl core::fmt::Display for ArithError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            ArithError::NanOperand => "operand is NaN",
+            ArithError::InvalidOperation => "invalid operation",
+            ArithError::DivisionByZero => "division by zero",
+            ArithError::Overflow => "overflow",
+            ArithError::Underflow => "underflow",
+            ArithError::Inexact => "inexact result",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArithError {}
+
+/// The error of a `TryFrom` conversion of a Float to a native number, that
+/// can't represent the value exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromFloatError(pub(crate) ());
+
+impl core::fmt::Display for TryFromFloatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("the value can't be converted exactly")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromFloatError {}
+
+/// The error of parsing a string that is not a valid number. The positions
+/// are byte offsets in the string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseFloatError {
+    /// The number has no digits, like "" or "-.e5".
+    EmptyMantissa,
+    /// The character at `position` is not a valid digit, or is not valid at
+    /// this place in the number, like the second '.' in "1.2.3".
+    InvalidDigit {
+        /// The byte offset of the character.
+        position: usize,
+    },
+    /// The exponent has no digits, like "1e" or "1e+".
+    EmptyExponent,
+}
+
+impl core::fmt::Display for ParseFloatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseFloatError::EmptyMantissa => {
+                f.write_str("no digits in float literal")
+            }
+            ParseFloatError::InvalidDigit { position } => {
+                write!(
+                    f,
+                    "invalid digit at position {} in float literal",
+                    position
+                )
+            }
+            ParseFloatError::EmptyExponent => {
+                f.write_str("no digits in the exponent of float literal")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseFloatError {}
+
 /// Declare the different categories of the floating point number. These
 /// categories are internal to the float, and can be access by the acessors:
 /// is_inf, is_zero, is_nan, is_normal.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Category {
     Infinity,
     NaN,
@@ -32,7 +176,7 @@ pub enum Category {
 /// arbitrary-precision floating-point number. The data structure is generic
 /// and accepts the EXPONENT and MANTISSA constants, that represent the encoding
 /// number of bits that are dedicated to storing these values.
-#[derive(Debug, Clone, Copy)]
+#[derive(Clone, Copy)]
 pub struct Float<
     const EXPONENT: usize,
     const MANTISSA: usize,
@@ -66,7 +210,7 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     }
 
     /// Create a new normal floating point number.
-    pub fn raw(
+    pub const fn raw(
         sign: bool,
         exp: i64,
         mantissa: BigInt<PARTS>,
@@ -121,6 +265,65 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             category: Category::NaN,
         }
     }
+
+    /// Returns a new NaN float that carries the payload `payload`. All NaNs
+    /// are quiet, and the payload is stored in the MANTISSA - 1 bits of the
+    /// trailing significand that are below the quiet bit. Higher bits of the
+    /// payload are dropped.
+    pub fn nan_with_payload(sign: bool, payload: BigInt<PARTS>) -> Self {
+        let mut mantissa = payload;
+        mantissa.mask(MANTISSA - 1);
+        Float {
+            sign,
+            exp: 0,
+            mantissa,
+            category: Category::NaN,
+        }
+    }
+
+    /// Returns the payload of the NaN, or None if the number is not a NaN.
+    /// Arithmetic operations and casts propagate the payload of their NaN
+    /// operand (the first one, if both are NaN). Invalid operations and the
+    /// other functions in the library return NaNs with a zero payload.
+    pub fn get_nan_payload(&self) -> Option<BigInt<PARTS>> {
+        if self.is_nan() {
+            return Some(self.mantissa);
+        }
+        None
+    }
+
+    /// Returns the largest finite number.
+    pub fn largest(sign: bool) -> Self {
+        let precision = Self::get_precision() as usize;
+        let exp_max = Self::get_exp_bounds().1;
+        Self::new(sign, exp_max, BigInt::all1s(precision))
+    }
+
+    /// Returns the smallest positive (or negative) number, which is
+    /// subnormal.
+    pub fn smallest(sign: bool) -> Self {
+        let exp_min = Self::get_exp_bounds().0;
+        Self::new(sign, exp_min, BigInt::one())
+    }
+
+    /// Returns the smallest positive (or negative) normal number.
+    pub fn smallest_normal(sign: bool) -> Self {
+        let exp_min = Self::get_exp_bounds().0;
+        Self::new(sign, exp_min, BigInt::one_hot(MANTISSA))
+    }
+
+    /// Returns the machine epsilon: the distance between one and the next
+    /// representable number, which is 2^-MANTISSA.
+    pub fn epsilon() -> Self {
+        let one = BigInt::one_hot(MANTISSA);
+        let mut eps = Self::new(false, -(MANTISSA as i64), one);
+        // The exponent may be below the range of tiny formats.
+        eps.normalize(
+            RoundingMode::NearestTiesToEven,
+            LossFraction::ExactlyZero,
+        );
+        eps
+    }
     /// Returns true if the Float is negative
     pub fn is_negative(&self) -> bool {
         self.sign
@@ -158,6 +361,36 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         false
     }
 
+    /// Returns true if this number is subnormal (denormal): non-zero, and
+    /// smaller than the smallest normal number.
+    pub fn is_subnormal(&self) -> bool {
+        self.is_normal()
+            && self.mantissa.msb_index() < Self::get_precision() as usize
+    }
+
+    /// Returns true if this number is neither infinite nor NaN.
+    pub fn is_finite(&self) -> bool {
+        self.is_normal() || self.is_zero()
+    }
+
+    /// Returns the floating point category of the number, like f64::classify.
+    /// Notice that is_normal() is also true for subnormal numbers, but this
+    /// method returns FpCategory::Subnormal for them.
+    pub fn classify(&self) -> FpCategory {
+        match self.category {
+            Category::Infinity => FpCategory::Infinite,
+            Category::NaN => FpCategory::Nan,
+            Category::Zero => FpCategory::Zero,
+            Category::Normal => {
+                if self.is_subnormal() {
+                    FpCategory::Subnormal
+                } else {
+                    FpCategory::Normal
+                }
+            }
+        }
+    }
+
     /// Update the sign of the float to `sign`. True means negative.
     pub fn set_sign(&mut self, sign: bool) {
         self.sign = sign
@@ -195,35 +428,74 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         let bits =
             Self::get_precision() as i64 - self.mantissa.msb_index() as i64;
         if bits > 0 {
-            self.exp += bits;
+            self.exp -= bits;
             self.mantissa.shift_left(bits as usize);
         }
     }
 
-    /// Prints the number using the internal representation.
-    #[cfg(feature = "std")]
-    pub fn dump(&self) {
+    /// Writes the internal representation of the number to `w`, like
+    /// "FP[+ E=   3 M = 11110000000]". This works without the standard
+    /// library, so it can print to a serial port on embedded targets.
+    pub fn write_dump(&self, w: &mut impl Write) -> core::fmt::Result {
         let sign = if self.sign { "-" } else { "+" };
         match self.category {
-            Category::NaN => {
-                println!("[{}NaN]", sign);
-            }
-            Category::Infinity => {
-                println!("[{}Inf]", sign);
-            }
-            Category::Zero => {
-                println!("[{}0.0]", sign);
-            }
+            Category::NaN => write!(w, "[{}NaN]", sign),
+            Category::Infinity => write!(w, "[{}Inf]", sign),
+            Category::Zero => write!(w, "[{}0.0]", sign),
             Category::Normal => {
+                write!(w, "FP[{} E={:4} M = ", sign, self.exp)?;
                 let m = self.mantissa;
-                println!("FP[{} E={:4} M = {}]", sign, self.exp, m.as_str());
+                for i in (0..m.msb_index().max(1)).rev() {
+                    let bit = (m.get_part(i / 64) >> (i % 64)) & 1;
+                    w.write_char(if bit == 1 { '1' } else { '0' })?;
+                }
+                w.write_char(']')
             }
         }
     }
 
+    /// Prints the number using the internal representation.
+    #[cfg(feature = "std")]
+    pub fn dump(&self) {
+        let mut s = String::new();
+        self.write_dump(&mut s).unwrap();
+        println!("{}", s);
+    }
+
+    /// Returns the biased exponent of the IEEE754 encoding of the number.
+    fn get_biased_exp(&self) -> i64 {
+        match self.category {
+            Category::Infinity | Category::NaN => (1 << EXPONENT) - 1,
+            Category::Zero => 0,
+            Category::Normal if self.is_subnormal() => 0,
+            Category::Normal => self.exp + Self::get_bias(),
+        }
+    }
+
+    /// The number of significant binary digits, including the integer bit.
+    pub const MANTISSA_DIGITS: u32 = MANTISSA as u32 + 1;
+
+    /// One greater than the exponent of the smallest normal number, like
+    /// f64::MIN_EXP. The numbers are in the form 0.1xxx * 2^exp.
+    pub const MIN_EXP: i32 = 3 - (1 << (EXPONENT - 1));
+
+    /// One greater than the exponent of the largest finite number, like
+    /// f64::MAX_EXP.
+    pub const MAX_EXP: i32 = 1 << (EXPONENT - 1);
+
+    /// The number of decimal digits that can be converted to this type and
+    /// back without change: floor((MANTISSA_DIGITS - 1) * log10(2)).
+    pub const DIGITS10: u32 = (MANTISSA as u128 * LOG10_2 / LOG10_2_ONE) as u32;
+
+    /// The number of decimal digits that are needed to convert any number of
+    /// this type to decimal and back without change:
+    /// ceil(MANTISSA_DIGITS * log10(2)) + 1.
+    pub const MAX_DIGITS10: u32 =
+        ((MANTISSA as u128 + 1) * LOG10_2 / LOG10_2_ONE) as u32 + 2;
+
     /// Returns the exponent bias for the number, as a positive number.
     /// https://en.wikipedia.org/wiki/IEEE_754#Basic_and_interchange_formats
-    pub(crate) fn get_bias() -> i64 {
+    pub(crate) const fn get_bias() -> i64 {
         ((1 << (EXPONENT - 1)) - 1) as i64
     }
 
@@ -242,21 +514,39 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     }
 }
 
+/// The value of log10(2), as a fixed-point number with 18 decimal digits.
+/// The product of log10(2) and the precision is never an integer, so the
+/// truncation does not change the number of digits.
+const LOG10_2: u128 = 301_029_995_663_981_195;
+const LOG10_2_ONE: u128 = 1_000_000_000_000_000_000;
+
 // IEEE 754-2019
 // Table 3.5 — Binary interchange format parameters.
 
 /// Predefined FP16 float with 5 exponent bits, and 10 mantissa bits.
 pub type FP16 = new_float_type!(5, 10);
+/// Predefined bfloat16 float with 8 exponent bits, and 7 mantissa bits. The
+/// format has the exponent range of FP32, and is the upper half of its bits.
+pub type BF16 = new_float_type!(8, 7);
+/// Predefined TF32 float with 8 exponent bits, and 10 mantissa bits. This is
+/// the input format of the NVIDIA tensor cores, with the exponent range of FP32
+/// and the precision of FP16.
+pub type TF32 = new_float_type!(8, 10);
 /// Predefined FP32 float with 8 exponent bits, and 23 mantissa bits.
 pub type FP32 = new_float_type!(8, 23);
 /// Predefined FP64 float with 11 exponent bits, and 52 mantissa bits.
 pub type FP64 = new_float_type!(11, 52);
+/// Predefined x87 extended precision float with 15 exponent bits, and 63
+/// mantissa bits. The encoding stores the integer bit explicitly, see
+/// `as_fp80_bits` and `from_fp80_bits`.
+pub type FP80 = new_float_type!(15, 63);
 /// Predefined FP128 float with 15 exponent bits, and 112 mantissa bits.
 pub type FP128 = new_float_type!(15, 112);
-/// Predefined FP256 float with 19 exponent bits, and 236 mantissa bits.
+/// Predefined FP256 (binary256, octuple precision) float with 19 exponent
+/// bits, and 236 mantissa bits.
 pub type FP256 = new_float_type!(19, 236);
 
-//// Shift `val` by `bits`, and report the loss.
+/// Shift `val` by `bits`, and report the loss.
 pub(crate) fn shift_right_with_loss<const P: usize>(
     mut val: BigInt<P>,
     bits: u64,
@@ -304,9 +594,8 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     /// The number overflowed, set the right value based on the rounding mode
     /// and sign.
     fn overflow(&mut self, rm: RoundingMode) {
-        let bounds = Self::get_exp_bounds();
         let inf = Self::inf(self.sign);
-        let max = Self::new(self.sign, bounds.1, BigInt::all1s(MANTISSA));
+        let max = Self::largest(self.sign);
 
         *self = match rm {
             RoundingMode::NearestTiesToEven => inf,
@@ -374,10 +663,15 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
 
     /// Normalize the number by adjusting the exponent to the legal range, shift
     /// the mantissa to the msb, and round the number if bits are lost. This is
-    /// based on Neil Booth' implementation in APFloat.
-    pub(crate) fn normalize(&mut self, rm: RoundingMode, loss: LossFraction) {
+    /// based on Neil Booth' implementation in APFloat. Returns the exception
+    /// flags that the rounding raised.
+    pub(crate) fn normalize(
+        &mut self,
+        rm: RoundingMode,
+        loss: LossFraction,
+    ) -> OpStatus {
         if !self.is_normal() {
-            return;
+            return OpStatus::OK;
         }
         let mut loss = loss;
         let bounds = Self::get_exp_bounds();
@@ -393,7 +687,7 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             if self.exp + exp_change > bounds.1 {
                 self.overflow(rm);
                 self.check_bounds();
-                return;
+                return OpStatus::OVERFLOW | OpStatus::INEXACT;
             }
 
             // Handle underflowing low exponents. Don't allow to go below the
@@ -406,7 +700,7 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
                 // Handle reducing the exponent.
                 debug_assert!(loss.is_exactly_zero(), "losing information");
                 self.shift_significand_left(-exp_change as u64);
-                return;
+                return OpStatus::OK;
             }
 
             if exp_change > 0 {
@@ -423,9 +717,8 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             // Canonicalize to zero.
             if self.mantissa.is_zero() {
                 *self = Self::zero(self.sign);
-                return;
             }
-            return;
+            return OpStatus::OK;
         }
 
         // Check if we need to round away from zero.
@@ -445,7 +738,7 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
                     self.shift_significand_right(1);
                 } else {
                     *self = Self::inf(self.sign);
-                    return;
+                    return OpStatus::OVERFLOW | OpStatus::INEXACT;
                 }
             }
         }
@@ -454,9 +747,35 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         if self.mantissa.is_zero() {
             *self = Self::zero(self.sign);
         }
+
+        // The result is inexact. Report an underflow if the rounded result is
+        // zero or subnormal.
+        let precision = Self::get_precision() as usize;
+        if self.is_zero() || self.mantissa.msb_index() < precision {
+            return OpStatus::UNDERFLOW | OpStatus::INEXACT;
+        }
+        OpStatus::INEXACT
     } // round.
 }
 
+/// Prints the internal representation of the number: the sign, the category,
+/// the unbiased and the biased exponent, and the parts of the mantissa in hex.
+/// This works without the standard library, unlike [`Float::dump`].
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Debug
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let sign = if self.sign { '-' } else { '+' };
+        f.debug_struct("Float")
+            .field("sign", &sign)
+            .field("category", &self.category)
+            .field("exp", &self.exp)
+            .field("biased_exp", &self.get_biased_exp())
+            .field("mantissa", &self.mantissa)
+            .finish()
+    }
+}
+
 impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> PartialEq
     for Float<EXPONENT, MANTISSA, PARTS>
 {
@@ -515,6 +834,9 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
                     bool_to_ord(!other.sign)
                 } else if self.exp > other.exp {
                     bool_to_ord(self.sign)
+                } else if self.sign {
+                    // Larger negative mantissas are smaller numbers.
+                    Some(other.mantissa.cmp(&self.mantissa))
                 } else {
                     Some(self.mantissa.cmp(&other.mantissa))
                 }
@@ -523,6 +845,414 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     }
 }
 
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Compares the number to `other` using the totalOrder predicate of
+    /// IEEE 754-2019 section 5.10. The order is:
+    /// -NaN < -inf < negative numbers < -0 < +0 < positive numbers < +inf <
+    /// +NaN, and NaNs of the same sign are ordered by their payload. Unlike
+    /// partial_cmp, this is a total order that can be used for sorting.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        if self.sign != other.sign {
+            return if self.sign {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+        }
+
+        // Order the magnitudes: zero < normal < inf < NaN.
+        let rank = |x: &Self| match x.category {
+            Category::Zero => 0,
+            Category::Normal => 1,
+            Category::Infinity => 2,
+            Category::NaN => 3,
+        };
+        let magnitude =
+            rank(self)
+                .cmp(&rank(other))
+                .then_with(|| match self.category {
+                    Category::Normal => self
+                        .exp
+                        .cmp(&other.exp)
+                        .then_with(|| self.mantissa.cmp(&other.mantissa)),
+                    Category::NaN => self.mantissa.cmp(&other.mantissa),
+                    Category::Zero | Category::Infinity => Ordering::Equal,
+                });
+
+        // Larger negative magnitudes are smaller numbers.
+        if self.sign {
+            magnitude.reverse()
+        } else {
+            magnitude
+        }
+    }
+
+    /// Returns the exponent and the significand of a normal or subnormal
+    /// number, so that the value is significand * 2^(exponent - MANTISSA),
+    /// and the significand is normalized (the MSB is at index MANTISSA). The
+    /// exponent of subnormal numbers is below the exponent range.
+    fn get_normalized(&self) -> (i64, BigInt<PARTS>) {
+        debug_assert!(self.is_normal());
+        let msb = self.mantissa.msb_index() as i64;
+        let shift = MANTISSA as i64 + 1 - msb;
+        let mut significand = self.mantissa;
+        significand.shift_left(shift as usize);
+        (self.exp - shift, significand)
+    }
+
+    /// Compares the exact values of the number and `other`, which can be of a
+    /// different format, without rounding either of them. Returns None if one
+    /// of the numbers is NaN, like partial_cmp.
+    ///```
+    ///    use arpfloat::{FP16, FP128};
+    ///    use core::cmp::Ordering;
+    ///    let x = FP128::from_f64(0.1);
+    ///    let y: FP16 = x.cast();
+    ///    assert_eq!(y.cmp_cross(&x), Some(Ordering::Less));
+    ///    assert!(!y.eq_cross(&x) && y == x.cast());
+    ///```
+    pub fn cmp_cross<const E: usize, const M: usize, const P: usize>(
+        &self,
+        other: &Float<E, M, P>,
+    ) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        // Zeros are equal, regardless of their sign.
+        let sign_a = self.sign && !self.is_zero();
+        let sign_b = other.get_sign() && !other.is_zero();
+        if sign_a != sign_b {
+            return Some(if sign_a {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            });
+        }
+
+        // Order the magnitudes: zero < normal < inf.
+        let rank = |category: Category| match category {
+            Category::Zero => 0,
+            Category::Normal => 1,
+            _ => 2,
+        };
+        let rank_a = rank(self.category);
+        let mut magnitude = rank_a.cmp(&rank(other.get_category()));
+        if magnitude == Ordering::Equal && self.is_normal() {
+            let (exp_a, sig_a) = self.get_normalized();
+            let (exp_b, sig_b) = other.get_normalized();
+            magnitude = exp_a.cmp(&exp_b).then_with(|| {
+                // Align the significands to the wider of the two formats.
+                if MANTISSA <= M {
+                    let mut sig_a = sig_a.cast::<P>();
+                    sig_a.shift_left(M - MANTISSA);
+                    sig_a.cmp(&sig_b)
+                } else {
+                    let mut sig_b = sig_b.cast::<PARTS>();
+                    sig_b.shift_left(MANTISSA - M);
+                    sig_a.cmp(&sig_b)
+                }
+            });
+        }
+
+        // Larger negative magnitudes are smaller numbers.
+        Some(if sign_a {
+            magnitude.reverse()
+        } else {
+            magnitude
+        })
+    }
+
+    /// Returns true if the number and `other`, which can be of a different
+    /// format, have the same exact value. NaN is not equal to any number, and
+    /// zeros of different signs are equal.
+    pub fn eq_cross<const E: usize, const M: usize, const P: usize>(
+        &self,
+        other: &Float<E, M, P>,
+    ) -> bool {
+        self.cmp_cross(other) == Some(Ordering::Equal)
+    }
+}
+
+/// A wrapper that orders the number with the totalOrder predicate (see
+/// [`Float::total_cmp`]), and implements `Eq` and `Ord`. This allows sorting
+/// numbers that may be NaN, and using them as keys of ordered collections.
+/// Zeros of different signs and NaNs with different payloads are different
+/// keys.
+///```
+///    use arpfloat::{TotalFloat, FP64};
+///    let vals = [3.0, f64::NAN, -0.0, 0.0].map(FP64::from_f64);
+///    let max = vals.iter().max_by_key(|x| TotalFloat(**x)).unwrap();
+///    assert!(max.is_nan());
+///    let min = vals.iter().copied().map(TotalFloat).min().unwrap();
+///    assert!(min.0.is_zero() && min.0.is_negative());
+///```
+#[derive(Debug, Clone, Copy)]
+pub struct TotalFloat<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>(pub Float<EXPONENT, MANTISSA, PARTS>);
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    From<Float<EXPONENT, MANTISSA, PARTS>>
+    for TotalFloat<EXPONENT, MANTISSA, PARTS>
+{
+    fn from(val: Float<EXPONENT, MANTISSA, PARTS>) -> Self {
+        TotalFloat(val)
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> PartialEq
+    for TotalFloat<EXPONENT, MANTISSA, PARTS>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Eq
+    for TotalFloat<EXPONENT, MANTISSA, PARTS>
+{
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    PartialOrd for TotalFloat<EXPONENT, MANTISSA, PARTS>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Ord
+    for TotalFloat<EXPONENT, MANTISSA, PARTS>
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// A format that holds all of the native integers exactly.
+type NativeInt = new_float_type!(8, 127);
+
+/// Implements the comparisons with native numbers, in both directions. The
+/// native number is converted to the exact format `$fmt` with `$func`, and the
+/// comparison is exact.
+macro_rules! impl_cmp_native {
+    ($fmt:ty, $func:ident, $($ty:ty),*) => {
+        $(
+            impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+                PartialEq<$ty> for Float<EXPONENT, MANTISSA, PARTS>
+            {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.eq_cross(&<$fmt>::$func((*other).into()))
+                }
+            }
+
+            impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+                PartialOrd<$ty> for Float<EXPONENT, MANTISSA, PARTS>
+            {
+                fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+                    self.cmp_cross(&<$fmt>::$func((*other).into()))
+                }
+            }
+
+            impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+                PartialEq<Float<EXPONENT, MANTISSA, PARTS>> for $ty
+            {
+                fn eq(&self, other: &Float<EXPONENT, MANTISSA, PARTS>) -> bool {
+                    other == self
+                }
+            }
+
+            impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+                PartialOrd<Float<EXPONENT, MANTISSA, PARTS>> for $ty
+            {
+                fn partial_cmp(
+                    &self,
+                    other: &Float<EXPONENT, MANTISSA, PARTS>,
+                ) -> Option<Ordering> {
+                    other.partial_cmp(self).map(Ordering::reverse)
+                }
+            }
+        )*
+    };
+}
+
+impl_cmp_native!(FP32, from_f32, f32);
+impl_cmp_native!(FP64, from_f64, f64);
+impl_cmp_native!(NativeInt, from_u128, u8, u16, u32, u64, u128);
+impl_cmp_native!(NativeInt, from_i128, i8, i16, i32, i64, i128);
+
+#[test]
+fn test_cmp_native() {
+    let x = FP64::from_f64(1.5);
+    assert!(x > 1.0 && x < 2.0 && x == 1.5 && x != 1.25);
+    assert!(1.0 < x && 2.0 > x && 1.5 == x && 1.5f32 == x);
+    assert!(x > 1 && x < 2u8 && x > -7i128 && 2 > x);
+    assert!(FP64::zero(true) == 0 && FP64::zero(false) == -0.0);
+    assert!(FP64::inf(false) > u128::MAX && FP64::inf(true) < f64::MIN);
+    assert!(FP64::nan(false) != FP64::nan(false).as_f64());
+    assert_eq!(FP64::nan(false).partial_cmp(&1.0), None);
+    assert_eq!(1u32.partial_cmp(&FP64::nan(true)), None);
+
+    // The comparisons are exact, and are not done in the narrower format.
+    let x: FP16 = FP64::from_f64(0.1).cast();
+    assert!(x != 0.1 && x < 0.1 && x == x.as_f64());
+    assert!(FP16::from_u64(2049) == 2048 && FP16::from_u64(2048) < 2049);
+    let big = FP256::from_u128(u128::MAX);
+    assert!(big == u128::MAX && big > u128::MAX - 1 && big < u128::MAX as f64);
+    let big = FP128::from_u128(u128::MAX);
+    assert!(big > u128::MAX && big == u128::MAX as f64);
+    let min = FP128::from_i128(i128::MIN);
+    assert!(
+        min == i128::MIN && min < i128::MIN + 1 && min > -(u128::MAX as f64)
+    );
+    assert!(FP32::from_f64(0.1) == 0.1f32 && FP32::from_f64(0.1) != 0.1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_classify() {
+    use super::utils;
+    use super::FP32;
+
+    let mut values = utils::get_special_test_values().to_vec();
+    values.extend([f64::from_bits(1), -f64::MIN_POSITIVE, 1e-310, 1e-300]);
+    for v in values {
+        let x = FP64::from_f64(v);
+        assert_eq!(x.classify(), v.classify(), "{}", v);
+        assert_eq!(x.is_finite(), v.is_finite());
+        assert_eq!(x.is_subnormal(), v.is_subnormal());
+    }
+
+    // Values that are subnormal in a narrow type.
+    let x: FP32 = FP64::from_f64(1e-40).cast();
+    assert_eq!(x.classify(), FpCategory::Subnormal);
+    let x: FP32 = FP64::from_f64(1e-37).cast();
+    assert_eq!(x.classify(), FpCategory::Normal);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_total_cmp() {
+    use super::utils;
+    use std::vec::Vec;
+
+    let mut values = utils::get_special_test_values().to_vec();
+    for bits in [0x7ff8_0000_0000_0000, 0x7ff8_0000_0000_0001, 0x7fff_ffff] {
+        values.push(f64::from_bits(bits | 0x7ff8_0000_0000_0000));
+        values.push(-f64::from_bits(bits | 0x7ff8_0000_0000_0000));
+    }
+    values.push(f64::from_bits(1));
+    values.push(-f64::from_bits(1));
+
+    // Check that the order matches the order of doubles.
+    for first in &values {
+        for second in &values {
+            let expected = first.total_cmp(second);
+            let a = FP64::from_f64(*first);
+            let b = FP64::from_f64(*second);
+            assert_eq!(a.total_cmp(&b), expected, "{} {}", first, second);
+        }
+    }
+
+    // Sort some numbers.
+    let mut sorted: Vec<FP64> =
+        values.iter().map(|x| FP64::from_f64(*x)).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let sorted: Vec<u64> =
+        sorted.iter().map(|x| x.as_f64().to_bits()).collect();
+    values.sort_by(|a, b| a.total_cmp(b));
+    let values: Vec<u64> = values.iter().map(|x| x.to_bits()).collect();
+    assert_eq!(sorted, values);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_total_float() {
+    use std::collections::BTreeMap;
+    use std::vec::Vec;
+
+    let nan = FP64::nan(false);
+    assert!(TotalFloat(nan) == TotalFloat(nan));
+    assert!(TotalFloat(FP64::zero(true)) != TotalFloat(FP64::zero(false)));
+    assert!(TotalFloat(FP64::zero(true)) < FP64::zero(false).into());
+    assert!(TotalFloat(FP64::nan(true)) < TotalFloat(FP64::inf(true)));
+
+    // Sort the numbers, and use them as keys.
+    let values = [2.5, f64::NAN, -0.0, -f64::NAN, 0.0, 2.5, f64::INFINITY];
+    let mut sorted: Vec<_> = values
+        .iter()
+        .map(|x| TotalFloat(FP64::from_f64(*x)))
+        .collect();
+    sorted.sort();
+    let sorted: Vec<u64> =
+        sorted.iter().map(|x| x.0.as_f64().to_bits()).collect();
+    let mut expected = values;
+    expected.sort_by(|a, b| a.total_cmp(b));
+    let expected: Vec<u64> = expected.iter().map(|x| x.to_bits()).collect();
+    assert_eq!(sorted, expected);
+
+    let mut map = BTreeMap::new();
+    for x in values {
+        *map.entry(TotalFloat(FP64::from_f64(x))).or_insert(0) += 1;
+    }
+    assert_eq!(map.len(), 6);
+    assert_eq!(map[&TotalFloat(FP64::from_f64(2.5))], 2);
+    assert!(map.keys().next_back().unwrap().0.is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_cmp_cross() {
+    use super::utils;
+    use super::{BF16, FP128, FP16, FP32};
+
+    // Check that the order of FP32 and FP64 numbers matches the order of the
+    // doubles. Narrow numbers with the same value are equal.
+    for first in utils::get_special_test_values() {
+        for second in utils::get_special_test_values() {
+            let expected = (first as f32 as f64).partial_cmp(&second);
+            let a = FP32::from_f64(first);
+            let b = FP64::from_f64(second);
+            assert_eq!(a.cmp_cross(&b), expected, "{} {}", first, second);
+            let expected = expected.map(|o| o.reverse());
+            assert_eq!(b.cmp_cross(&a), expected, "{} {}", first, second);
+        }
+    }
+
+    // Numbers that round to the same narrow number are not equal.
+    let x = FP128::from_f64(0.1);
+    let y: FP16 = x.cast();
+    assert!(y == x.cast());
+    assert!(!y.eq_cross(&x));
+    assert!(y.eq_cross(&y.cast::<15, 112, 4>()));
+    let tiny = FP128::from_f64(1e-30);
+    assert_eq!(FP16::zero(true).cmp_cross(&tiny), Some(Ordering::Less));
+    assert_eq!(tiny.cmp_cross(&FP16::zero(true)), Some(Ordering::Greater));
+    assert!(FP16::zero(true).eq_cross(&FP128::zero(false)));
+
+    // Formats where neither holds all of the numbers of the other.
+    let a = BF16::from_f64(1e-6);
+    let b = FP16::from_f64(1e-6);
+    assert_eq!(a.cmp_cross(&b), Some(Ordering::Less));
+    assert_eq!(a.as_f64().partial_cmp(&b.as_f64()), Some(Ordering::Less));
+    let a = BF16::from_f64(1.5);
+    assert!(a.eq_cross(&FP16::from_f64(1.5)));
+    let inf = FP16::inf(false);
+    assert_eq!(BF16::from_f64(1e10).cmp_cross(&inf), Some(Ordering::Less));
+    assert_eq!(inf.cmp_cross(&BF16::inf(false)), Some(Ordering::Equal));
+
+    // Subnormal numbers.
+    let a = FP16::from_f64(2f64.powi(-24));
+    let b = FP32::from_f64(2f64.powi(-24));
+    assert!(a.eq_cross(&b) && b.eq_cross(&a));
+    let c = FP32::from_f64(2f64.powi(-24) * (1. + 2f64.powi(-20)));
+    assert_eq!(a.cmp_cross(&c), Some(Ordering::Less));
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn test_comparisons() {
@@ -545,8 +1275,164 @@ fn test_comparisons() {
     }
 }
 
+#[test]
+fn test_comparisons_same_exponent() {
+    use super::utils::Lfsr;
+
+    assert!(FP64::from_f64(-1.5) < FP64::from_f64(-1.0));
+    assert!(FP64::from_f64(1.5) > FP64::from_f64(1.0));
+
+    // Compare random values, including values with the same exponent.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..1000 {
+        let v0 = f64::from_bits(lfsr.get64());
+        let v1 = f64::from_bits(v0.to_bits() ^ (lfsr.get64() & 0xffff));
+        let f0 = FP64::from_f64(v0);
+        let f1 = FP64::from_f64(v1);
+        assert_eq!(v0.partial_cmp(&v1), f0.partial_cmp(&f1));
+    }
+}
+
+#[test]
+fn test_align_mantissa() {
+    // The smallest subnormal is 1 * 2^(min_exp - 52). The aligned mantissa is
+    // 2^52, so the exponent must drop by 52 to keep the value.
+    let mut x = FP64::from_f64(f64::from_bits(1));
+    let exp = x.get_exp();
+    x.align_mantissa();
+    assert_eq!(x.get_mantissa().msb_index(), 53);
+    assert_eq!(x.get_exp(), exp - 52);
+
+    // Normal numbers are already aligned.
+    let mut y = FP64::from_f64(-3.5);
+    y.align_mantissa();
+    assert_eq!(y.get_exp(), 1);
+    assert_eq!(y.as_f64(), -3.5);
+}
+
 #[test]
 fn test_one_imm() {
     let x = FP64::one(false);
     assert_eq!(x.as_f64(), 1.0);
 }
+
+#[test]
+fn test_format_metadata() {
+    use super::{FP128, FP16, FP32};
+    assert_eq!(FP64::MANTISSA_DIGITS, f64::MANTISSA_DIGITS);
+    assert_eq!(FP64::MIN_EXP, f64::MIN_EXP);
+    assert_eq!(FP64::MAX_EXP, f64::MAX_EXP);
+    assert_eq!(FP64::DIGITS10, f64::DIGITS);
+    assert_eq!(FP64::MAX_DIGITS10, 17);
+    assert_eq!(FP32::MANTISSA_DIGITS, f32::MANTISSA_DIGITS);
+    assert_eq!(FP32::MIN_EXP, f32::MIN_EXP);
+    assert_eq!(FP32::MAX_EXP, f32::MAX_EXP);
+    assert_eq!(FP32::DIGITS10, f32::DIGITS);
+    assert_eq!(FP32::MAX_DIGITS10, 9);
+    assert_eq!(FP16::MANTISSA_DIGITS, 11);
+    assert_eq!(FP16::MIN_EXP, -13);
+    assert_eq!(FP16::MAX_EXP, 16);
+    assert_eq!(FP16::DIGITS10, 3);
+    assert_eq!(FP16::MAX_DIGITS10, 5);
+    assert_eq!(FP128::MANTISSA_DIGITS, 113);
+    assert_eq!(FP128::MIN_EXP, -16381);
+    assert_eq!(FP128::MAX_EXP, 16384);
+    assert_eq!(FP128::DIGITS10, 33);
+    assert_eq!(FP128::MAX_DIGITS10, 36);
+
+    // Check that the constants agree with the exponent bounds.
+    let (exp_min, exp_max) = FP128::get_exp_bounds();
+    assert_eq!(FP128::MIN_EXP as i64, exp_min + 1);
+    assert_eq!(FP128::MAX_EXP as i64, exp_max + 1);
+}
+
+#[test]
+fn test_limits() {
+    use super::{FP16, FP32};
+    assert_eq!(FP64::largest(false).as_f64(), f64::MAX);
+    assert_eq!(FP64::largest(true).as_f64(), f64::MIN);
+    assert_eq!(FP64::smallest(false).as_f64(), f64::from_bits(1));
+    assert_eq!(FP64::smallest_normal(false).as_f64(), f64::MIN_POSITIVE);
+    assert_eq!(FP64::epsilon().as_f64(), f64::EPSILON);
+    assert_eq!(FP32::largest(false).as_f32(), f32::MAX);
+    assert_eq!(FP32::smallest(true).as_f32(), -f32::from_bits(1));
+    assert_eq!(FP32::smallest_normal(false).as_f32(), f32::MIN_POSITIVE);
+    assert_eq!(FP32::epsilon().as_f32(), f32::EPSILON);
+    assert_eq!(FP16::largest(false).as_f64(), 65504.);
+    assert_eq!(FP16::smallest(false).as_f64(), 2f64.powi(-24));
+    assert_eq!(FP16::smallest_normal(false).as_f64(), 2f64.powi(-14));
+    assert_eq!(FP16::epsilon().as_f64(), 2f64.powi(-10));
+
+    // The largest number is the next number below infinity, and overflow
+    // with rounding to zero produces it.
+    let max = FP64::largest(false);
+    assert_eq!(max, FP64::inf(false).next_down());
+    assert!(max.next_up().is_inf());
+    let big = FP64::mul_with_rm(max, max, RoundingMode::Zero);
+    assert_eq!(big, max);
+    assert_eq!(big.get_mantissa(), max.get_mantissa());
+    assert_eq!(FP64::smallest(false).next_down(), FP64::zero(false));
+    let one = FP64::one(false);
+    assert_eq!(one.next_up() - one, FP64::epsilon());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_debug() {
+    use super::FP16;
+    use std::format;
+    let x = FP16::from_f64(-1.5);
+    assert_eq!(
+        format!("{:?}", x),
+        "Float { sign: '-', category: Normal, exp: 0, biased_exp: 15, \
+         mantissa: [0x0000000000000600] }"
+    );
+    let x = FP16::smallest(false);
+    let s = format!("{:?}", x);
+    assert!(s.contains("exp: -14, biased_exp: 0"), "{}", s);
+    assert!(s.ends_with("mantissa: [0x0000000000000001] }"), "{}", s);
+    let s = format!("{:?}", FP16::inf(false));
+    assert!(s.contains("category: Infinity"), "{}", s);
+    assert!(s.contains("biased_exp: 31"), "{}", s);
+    let s = format!("{:?}", FP128::zero(true));
+    assert!(s.contains("sign: '-', category: Zero"), "{}", s);
+    assert!(
+        s.ends_with("0x0000000000000000, 0x0000000000000000] }"),
+        "{}",
+        s
+    );
+
+    // The parts are printed from the most significant part.
+    let x = BigInt::<2>::from_parts(&[1, 1 << 63]);
+    let s = format!("{:?}", x);
+    assert_eq!(s, "[0x8000000000000000, 0x0000000000000001]");
+}
+
+#[test]
+fn test_write_dump() {
+    use super::FP16;
+    use alloc::string::String;
+    let dump = |x: &dyn Fn(&mut String) -> core::fmt::Result| {
+        let mut s = String::new();
+        x(&mut s).unwrap();
+        s
+    };
+    let x = FP16::from_i64(15);
+    assert_eq!(dump(&|s| x.write_dump(s)), "FP[+ E=   3 M = 11110000000]");
+    let x = FP16::from_f64(-0.375);
+    assert_eq!(dump(&|s| x.write_dump(s)), "FP[- E=  -2 M = 11000000000]");
+    let x = FP16::smallest(false);
+    assert_eq!(dump(&|s| x.write_dump(s)), "FP[+ E= -14 M = 1]");
+    assert_eq!(dump(&|s| FP16::inf(true).write_dump(s)), "[-Inf]");
+    assert_eq!(dump(&|s| FP16::nan(false).write_dump(s)), "[+NaN]");
+    assert_eq!(dump(&|s| FP16::zero(false).write_dump(s)), "[+0.0]");
+
+    // Mantissas that span several parts are printed in full.
+    let x = FP128::from_u64(3);
+    let expected = String::from("FP[+ E=   1 M = 11") + &"0".repeat(111) + "]";
+    assert_eq!(dump(&|s| x.write_dump(s)), expected);
+    let m = BigInt::<2>::from_parts(&[5, 1]);
+    let expected = String::from("[|") + &"0".repeat(63) + "1|";
+    let expected = expected + &"0".repeat(61) + "101]";
+    assert_eq!(dump(&|s| m.write_dump(s)), expected);
+}
@@ -0,0 +1,324 @@
+//! The 8-bit floating point formats of the OCP 8-bit Floating Point
+//! Specification (OFP8). E5M2 follows the IEEE754 rules, and has infinities
+//! and NaNs. E4M3 has no infinities, and uses the largest exponent for normal
+//! numbers, except for a single NaN encoding (S.1111.111). This extends the
+//! range of E4M3 to 448, but it can't be described by the generic Float, so
+//! the formats are stored as their 8-bit encoding, and are converted to and
+//! from any Float.
+//!
+//! Conversions to FP8 can saturate: values that overflow are converted to the
+//! largest finite number, instead of to infinity (E5M2) or NaN (E4M3).
+//!```
+//!    use arpfloat::{E4M3, FP32, RoundingMode};
+//!    let x = FP32::from_f64(1000.);
+//!    let rm = RoundingMode::NearestTiesToEven;
+//!    assert!(E4M3::from_float(&x, rm, false).is_nan());
+//!    let y: FP32 = E4M3::from_float(&x, rm, true).to_float();
+//!    assert_eq!(y.as_f32(), 448.);
+//!```
+
+use super::float::{Float, RoundingMode};
+
+/// The IEEE754 format with the layout of E4M3. It represents all of the E4M3
+/// numbers that are below 256.
+type E4M3Ieee = Float<4, 3, 1>;
+/// A format with the precision of E4M3, and a wider exponent range, that
+/// represents all of the E4M3 numbers.
+type E4M3Wide = Float<5, 3, 1>;
+/// The IEEE754 format with the layout of E5M2.
+type E5M2Ieee = Float<5, 2, 1>;
+
+/// Returns true if overflow in the rounding mode `rm` produces the largest
+/// finite number, and not infinity.
+fn overflow_to_largest(sign: bool, rm: RoundingMode) -> bool {
+    match rm {
+        RoundingMode::NearestTiesToEven | RoundingMode::NearestTiesToAway => {
+            false
+        }
+        RoundingMode::Zero => true,
+        RoundingMode::Positive => sign,
+        RoundingMode::Negative => !sign,
+    }
+}
+
+/// An 8-bit float with 4 exponent bits and 3 mantissa bits, as defined by the
+/// OCP FP8 specification. The format has no infinities and one NaN encoding.
+#[derive(Debug, Clone, Copy)]
+pub struct E4M3(u8);
+
+impl E4M3 {
+    /// The largest finite number (448).
+    pub const MAX: Self = E4M3(0x7e);
+    /// The NaN encoding.
+    pub const NAN: Self = E4M3(0x7f);
+
+    /// Creates a number from its 8-bit encoding.
+    pub fn from_bits(bits: u8) -> Self {
+        E4M3(bits)
+    }
+
+    /// Returns the 8-bit encoding of the number.
+    pub fn to_bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns true if the number is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.0 & 0x7f == 0x7f
+    }
+
+    /// Converts `val` to E4M3, with the rounding mode `rm`. If `saturate` is
+    /// set then infinities and numbers that overflow are converted to the
+    /// largest finite number, and otherwise they are converted to NaN.
+    pub fn from_float<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+        saturate: bool,
+    ) -> Self {
+        let sign = val.get_sign();
+        let sign_bit = (sign as u8) << 7;
+        let overflow = || {
+            if saturate || (!val.is_inf() && overflow_to_largest(sign, rm)) {
+                E4M3(Self::MAX.0 | sign_bit)
+            } else {
+                E4M3(Self::NAN.0 | sign_bit)
+            }
+        };
+
+        if val.is_nan() {
+            return E4M3(Self::NAN.0 | sign_bit);
+        } else if val.is_inf() {
+            return overflow();
+        }
+
+        // Numbers below 128 can't round to the range that is outside of the
+        // IEEE format, and they use the IEEE encoding, with subnormals.
+        let abs = val.abs();
+        if abs < Float::from_u64(128) {
+            let x: E4M3Ieee = val.cast_with_rm(rm);
+            return E4M3(x.as_native_float() as u8);
+        }
+
+        // The numbers at and above 128 are normal.
+        let x: E4M3Wide = val.cast_with_rm(rm);
+        let mantissa = x.get_mantissa().as_u64() as u8 & 0x7;
+        match (x.is_inf(), x.get_exp()) {
+            (false, 7) => E4M3(sign_bit | 0x70 | mantissa),
+            (false, 8) if mantissa != 0x7 => E4M3(sign_bit | 0x78 | mantissa),
+            _ => overflow(),
+        }
+    }
+
+    /// Converts the number to a Float. The conversion is exact, unless the
+    /// target format is narrower than E4M3.
+    pub fn to_float<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        let sign = self.0 & 0x80 != 0;
+        if self.is_nan() {
+            return Float::nan(sign);
+        }
+
+        // The numbers with the largest exponent are outside of the range of
+        // the IEEE format.
+        if self.0 & 0x78 == 0x78 {
+            let mantissa = 0x8 | (self.0 & 0x7) as u64;
+            let rm = RoundingMode::NearestTiesToEven;
+            let mut x = E4M3Wide::from_u64(mantissa).scale(5, rm);
+            x.set_sign(sign);
+            return x.cast();
+        }
+        E4M3Ieee::from_native_float(self.0 as u64).cast()
+    }
+}
+
+/// An 8-bit float with 5 exponent bits and 2 mantissa bits, as defined by the
+/// OCP FP8 specification. The format follows the IEEE754 rules.
+#[derive(Debug, Clone, Copy)]
+pub struct E5M2(u8);
+
+impl E5M2 {
+    /// The largest finite number (57344).
+    pub const MAX: Self = E5M2(0x7b);
+    /// Positive infinity.
+    pub const INFINITY: Self = E5M2(0x7c);
+    /// The canonical quiet NaN encoding.
+    pub const NAN: Self = E5M2(0x7e);
+
+    /// Creates a number from its 8-bit encoding.
+    pub fn from_bits(bits: u8) -> Self {
+        E5M2(bits)
+    }
+
+    /// Returns the 8-bit encoding of the number.
+    pub fn to_bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns true if the number is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.0 & 0x7f > 0x7c
+    }
+
+    /// Returns true if the number is an infinity.
+    pub fn is_inf(&self) -> bool {
+        self.0 & 0x7f == 0x7c
+    }
+
+    /// Converts `val` to E5M2, with the rounding mode `rm`. If `saturate` is
+    /// set then finite numbers that overflow are converted to the largest
+    /// finite number, instead of to infinity. Infinities are preserved.
+    pub fn from_float<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+        saturate: bool,
+    ) -> Self {
+        let mut x: E5M2Ieee = val.cast_with_rm(rm);
+        if saturate && x.is_inf() && !val.is_inf() {
+            x = E5M2Ieee::largest(val.get_sign());
+        }
+        E5M2(x.as_native_float() as u8)
+    }
+
+    /// Converts the number to a Float. The conversion is exact, unless the
+    /// target format is narrower than E5M2.
+    pub fn to_float<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        E5M2Ieee::from_native_float(self.0 as u64).cast()
+    }
+}
+
+#[test]
+fn test_fp8_decode() {
+    use crate::FP32;
+
+    // Known values of the encodings.
+    let e4m3 = |bits: u8| E4M3::from_bits(bits).to_float::<8, 23, 1>();
+    let e5m2 = |bits: u8| E5M2::from_bits(bits).to_float::<8, 23, 1>();
+    assert_eq!(e4m3(0x7e).as_f32(), 448.);
+    assert_eq!(e4m3(0x78).as_f32(), 256.);
+    assert_eq!(e4m3(0x77).as_f32(), 240.);
+    assert_eq!(e4m3(0x38).as_f32(), 1.);
+    assert_eq!(e4m3(0x08).as_f32(), 1. / 64.);
+    assert_eq!(e4m3(0x01).as_f32(), 1. / 512.);
+    assert_eq!(e4m3(0xfe).as_f32(), -448.);
+    assert!(e4m3(0x80).is_zero() && e4m3(0x80).is_negative());
+    assert!(e4m3(0x7f).is_nan() && e4m3(0xff).is_nan());
+    assert_eq!(e5m2(0x7b).as_f32(), 57344.);
+    assert_eq!(e5m2(0x3c).as_f32(), 1.);
+    assert_eq!(e5m2(0x01).as_f32(), 1. / 65536.);
+    assert!(e5m2(0x7c).is_inf() && e5m2(0xfc).is_negative());
+    assert!(e5m2(0x7d).is_nan() && e5m2(0xff).is_nan());
+
+    // Every encoding converts back to itself.
+    let rm = RoundingMode::NearestTiesToEven;
+    for bits in 0..=255 {
+        let x: FP32 = E4M3::from_bits(bits).to_float();
+        let y = E4M3::from_float(&x, rm, false);
+        assert!(y.to_bits() == bits || (x.is_nan() && y.is_nan()));
+        let x: FP32 = E5M2::from_bits(bits).to_float();
+        let y = E5M2::from_float(&x, rm, false);
+        assert!(y.to_bits() == bits || (x.is_nan() && y.is_nan()));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_fp8_rounding() {
+    use super::utils::Lfsr;
+    use crate::FP32;
+    use std::vec::Vec;
+    use RoundingMode::*;
+
+    // Find the nearest encodings by searching all of the finite values.
+    let e4m3: Vec<(f32, u8)> = (0..=255)
+        .filter(|b| !E4M3::from_bits(*b).is_nan())
+        .map(|b| (E4M3::from_bits(b).to_float::<8, 23, 1>().as_f32(), b))
+        .collect();
+    let e5m2: Vec<(f32, u8)> = (0..=255)
+        .filter(|b| !E5M2::from_bits(*b).is_nan())
+        .filter(|b| !E5M2::from_bits(*b).is_inf())
+        .map(|b| (E5M2::from_bits(b).to_float::<8, 23, 1>().as_f32(), b))
+        .collect();
+    let round = |table: &Vec<(f32, u8)>, f: f32, rm: RoundingMode| {
+        let below = table.iter().filter(|v| v.0 <= f);
+        let above = table.iter().filter(|v| v.0 >= f);
+        let lo = below.max_by(|a, b| a.0.total_cmp(&b.0)).unwrap();
+        let hi = above.min_by(|a, b| a.0.total_cmp(&b.0)).unwrap();
+        match rm {
+            Negative => lo.1,
+            Positive => hi.1,
+            Zero => (if f > 0. { lo } else { hi }).1,
+            _ => {
+                let (d0, d1) = (f - lo.0, hi.0 - f);
+                let even = if lo.1 & 1 == 0 { lo.1 } else { hi.1 };
+                if d0 < d1 {
+                    lo.1
+                } else if d1 < d0 {
+                    hi.1
+                } else {
+                    even
+                }
+            }
+        }
+    };
+
+    let mut lfsr = Lfsr::new();
+    for i in 0..3000 {
+        // Values in the subnormal range, the normal range, and the top
+        // of the range of E4M3.
+        let scale = [1. / 512., 1. / 16., 1., 16., 110.][i % 5];
+        let sign = if i % 2 == 0 { 1. } else { -1. };
+        let f = (lfsr.get() % 4096) as f32 / 1024. * scale * sign;
+        if f == 0. {
+            continue;
+        }
+        let x = FP32::from_f32(f);
+        for rm in [NearestTiesToEven, Zero, Positive, Negative] {
+            let r = E4M3::from_float(&x, rm, false).to_bits();
+            assert_eq!(r, round(&e4m3, f, rm));
+            let r = E5M2::from_float(&x, rm, false).to_bits();
+            assert_eq!(r, round(&e5m2, f, rm));
+        }
+    }
+}
+
+#[test]
+fn test_fp8_overflow() {
+    use crate::FP64;
+    use RoundingMode::*;
+    let big = FP64::from_f64(500.);
+    let huge = FP64::from_f64(1e10);
+    let inf = FP64::inf(false);
+
+    // E4M3 overflows to NaN, or saturates.
+    for val in [big, huge, inf] {
+        assert!(E4M3::from_float(&val, NearestTiesToEven, false).is_nan());
+        let r = E4M3::from_float(&val.neg(), NearestTiesToEven, true);
+        assert_eq!(r.to_bits(), 0xfe);
+    }
+    // 464 is a tie between 448 and 480, and rounds to the even 448.
+    let tie = FP64::from_f64(464.);
+    let r = E4M3::from_float(&tie, NearestTiesToEven, false);
+    assert_eq!(r.to_bits(), 0x7e);
+    let r = E4M3::from_float(&FP64::from_f64(465.), NearestTiesToEven, false);
+    assert!(r.is_nan());
+    // Rounding toward zero produces the largest number.
+    assert_eq!(E4M3::from_float(&big, Zero, false).to_bits(), 0x7e);
+    assert!(E4M3::from_float(&big, Positive, false).is_nan());
+    assert_eq!(
+        E4M3::from_float(&big.neg(), Positive, false).to_bits(),
+        0xfe
+    );
+
+    // E5M2 overflows to infinity, or saturates finite values.
+    assert!(E5M2::from_float(&huge, NearestTiesToEven, false).is_inf());
+    let r = E5M2::from_float(&huge.neg(), NearestTiesToEven, true);
+    assert_eq!(r.to_bits(), 0xfb);
+    assert!(E5M2::from_float(&inf, NearestTiesToEven, true).is_inf());
+    assert_eq!(E5M2::from_float(&huge, Zero, false).to_bits(), 0x7b);
+    let nan = FP64::nan(true);
+    assert!(E5M2::from_float(&nan, NearestTiesToEven, true).is_nan());
+    assert!(E4M3::from_float(&nan, NearestTiesToEven, true).is_nan());
+}
@@ -1,6 +1,10 @@
+use crate::bigint::{BigInt, DynBigInt, LossFraction};
+use crate::cache::memoize;
 use crate::RoundingMode;
+use crate::FP256;
+use core::cmp::Ordering;
 
-use super::float::Float;
+use super::float::{ArithError, Float};
 
 impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     Float<EXPONENT, MANTISSA, PARTS>
@@ -9,9 +13,62 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     pub fn sqr(&self) -> Self {
         *self * *self
     }
-    /// Calculates the square root of the number using the Newton Raphson
-    /// method.
+
+    /// Raise the number to the integer power `n`, using exponentiation by
+    /// squaring. Negative powers are computed as the reciprocal of the
+    /// positive power.
+    pub fn powi(&self, n: i64) -> Self {
+        let exp = n.unsigned_abs();
+        if n >= 0 {
+            return Self::powi_by_squaring(*self, exp);
+        }
+
+        // The positive power may overflow while its reciprocal is still
+        // representable as a subnormal number. Fall back to raising 1/x in
+        // that case.
+        let positive = Self::powi_by_squaring(*self, exp);
+        if positive.is_inf() && !self.is_inf() {
+            return Self::powi_by_squaring(Self::one(false) / *self, exp);
+        }
+        Self::one(false) / positive
+    }
+
+    /// Computes base^exp by squaring the base for each bit of the exponent.
+    fn powi_by_squaring(mut base: Self, mut exp: u64) -> Self {
+        let mut v = Self::one(false);
+        loop {
+            if exp & 0x1 == 1 {
+                v = v * base;
+            }
+            exp >>= 1;
+            if exp == 0 {
+                return v;
+            }
+            base = base.sqr();
+        }
+    }
+
+    /// Calculates the square root of the number.
     pub fn sqrt(&self) -> Self {
+        self.sqrt_with_rm(RoundingMode::NearestTiesToEven)
+    }
+
+    /// Calculates the square root of the number, or returns an error if the
+    /// number is NaN or negative.
+    pub fn checked_sqrt(&self) -> Result<Self, ArithError> {
+        if self.is_nan() {
+            Err(ArithError::NanOperand)
+        } else if self.is_negative() && !self.is_zero() {
+            Err(ArithError::InvalidOperation)
+        } else {
+            Ok(self.sqrt())
+        }
+    }
+
+    /// Calculates the square root of the number, correctly rounded with the
+    /// rounding mode `rm`. The root is computed as the integer square root
+    /// of the mantissa, and the remainder decides the rounding.
+    pub fn sqrt_with_rm(&self, rm: RoundingMode) -> Self {
         if self.is_zero() {
             return *self; // (+/-) zero
         } else if self.is_nan() || self.is_negative() {
@@ -20,23 +77,172 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             return *self; // Inf+.
         }
 
-        let target = *self;
-        let two = Self::from_u64(2);
+        // The number is m * 2^k, where m has 'precision' bits. Shift the
+        // mantissa so that it has 2*precision-1 or 2*precision bits, and the
+        // exponent is even. The root then has exactly 'precision' bits. The
+        // mantissa storage has room for twice the precision bits.
+        let mut x = *self;
+        x.align_mantissa();
+        let precision = Self::get_precision() as i64;
+        let k = x.get_exp() - MANTISSA as i64;
+        let mut shift = precision - 1;
+        if (k - shift).rem_euclid(2) == 1 {
+            shift += 1;
+        }
+        let mut n = x.get_mantissa();
+        n.shift_left(shift as usize);
+        let root = n.isqrt();
+
+        // The fraction of the root is more than half if n > (root + 1/2)^2,
+        // which is n - root^2 > root, for integers. It's never exactly half.
+        let rem = n - root * root;
+        let loss = if rem.is_zero() {
+            LossFraction::ExactlyZero
+        } else if rem > root {
+            LossFraction::MoreThanHalf
+        } else {
+            LossFraction::LessThanHalf
+        };
 
-        // Start the search at max(2, x).
-        let mut x = if target < two { two } else { target };
+        let exp = (k - shift) / 2 + MANTISSA as i64;
+        let mut res = Self::new(false, exp, root);
+        res.normalize(rm, loss);
+        res
+    }
+
+    /// Calculates the cube root of the number using the Newton Raphson
+    /// method. Unlike sqrt, negative numbers have a real cube root.
+    pub fn cbrt(&self) -> Self {
+        if !self.is_normal() {
+            return *self; // (+/-) zero, inf, NaN.
+        }
+
+        // Write |x| = m * 2^(3j), with m in [1, 8), so the root is
+        // cbrt(m) * 2^j. Scaling by 2^(3j) is exact, even for subnormals,
+        // and keeps the squares in the iteration from underflowing.
+        let (k, m) = self.abs().split_exponent();
+        let j = k.div_euclid(3);
+        let target = m.scale(k - 3 * j, RoundingMode::Zero);
+        let three = Self::from_u64(3);
+
+        // Start the search at 2, which is larger than the root. The iteration
+        // then decreases monotonically towards the root.
+        let mut x = Self::from_u64(2);
         let mut prev = x;
 
         loop {
-            x = (x + (target / x)) / two;
+            x = (x + x + target / x.sqr()) / three;
             // Stop when value did not change or regressed.
             if prev < x || x == prev {
-                return x;
+                prev.set_sign(self.is_negative());
+                // The root of a finite number is never subnormal, so this
+                // is exact.
+                return prev.scale(j, RoundingMode::Zero);
             }
             prev = x;
         }
     }
 
+    /// Calculates sqrt(x^2 + y^2). The operands are scaled by a power of two
+    /// before squaring, so the computation does not overflow or underflow
+    /// unless the result does.
+    pub fn hypot(&self, y: Self) -> Self {
+        // hypot(+-inf, NaN) is inf, like in libm.
+        if self.is_inf() || y.is_inf() {
+            return Self::inf(false);
+        }
+        if self.is_nan() || y.is_nan() {
+            return Self::nan(false);
+        }
+        if self.is_zero() || y.is_zero() {
+            return if self.is_zero() { y.abs() } else { self.abs() };
+        }
+
+        let exp = self.get_exp().max(y.get_exp());
+        let a = self.scale(-exp, RoundingMode::NearestTiesToEven);
+        let b = y.scale(-exp, RoundingMode::NearestTiesToEven);
+        let r = (a.sqr() + b.sqr()).sqrt();
+        r.scale(exp, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Split the normal number into x = m * 2^k, where |m| is in the range
+    /// [1, 2). Returns the pair (k, m). This also works for subnormals.
+    fn split_exponent(&self) -> (i64, Self) {
+        debug_assert!(self.is_normal());
+        let msb = self.get_mantissa().msb_index() as i64;
+        let k = self.get_exp() - MANTISSA as i64 + msb - 1;
+        (k, self.scale(-k, RoundingMode::Zero))
+    }
+
+    /// Calculates the reciprocal 1/x using the Newton Raphson method, that
+    /// only uses multiplication. The result may be one ulp away from the
+    /// correctly rounded division.
+    pub fn recip(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        } else if self.is_zero() {
+            return Self::inf(self.get_sign());
+        } else if self.is_inf() {
+            return Self::zero(self.get_sign());
+        }
+
+        // Compute 1/m for x = m * 2^k, with m in the range [1, 2).
+        let (k, m) = self.abs().split_exponent();
+        let one = Self::one(false);
+
+        // Start with a linear approximation that is accurate to 4 bits:
+        // 1/m ~ 24/17 - 8/17 * m.
+        let c0 = Self::from_u64(24) / Self::from_u64(17);
+        let c1 = Self::from_u64(8) / Self::from_u64(17);
+        let mut y = c0 - c1 * m;
+
+        // Each iteration doubles the number of correct bits.
+        let mut bits = 4;
+        while bits < 2 * (MANTISSA + 1) {
+            y = y + y * (one - m * y);
+            bits *= 2;
+        }
+
+        y.set_sign(self.get_sign());
+        y.scale(-k, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Calculates the reciprocal square root 1/sqrt(x) using the Newton
+    /// Raphson method, that does not divide in the loop.
+    pub fn rsqrt(&self) -> Self {
+        if self.is_zero() {
+            return Self::inf(self.get_sign()); // (+/-) zero.
+        } else if self.is_nan() || self.is_negative() {
+            return Self::nan(self.get_sign()); // (-/+)Nan, -Number.
+        } else if self.is_inf() {
+            return Self::zero(false); // Inf+.
+        }
+
+        // Split x into m * 2^k, where k is even and m is in the range [1, 4).
+        let (mut k, mut m) = self.split_exponent();
+        if k % 2 != 0 {
+            m = m.scale(1, RoundingMode::Zero);
+            k -= 1;
+        }
+
+        // Start with a linear approximation that is accurate to 2 bits:
+        // 1/sqrt(m) ~ 6/5 - m/5.
+        let one = Self::one(false);
+        let five = Self::from_u64(5);
+        let mut y = (Self::from_u64(6) - m) / five;
+
+        // Each iteration of y = y + y * (1 - m * y^2) / 2 roughly doubles the
+        // number of correct bits.
+        let mut bits = 2;
+        while bits < 2 * (MANTISSA + 1) {
+            let r = one - m * y.sqr();
+            y = y + (y * r).scale(-1, RoundingMode::Zero);
+            bits *= 2;
+        }
+
+        y.scale(-k / 2, RoundingMode::NearestTiesToEven)
+    }
+
     /// Returns the absolute value of this float.
     pub fn abs(&self) -> Self {
         let mut x = *self;
@@ -44,6 +250,206 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         x
     }
 
+    /// Returns a number that represents the sign of self: 1.0 if the number is
+    /// positive (including +0 and +inf), -1.0 if the number is negative
+    /// (including -0 and -inf), and NaN if the number is NaN.
+    pub fn signum(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        Self::one(self.get_sign())
+    }
+
+    /// Returns a number with the magnitude of self and the sign of `sign`.
+    pub fn copysign(&self, sign: Self) -> Self {
+        let mut x = *self;
+        x.set_sign(sign.get_sign());
+        x
+    }
+
+    /// Restricts the number to the range [min, max]. Returns NaN if the number
+    /// is NaN.
+    ///
+    /// # Panics
+    /// Panics if min > max, min is NaN, or max is NaN (like f64::clamp).
+    pub fn clamp(&self, min: Self, max: Self) -> Self {
+        assert!(min <= max, "min > max, or either was NaN");
+        if *self < min {
+            min
+        } else if *self > max {
+            max
+        } else {
+            *self
+        }
+    }
+
+    /// Returns the positive difference: self - `other` if self > `other`, and
+    /// +0 otherwise. Returns NaN if either number is NaN.
+    pub fn fdim(&self, other: Self) -> Self {
+        if self.is_nan() || other.is_nan() {
+            return Self::nan(false);
+        }
+        if *self > other {
+            *self - other
+        } else {
+            Self::zero(false)
+        }
+    }
+
+    /// Returns the normal number that is one ulp away from self, with a larger
+    /// magnitude if `up` is set, and with a smaller magnitude otherwise.
+    fn next_magnitude(&self, up: bool) -> Self {
+        debug_assert!(self.is_normal());
+        let (exp_min, exp_max) = Self::get_exp_bounds();
+        let precision = Self::get_precision() as usize;
+        let mut m = self.get_mantissa();
+        let mut exp = self.get_exp();
+
+        if up {
+            let _ = m.inplace_add(&BigInt::one());
+            // Did the mantissa overflow to the next exponent?
+            if m.msb_index() > precision {
+                m.shift_right(1);
+                exp += 1;
+                if exp > exp_max {
+                    return Self::inf(self.get_sign());
+                }
+            }
+        } else {
+            let _ = m.inplace_sub(&BigInt::one());
+            // Did the mantissa drop to the previous exponent? Subnormal
+            // numbers stay at the lowest exponent.
+            if m.msb_index() < precision && exp > exp_min {
+                m.shift_left(1);
+                let _ = m.inplace_add(&BigInt::one());
+                exp -= 1;
+            }
+        }
+        Self::new(self.get_sign(), exp, m)
+    }
+
+    /// Returns the smallest representable number that is greater than self.
+    pub fn next_up(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_inf() {
+            if !self.is_negative() {
+                return *self;
+            }
+            return Self::largest(true);
+        }
+        if self.is_zero() {
+            return Self::smallest(false);
+        }
+        self.next_magnitude(!self.is_negative())
+    }
+
+    /// Returns the largest representable number that is less than self.
+    pub fn next_down(&self) -> Self {
+        self.neg().next_up().neg()
+    }
+
+    /// Returns the unit in the last place of the number: the distance from
+    /// the number to the next representable number of a larger magnitude.
+    /// The ulp of the largest finite number is the distance to the number
+    /// below it, and subnormal numbers share the ulp of the smallest
+    /// exponent. Returns +inf for inf, and NaN for NaN.
+    pub fn ulp(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_inf() {
+            return Self::inf(false);
+        }
+        let exp = if self.is_zero() {
+            Self::get_exp_bounds().0
+        } else {
+            self.get_exp()
+        };
+        let one = Self::one(false);
+        one.scale(exp - MANTISSA as i64, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Returns the next representable number after self, in the direction of
+    /// `toward`. Returns `toward` if the numbers are equal.
+    pub fn next_after(&self, toward: Self) -> Self {
+        if self.is_nan() || toward.is_nan() {
+            return Self::nan(false);
+        }
+        match self.partial_cmp(&toward) {
+            Some(Ordering::Less) => self.next_up(),
+            Some(Ordering::Greater) => self.next_down(),
+            _ => toward,
+        }
+    }
+
+    /// Returns the number that is halfway between self and `other`, without
+    /// overflowing for large numbers or losing the low bits of small numbers.
+    pub fn midpoint(&self, other: Self) -> Self {
+        let (exp_min, exp_max) = Self::get_exp_bounds();
+        let rm = RoundingMode::NearestTiesToEven;
+        // Numbers that are not tiny, and can be halved exactly.
+        let large =
+            |x: &Self| x.is_inf() || (x.is_normal() && x.get_exp() > exp_min);
+        // Numbers that can be doubled without overflowing.
+        let small =
+            |x: &Self| !x.is_inf() && (!x.is_normal() || x.get_exp() < exp_max);
+
+        if small(self) && small(&other) {
+            (*self + other).scale(-1, rm)
+        } else if !large(self) {
+            *self + other.scale(-1, rm)
+        } else if !large(&other) {
+            self.scale(-1, rm) + other
+        } else {
+            self.scale(-1, rm) + other.scale(-1, rm)
+        }
+    }
+
+    /// Computes a + t * (b - a), where t is a value in the range [0, 1], in
+    /// the current precision. The result is exact at t = 0 and t = 1, and is
+    /// monotonic in t. This is the algorithm of std::lerp in C++20.
+    fn lerp_monotonic(a: Self, b: Self, t: Self) -> Self {
+        let zero = Self::zero(false);
+        let one = Self::one(false);
+        // The end points have different signs.
+        if (a <= zero && b >= zero) || (a >= zero && b <= zero) {
+            return t * b + (one - t) * a;
+        }
+        if t == one {
+            return b;
+        }
+        // Clamp to b, to keep the results monotonic.
+        let x = a + t * (b - a);
+        if (t > one) == (b > a) {
+            if b < x {
+                x
+            } else {
+                b
+            }
+        } else if x < b {
+            x
+        } else {
+            b
+        }
+    }
+
+    /// Returns the linear interpolation a + t * (b - a). The result is exact
+    /// at t = 0 and t = 1, and is monotonic in t. The computation is done
+    /// with extra precision and the result is rounded once, so it is
+    /// correctly rounded, unless the exponents of the operands are very far
+    /// apart.
+    pub fn lerp(a: Self, b: Self, t: Self) -> Self {
+        if Self::has_wide_float() {
+            let a: WideFloat = a.cast();
+            let b: WideFloat = b.cast();
+            let t: WideFloat = t.cast();
+            return WideFloat::lerp_monotonic(a, b, t).cast();
+        }
+        Self::lerp_monotonic(a, b, t)
+    }
+
     /// Returns the greater of self and `other`.
     pub fn max(&self, other: Self) -> Self {
         if self.is_nan() {
@@ -120,6 +526,263 @@ fn test_sqrt() {
     check(5.0120298432056786e-8, 0.0002238756316173263);
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_sqrt_with_rm() {
+    use super::utils::{self, Lfsr};
+    use super::{FP128, FP16, FP32, FP64};
+    use RoundingMode::*;
+
+    // The native sqrt is correctly rounded. Find the rounding direction of
+    // the native result by squaring it exactly.
+    let check = |v: f64| {
+        let x = FP64::from_f64(v);
+        let r = v.sqrt();
+        let r_wide = FP128::from_f64(r);
+        let x_wide = FP128::from_f64(v);
+        let (down, up) = match (r_wide * r_wide).partial_cmp(&x_wide) {
+            Some(Ordering::Less) => (r, r.next_up()),
+            Some(Ordering::Greater) => (r.next_down(), r),
+            _ => (r, r),
+        };
+        assert_eq!(x.sqrt_with_rm(NearestTiesToEven).as_f64(), r);
+        assert_eq!(x.sqrt_with_rm(NearestTiesToAway).as_f64(), r);
+        assert_eq!(x.sqrt_with_rm(Zero).as_f64(), down);
+        assert_eq!(x.sqrt_with_rm(Negative).as_f64(), down);
+        assert_eq!(x.sqrt_with_rm(Positive).as_f64(), up);
+    };
+
+    let mut lfsr = Lfsr::new();
+    for _ in 0..2000 {
+        let v = f64::from_bits(lfsr.get64() >> 1);
+        if v.is_finite() {
+            check(v);
+        }
+    }
+    for v in utils::get_special_test_values() {
+        if v.is_finite() && v > 0. {
+            check(v);
+        }
+    }
+    check(f64::from_bits(1));
+    check(f64::from_bits(3));
+    check(f64::MAX);
+
+    // Compare the narrow types to the native float.
+    for i in 1..500 {
+        let v = i as f32 / 7.;
+        assert_eq!(FP32::from_f32(v).sqrt().as_f32(), v.sqrt());
+        let h = FP16::from_u64(i);
+        let r: FP16 = FP64::from_u64(i).sqrt().cast();
+        assert_eq!(h.sqrt(), r);
+    }
+}
+
+#[test]
+fn test_checked_sqrt() {
+    use super::FP64;
+    let four = FP64::from_u64(4);
+    assert_eq!(four.checked_sqrt().unwrap().as_f64(), 2.);
+    assert!(FP64::zero(true).checked_sqrt().unwrap().is_zero());
+    assert!(FP64::inf(false).checked_sqrt().unwrap().is_inf());
+    let invalid = Err(ArithError::InvalidOperation);
+    assert_eq!(FP64::from_i64(-4).checked_sqrt(), invalid);
+    assert_eq!(FP64::inf(true).checked_sqrt(), invalid);
+    let nan = FP64::nan(false);
+    assert_eq!(nan.checked_sqrt(), Err(ArithError::NanOperand));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_powi() {
+    use super::utils;
+    use super::{FP32, FP64};
+
+    // Integer powers that are exact.
+    for i in -10..10 {
+        assert_eq!(FP64::from_i64(3).powi(i).as_f64(), 3f64.powi(i as i32));
+        assert_eq!(FP64::from_i64(-2).powi(i).as_f64(), (-2f64).powi(i as i32));
+    }
+    assert_eq!(FP64::from_f64(1.5).powi(0).as_f64(), 1.);
+    assert_eq!(FP64::nan(false).powi(0).as_f64(), 1.);
+
+    for i in [-300, -77, -13, -1, 1, 7, 19, 64, 301] {
+        for v in [0.9, 1.1, 2.5, -7.25, 123.456] {
+            let r0 = FP64::from_f64(v).powi(i).as_f64();
+            assert!(utils::is_close(r0, v.powi(i as i32), 64));
+        }
+    }
+
+    // Check overflow, underflow and subnormal results.
+    assert!(FP32::from_u64(10).powi(40).is_inf());
+    assert!(FP32::from_i64(-10).powi(41).is_negative());
+    assert!(FP32::from_u64(10).powi(-50).is_zero());
+    // Native powi returns zero here, because 2^1074 overflows.
+    let r0 = FP64::from_u64(2).powi(-1074).as_f64();
+    assert_eq!(r0, f64::from_bits(1));
+    let r0 = FP64::from_u64(2).powi(-1060).as_f64();
+    assert_eq!(r0, f64::from_bits(1 << 14));
+    assert_eq!(FP64::one(true).powi(i64::MIN).as_f64(), 1.);
+    assert_eq!(FP64::one(true).powi(i64::MAX).as_f64(), -1.);
+
+    // Test the special values.
+    for v in utils::get_special_test_values() {
+        for i in [-3, -2, -1, 0, 1, 2, 3] {
+            let r0 = FP64::from_f64(v).powi(i).as_f64();
+            assert!(utils::is_close(r0, v.powi(i as i32), 4));
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_cbrt() {
+    use super::utils;
+    use super::{FP16, FP32, FP64};
+
+    // Perfect cubes, positive and negative.
+    for i in -100..100 {
+        let v = FP64::from_i64(i * i * i);
+        assert_eq!(v.cbrt().as_f64(), i as f64);
+    }
+
+    for v in [1e-300, 1e-20, 0.001, 0.5, 2., 10., 1234.5, 1e15, 1e300] {
+        let r0 = FP64::from_f64(v).cbrt().as_f64();
+        assert!(utils::is_close(r0, v.cbrt(), 1));
+        let r0 = FP64::from_f64(-v).cbrt().as_f64();
+        assert!(utils::is_close(r0, (-v).cbrt(), 1));
+    }
+
+    // Subnormal numbers.
+    let r0 = FP32::from_f32(1e-40).cbrt().as_f32();
+    assert!(utils::is_close(r0 as f64, 1e-40f32.cbrt() as f64, 1));
+    let r0 = FP64::from_f64(f64::from_bits(1)).cbrt().as_f64();
+    assert!(utils::is_close(r0, f64::from_bits(1).cbrt(), 1));
+    // The smallest FP16 subnormal is 2^-24, and the root is 2^-8.
+    let r0 = FP16::from_f64(2f64.powi(-24)).cbrt();
+    assert_eq!(r0.as_f64(), 0.00390625);
+    for i in 1..1024 {
+        let v = FP16::from_bits([i]);
+        let expected = FP16::from_f64(v.as_f64().cbrt());
+        let r0 = v.cbrt();
+        assert!((r0 - expected).abs() <= expected.ulp());
+    }
+
+    // Test the special values.
+    for v in utils::get_special_test_values() {
+        let r0 = FP64::from_f64(v).cbrt().as_f64();
+        assert!(utils::is_close(r0, v.cbrt(), 1));
+        assert!(r0.is_nan() || r0.is_sign_negative() == v.is_sign_negative());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_hypot() {
+    use super::utils;
+    use super::FP64;
+
+    assert_eq!(FP64::from_u64(3).hypot(FP64::from_i64(-4)).as_f64(), 5.);
+
+    let values = [1e-310, 1e-300, 0.001, 1.5, 3., 7.25, 1e10, 1e300, 1e308];
+    for x in values {
+        for y in values {
+            let r0 = FP64::from_f64(x).hypot(FP64::from_f64(-y)).as_f64();
+            assert!(utils::is_close(r0, x.hypot(y), 1));
+        }
+    }
+
+    // Check that values that overflow when squared don't overflow.
+    let r0 = FP64::from_f64(f64::MAX).hypot(FP64::from_f64(1.)).as_f64();
+    assert_eq!(r0, f64::MAX);
+    let r0 = FP64::from_f64(f64::MAX).hypot(FP64::from_f64(f64::MAX));
+    assert!(r0.is_inf());
+
+    // Test the special values.
+    for x in utils::get_special_test_values() {
+        for y in utils::get_special_test_values() {
+            let r0 = FP64::from_f64(x).hypot(FP64::from_f64(y)).as_f64();
+            let r1 = x.hypot(y);
+            assert!(utils::is_close(r0, r1, 1));
+            assert!(r0.is_nan() || !r0.is_sign_negative());
+        }
+    }
+    let r0 = FP64::nan(false).hypot(FP64::inf(true));
+    assert!(r0.is_inf() && !r0.is_negative());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_recip() {
+    use super::utils;
+    use super::utils::Lfsr;
+    use super::{FP128, FP16, FP64};
+
+    for i in -20..20 {
+        let v = FP64::from_u64(1).scale(i, RoundingMode::NearestTiesToEven);
+        assert_eq!(v.recip().as_f64(), 1. / v.as_f64());
+    }
+
+    // Compare to the division of random values.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..5000 {
+        let v = f64::from_bits(lfsr.get64());
+        let r0 = FP64::from_f64(v).recip().as_f64();
+        assert!(utils::is_close(r0, 1. / v, 1));
+    }
+
+    for v in [0.1, 3., 7.5, -1234.567, 1e-300] {
+        let x = FP128::from_f64(v);
+        let err = (x.recip() - FP128::one(false) / x).abs();
+        assert!(err <= x.recip().abs().scale(-112, RoundingMode::Zero));
+    }
+    assert_eq!(FP16::from_f32(3.).recip().as_f32(), 0.33325195);
+
+    // Test the special values.
+    for v in utils::get_special_test_values() {
+        let r0 = FP64::from_f64(v).recip().as_f64();
+        let r1 = 1. / v;
+        assert!(utils::is_close(r0, r1, 1));
+        assert!(r0.is_nan() || r0.is_sign_negative() == r1.is_sign_negative());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_rsqrt() {
+    use super::utils;
+    use super::utils::Lfsr;
+    use super::{FP128, FP64};
+
+    for i in -20..20 {
+        let v = FP64::from_u64(1).scale(2 * i, RoundingMode::NearestTiesToEven);
+        assert_eq!(v.rsqrt().as_f64(), 1. / v.as_f64().sqrt());
+    }
+
+    // Compare to the division by the square root of random values.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..5000 {
+        let v = f64::from_bits(lfsr.get64()).abs();
+        let r0 = FP64::from_f64(v).rsqrt().as_f64();
+        assert!(utils::is_close(r0, 1. / v.sqrt(), 2));
+    }
+
+    for v in [0.1, 3., 7.5, 1234.567, 1e-300] {
+        let x = FP128::from_f64(v);
+        let r1 = FP128::one(false) / x.sqrt();
+        let err = (x.rsqrt() - r1).abs();
+        assert!(err <= r1.scale(-110, RoundingMode::Zero));
+    }
+
+    // Test the special values.
+    for v in utils::get_special_test_values() {
+        let r0 = FP64::from_f64(v).rsqrt().as_f64();
+        let r1 = 1. / v.sqrt();
+        assert!(utils::is_close(r0, r1, 2));
+        assert!(r0.is_nan() || r0.is_sign_negative() == r1.is_sign_negative());
+    }
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn test_min_max() {
@@ -171,36 +834,364 @@ fn test_abs() {
     }
 }
 
-//  Compute basic constants.
-
-impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
-    Float<EXPONENT, MANTISSA, PARTS>
-{
-    /// Computes PI -- Algorithm description in Pg 246:
-    /// Fast Multiple-Precision Evaluation of Elementary Functions
-    /// by Richard P. Brent.
-    pub fn pi() -> Self {
-        let one = Self::from_i64(1);
-        let two = Self::from_i64(2);
-        let four = Self::from_i64(4);
-
-        let mut a = one;
-        let mut b = one / two.sqrt();
-        let mut t = one / four;
-        let mut x = one;
+#[cfg(feature = "std")]
+#[test]
+fn test_clamp_fdim() {
+    use super::utils;
+    use super::FP64;
+    let (lo, hi) = (-2.5, 10.);
+    let (flo, fhi) = (FP64::from_f64(lo), FP64::from_f64(hi));
+    for v in utils::get_special_test_values() {
+        let r0 = FP64::from_f64(v).clamp(flo, fhi).as_f64();
+        let r1 = v.clamp(lo, hi);
+        assert_eq!(r0.is_nan(), r1.is_nan());
+        if !r1.is_nan() {
+            assert_eq!(r0, r1);
+        }
 
-        while a != b {
-            let y = a;
-            a = (a + b) / two;
-            b = (b * y).sqrt();
-            t = t - x * ((a - y).sqr());
-            x = x * two;
+        for w in utils::get_special_test_values() {
+            let r0 = FP64::from_f64(v).fdim(FP64::from_f64(w)).as_f64();
+            let r1 = if v.is_nan() || w.is_nan() {
+                f64::NAN
+            } else if v > w {
+                v - w
+            } else {
+                0.
+            };
+            assert_eq!(r0.is_nan(), r1.is_nan());
+            if !r1.is_nan() {
+                assert_eq!(r0, r1);
+                assert!(!r0.is_sign_negative());
+            }
         }
-        a * a / t
     }
+}
 
-    /// Computes e using Euler's continued fraction, which is a simple series.
-    pub fn e() -> Self {
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn test_clamp_panics() {
+    use super::FP64;
+    let one = FP64::from_u64(1);
+    let _ = one.clamp(FP64::from_u64(2), FP64::from_u64(0));
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn test_clamp_nan_panics() {
+    use super::FP64;
+    let one = FP64::from_u64(1);
+    let _ = one.clamp(FP64::nan(false), FP64::from_u64(2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_next_up_down() {
+    use super::utils;
+    use super::utils::Lfsr;
+    use super::{FP16, FP64};
+
+    fn check(v: f64) {
+        let x = FP64::from_f64(v);
+        for (r0, r1) in [
+            (x.next_up().as_f64(), v.next_up()),
+            (x.next_down().as_f64(), v.next_down()),
+        ] {
+            assert_eq!(r0.is_nan(), r1.is_nan());
+            if !r1.is_nan() {
+                assert_eq!(r0, r1);
+                assert_eq!(r0.is_sign_negative(), r1.is_sign_negative());
+            }
+        }
+    }
+
+    // Check the transitions between exponents, subnormals, zero and inf.
+    let edges = [
+        1.,
+        -1.,
+        2.,
+        f64::MIN_POSITIVE,
+        -f64::MIN_POSITIVE,
+        f64::from_bits(1),
+        f64::from_bits(2),
+        -f64::from_bits(1),
+        f64::MAX,
+        f64::MIN,
+    ];
+    for v in edges.iter().chain(utils::get_special_test_values().iter()) {
+        check(*v);
+    }
+
+    let mut lfsr = Lfsr::new();
+    for _ in 0..5000 {
+        check(f64::from_bits(lfsr.get64()));
+    }
+
+    // Walk over all of the positive FP16 numbers.
+    let mut x = FP16::zero(false);
+    let mut count = 0;
+    while !x.is_inf() {
+        let y = x.next_up();
+        assert!(y > x);
+        assert!(y.next_down() == x);
+        x = y;
+        count += 1;
+    }
+    assert_eq!(count, 0x7c00);
+
+    // Test next_after.
+    let one = FP64::from_u64(1);
+    let two = FP64::from_u64(2);
+    assert_eq!(one.next_after(two).as_f64(), 1f64.next_up());
+    assert_eq!(one.next_after(two.neg()).as_f64(), 1f64.next_down());
+    assert_eq!(one.next_after(one).as_f64(), 1.);
+    let zero = FP64::zero(false);
+    assert!(zero.next_after(FP64::zero(true)).is_negative());
+    assert!(one.next_after(FP64::nan(false)).is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_ulp() {
+    use super::utils;
+    use super::utils::Lfsr;
+    use super::{FP128, FP16, FP64};
+
+    fn check(v: f64) {
+        let r0 = FP64::from_f64(v).ulp().as_f64();
+        let a = v.abs();
+        let r1 = if a.is_infinite() {
+            a
+        } else if a == f64::MAX {
+            a - a.next_down()
+        } else {
+            a.next_up() - a
+        };
+        assert_eq!(r0.is_nan(), r1.is_nan());
+        if !r1.is_nan() {
+            assert_eq!(r0, r1);
+        }
+    }
+
+    check(1.);
+    check(-3.);
+    check(f64::MAX);
+    check(f64::MIN_POSITIVE);
+    check(f64::from_bits(7));
+    for v in utils::get_special_test_values() {
+        check(v);
+    }
+    let mut lfsr = Lfsr::new();
+    for _ in 0..5000 {
+        check(f64::from_bits(lfsr.get64()));
+    }
+
+    // Check other formats.
+    assert_eq!(FP16::from_u64(1).ulp().as_f32(), 2f32.powi(-10));
+    let one = FP128::from_u64(1);
+    let ulp = one.scale(-112, RoundingMode::NearestTiesToEven);
+    assert!(one.ulp() == ulp);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_midpoint() {
+    use super::utils;
+    use super::FP64;
+
+    let values = [
+        f64::MAX,
+        f64::MIN,
+        f64::MIN_POSITIVE,
+        f64::from_bits(1),
+        f64::from_bits(3),
+        -f64::from_bits(5),
+        1.,
+        -7.5,
+    ];
+    let specials = utils::get_special_test_values();
+    for a in values.iter().chain(specials.iter()) {
+        for b in values.iter().chain(specials.iter()) {
+            let r0 = FP64::from_f64(*a).midpoint(FP64::from_f64(*b)).as_f64();
+            let r1 = a.midpoint(*b);
+            assert_eq!(r0.is_nan(), r1.is_nan());
+            if !r1.is_nan() {
+                assert_eq!(r0, r1);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lerp() {
+    use super::utils::Lfsr;
+    use super::{FP128, FP32};
+
+    let f = FP32::from_f32;
+    assert_eq!(FP32::lerp(f(1.), f(3.), f(0.25)).as_f32(), 1.5);
+    assert_eq!(FP32::lerp(f(-1.), f(3.), f(0.5)).as_f32(), 1.);
+    assert_eq!(FP32::lerp(f(2.), f(4.), f(2.)).as_f32(), 6.);
+
+    let mut lfsr = Lfsr::new();
+    for _ in 0..200 {
+        let a = (lfsr.get() % 2000) as f32 / 7. - 100.;
+        let b = (lfsr.get() % 2000) as f32 / 3. - 300.;
+        let (fa, fb) = (f(a), f(b));
+
+        // The end points are exact.
+        assert_eq!(FP32::lerp(fa, fb, f(0.)).as_f32(), a);
+        assert_eq!(FP32::lerp(fa, fb, f(1.)).as_f32(), b);
+
+        // Check the correct rounding, using the exact result at FP128.
+        let t = (lfsr.get() % 1000) as f32 / 999.;
+        let exact = FP128::lerp(fa.cast(), fb.cast(), f(t).cast());
+        let r0 = FP32::lerp(fa, fb, f(t));
+        assert_eq!(r0.as_f32(), exact.as_f32());
+
+        // Check that the results are monotonic, for neighboring values of t.
+        let mut t = f(t);
+        let mut prev = FP32::lerp(fa, fb, t);
+        for _ in 0..20 {
+            t = t.next_up();
+            let r = FP32::lerp(fa, fb, t);
+            assert!((b >= a && r >= prev) || (b <= a && r <= prev));
+            prev = r;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_signum_copysign() {
+    use super::utils;
+    use super::FP64;
+    for v in utils::get_special_test_values() {
+        let r0 = FP64::from_f64(v).signum().as_f64();
+        let r1 = v.signum();
+        assert_eq!(r0.is_nan(), r1.is_nan());
+        if !r1.is_nan() {
+            assert_eq!(r0, r1);
+        }
+
+        for s in utils::get_special_test_values() {
+            let r0 = FP64::from_f64(v).copysign(FP64::from_f64(s)).as_f64();
+            let r1 = v.copysign(s);
+            assert_eq!(r0.is_nan(), r1.is_nan());
+            assert_eq!(r0.is_sign_negative(), r1.is_sign_negative());
+            if !r1.is_nan() {
+                assert_eq!(r0, r1);
+            }
+        }
+    }
+}
+
+/// Intermediate results of functions that need extra precision are computed
+/// in this format, and are rounded once when converting to the target format.
+pub(crate) type WideFloat = FP256;
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Returns true if all of the values of this format can be converted
+    /// exactly to WideFloat, with at least 32 extra bits of precision.
+    pub(crate) fn has_wide_float() -> bool {
+        EXPONENT <= 19 && MANTISSA + 32 <= 236
+    }
+}
+
+//  Compute basic constants.
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Returns PI. The value is computed once for each type, and cached.
+    pub fn pi() -> Self {
+        memoize("pi", Self::pi_agm)
+    }
+
+    /// Computes PI -- Algorithm description in Pg 246:
+    /// Fast Multiple-Precision Evaluation of Elementary Functions
+    /// by Richard P. Brent.
+    fn pi_agm() -> Self {
+        let one = Self::from_i64(1);
+        let two = Self::from_i64(2);
+        let four = Self::from_i64(4);
+
+        let mut a = one;
+        let mut b = one / two.sqrt();
+        let mut t = one / four;
+        let mut x = one;
+
+        // Stop when the values are equal, or when rounding makes them stop
+        // changing, or swap around the mean.
+        while a != b {
+            let y = a;
+            let next_a = (a + b) / two;
+            let next_b = (b * y).sqrt();
+            let stuck = next_a == a && next_b == b;
+            if stuck || (next_a == b && next_b == a) {
+                break;
+            }
+            a = next_a;
+            b = next_b;
+            t = t - x * ((a - y).sqr());
+            x = x * two;
+        }
+        a * a / t
+    }
+
+    /// Computes the arithmetic-geometric mean of `a` and `b`, by replacing
+    /// the pair with their arithmetic mean (a + b) / 2 and geometric mean
+    /// sqrt(a * b) until the two values meet. The convergence is quadratic:
+    /// once the values are close, each iteration doubles the number of
+    /// correct bits, so about log2(MANTISSA) iterations are needed after the
+    /// first few. Returns NaN if either value is negative.
+    pub fn agm(a: Self, b: Self) -> Self {
+        if a.is_nan() || b.is_nan() || a.is_negative() || b.is_negative() {
+            return Self::nan(false);
+        }
+        if a.is_zero() || b.is_zero() {
+            return if a.is_inf() || b.is_inf() {
+                Self::nan(false)
+            } else {
+                Self::zero(false)
+            };
+        }
+        if a.is_inf() || b.is_inf() {
+            return Self::inf(false);
+        }
+
+        // The mean is homogeneous: agm(2^k a, 2^k b) = 2^k agm(a, b). Scale the
+        // values to avoid the overflow of a * b.
+        let exp = a.get_exp().max(b.get_exp());
+        let rm = RoundingMode::NearestTiesToEven;
+        let mut a = a.scale(-exp, rm);
+        let mut b = b.scale(-exp, rm);
+
+        // Stop when the values are equal, or when rounding makes them stop
+        // changing, or swap around the mean.
+        while a != b {
+            let next_a = (a + b).scale(-1, rm);
+            let next_b = (a * b).sqrt();
+            let stuck = next_a == a && next_b == b;
+            if stuck || (next_a == b && next_b == a) {
+                break;
+            }
+            a = next_a;
+            b = next_b;
+        }
+        a.scale(exp, rm)
+    }
+
+    /// Returns e. The value is computed once for each type, and cached.
+    pub fn e() -> Self {
+        memoize("e", Self::e_continued_fraction)
+    }
+
+    /// Computes e using Euler's continued fraction, which is a simple series.
+    fn e_continued_fraction() -> Self {
         let two = Self::from_i64(2);
         let one = Self::from_i64(1);
         let mut term = one;
@@ -212,6 +1203,297 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
 
         two + one / term
     }
+
+    /// Returns the Euler-Mascheroni constant (0.5772...). Not to be confused
+    /// with gamma(), which is the Gamma function.
+    pub fn euler_gamma() -> Self {
+        memoize("euler_gamma", || {
+            // The subtraction at the end cancels a few bits, so compute this
+            // with more precision, when it's available.
+            if Self::has_wide_float() {
+                return WideFloat::brent_mcmillan().cast();
+            }
+            Self::brent_mcmillan()
+        })
+    }
+
+    /// Computes the Euler-Mascheroni constant using the Brent-McMillan
+    /// formula: gamma = U / V - ln(n), where U = sum(H_k * (n^k / k!)^2),
+    /// V = sum((n^k / k!)^2) and H_k is the k-th harmonic number. The error
+    /// is about e^(-4n), and the terms are all positive.
+    fn brent_mcmillan() -> Self {
+        let n = (MANTISSA as u64 + 1) / 5 + 2;
+        let n_f = Self::from_u64(n);
+        let one = Self::one(false);
+        let mut term = one;
+        let mut harmonic = Self::zero(false);
+        let mut u = Self::zero(false);
+        let mut v = one;
+        let mut k = 1;
+        loop {
+            let k_f = Self::from_u64(k);
+            term = term * (n_f / k_f).sqr();
+            harmonic = harmonic + one / k_f;
+            let prev = (u, v);
+            u = u + term * harmonic;
+            v = v + term;
+            if (u, v) == prev {
+                return u / v - n_f.ln();
+            }
+            k += 1;
+        }
+    }
+
+    /// Sums the series t_0 + t_1 + t_2 ..., where t_0 is `first`, and the
+    /// ratio of consecutive terms is a rational function of k, like in
+    /// hypergeometric series: t_(k+1) = t_k * p(k) / q(k), where `ratio`
+    /// returns the pair (p(k), q(k)). The ratio needs to be smaller than one in
+    /// magnitude, and the series is summed until the terms stop changing the
+    /// sum.
+    pub(crate) fn sum_hypergeometric<F: Fn(u64) -> (i64, u64)>(
+        first: Self,
+        ratio: F,
+    ) -> Self {
+        let mut term = first;
+        let mut sum = first;
+        let mut k = 0;
+        loop {
+            let (p, q) = ratio(k);
+            term = term * Self::from_i64(p) / Self::from_u64(q);
+            let prev = sum;
+            sum = sum + term;
+            if sum == prev {
+                return sum;
+            }
+            k += 1;
+        }
+    }
+
+    /// Returns Catalan's constant (0.9159...).
+    pub fn catalan() -> Self {
+        memoize("catalan", || {
+            if Self::has_wide_float() {
+                return WideFloat::catalan_ramanujan().cast();
+            }
+            Self::catalan_ramanujan()
+        })
+    }
+
+    /// Computes Catalan's constant using Ramanujan's formula:
+    /// G = pi/8 * ln(2 + sqrt(3)) + 3/8 * sum(1 / ((2k+1)^2 * C(2k, k))).
+    /// The ratio of consecutive terms approaches 1/4, so every term adds
+    /// about 2 bits, and the 236-bit wide evaluation takes ~110 terms.
+    fn catalan_ramanujan() -> Self {
+        let one = Self::one(false);
+        let three = Self::from_u64(3);
+        let sum = Self::sum_hypergeometric(one, |k| {
+            (
+                ((2 * k + 1) * (k + 1)) as i64,
+                2 * (2 * k + 3) * (2 * k + 3),
+            )
+        });
+        let log = (Self::from_u64(2) + three.sqrt()).ln();
+        let rm = RoundingMode::Zero;
+        (Self::pi() * log + three * sum).scale(-3, rm)
+    }
+
+    /// Returns Apery's constant, zeta(3) (1.2020...).
+    pub fn zeta3() -> Self {
+        memoize("zeta3", || {
+            if Self::has_wide_float() {
+                return WideFloat::zeta3_series().cast();
+            }
+            Self::zeta3_series()
+        })
+    }
+
+    /// Computes zeta(3) using the series that Apery used in his proof of
+    /// irrationality: zeta(3) = 5/2 * sum((-1)^(k+1) / (k^3 * C(2k, k))),
+    /// for k >= 1. Every term adds about 2 bits.
+    fn zeta3_series() -> Self {
+        let sum = Self::sum_hypergeometric(Self::one(false), |i| {
+            let k = i + 1;
+            (-((k * k * k) as i64), 2 * (k + 1) * (k + 1) * (2 * k + 1))
+        });
+        sum * Self::from_u64(5) / Self::from_u64(4)
+    }
+
+    /// Computes atanh(1/q) = 1/q + 1/(3q^3) + 1/(5q^5) ..., as a fixed-point
+    /// number with `bits` fractional bits. Returns the value and a bound on
+    /// the error, in units of the last bit.
+    fn fixed_point_atanh_inv(q: u64, bits: usize) -> (DynBigInt, u64) {
+        let q2 = DynBigInt::from_u64(q * q);
+        let mut power = DynBigInt::zero();
+        power.flip_bit(bits);
+        power.inplace_div(&DynBigInt::from_u64(q));
+        let mut sum = power.clone();
+        let mut i = 1;
+        loop {
+            power.inplace_div(&q2);
+            if power.is_zero() {
+                // Every term is truncated, and the error of the powers stays
+                // below two units. The tail of the series is below one unit.
+                return (sum, 3 * i + 3);
+            }
+            let mut term = power.clone();
+            term.inplace_div(&DynBigInt::from_u64(2 * i + 1));
+            sum.inplace_add(&term);
+            i += 1;
+        }
+    }
+
+    /// Converts the fixed-point number `val`, with `bits` fractional bits, to
+    /// a float using the rounding mode `rm`. The value is known to be an
+    /// approximation of an irrational number, so the bits below the
+    /// fixed-point precision are treated as non-zero.
+    fn from_fixed_point(
+        mut val: DynBigInt,
+        bits: usize,
+        rm: RoundingMode,
+    ) -> Self {
+        // Keep a few bits more than the precision, and round with the bits
+        // that are shifted out, and the bits below the fixed-point number.
+        let shift = val.msb_index().saturating_sub(MANTISSA + 3);
+        let loss = match val.get_loss_kind_for_bit(shift) {
+            LossFraction::ExactlyZero => LossFraction::LessThanHalf,
+            LossFraction::ExactlyHalf => LossFraction::MoreThanHalf,
+            loss => loss,
+        };
+        val.shift_right(shift);
+        let exp = MANTISSA as i64 + shift as i64 - bits as i64;
+        let mut x = Self::new(false, exp, val.to_bigint());
+        x.normalize(rm, loss);
+        x
+    }
+
+    /// Returns ln(10). The value is computed once for each type, and cached.
+    fn ln10() -> Self {
+        memoize("ln10", || {
+            Self::ln10_with_rm(RoundingMode::NearestTiesToEven)
+        })
+    }
+
+    /// Rounds an irrational constant with the rounding mode `rm`. The
+    /// function `f` computes the constant as a fixed-point number with the
+    /// requested number of fractional bits, and returns it with a bound on
+    /// its error, in units of the last bit. Both ends of the error interval
+    /// need to round to the same value, or the result could be incorrectly
+    /// rounded, so the computation is repeated with more bits until they do.
+    fn round_fixed_point_interval<F: Fn(usize) -> (DynBigInt, u64)>(
+        f: F,
+        rm: RoundingMode,
+    ) -> Self {
+        let mut bits = MANTISSA + 16;
+        loop {
+            let (val, err) = f(bits);
+            let err = DynBigInt::from_u64(err);
+            let mut low = val.clone();
+            low.inplace_sub(&err);
+            let mut high = val;
+            high.inplace_add(&err);
+            let low = Self::from_fixed_point(low, bits, rm);
+            if low == Self::from_fixed_point(high, bits, rm) {
+                return low;
+            }
+            bits += 64;
+        }
+    }
+
+    /// Computes atan(1/q) = 1/q - 1/(3q^3) + 1/(5q^5) ..., as a fixed-point
+    /// number with `bits` fractional bits. Returns the value and a bound on
+    /// the error, in units of the last bit.
+    fn fixed_point_atan_inv(q: u64, bits: usize) -> (DynBigInt, u64) {
+        let q2 = DynBigInt::from_u64(q * q);
+        let mut power = DynBigInt::zero();
+        power.flip_bit(bits);
+        power.inplace_div(&DynBigInt::from_u64(q));
+        let mut pos = power.clone();
+        let mut neg = DynBigInt::zero();
+        let mut i = 1;
+        loop {
+            power.inplace_div(&q2);
+            if power.is_zero() {
+                // Every term is truncated, and the error of the powers stays
+                // below two units. The tail of the series is below one unit.
+                pos.inplace_sub(&neg);
+                return (pos, 3 * i + 3);
+            }
+            let mut term = power.clone();
+            term.inplace_div(&DynBigInt::from_u64(2 * i + 1));
+            if i % 2 == 1 {
+                neg.inplace_add(&term);
+            } else {
+                pos.inplace_add(&term);
+            }
+            i += 1;
+        }
+    }
+
+    /// Returns PI, correctly rounded with the rounding mode `rm`. The value is
+    /// computed with Machin's formula: pi = 16 * atan(1/5) - 4 * atan(1/239).
+    pub fn pi_with_rm(rm: RoundingMode) -> Self {
+        Self::round_fixed_point_interval(Self::fixed_point_pi, rm)
+    }
+
+    /// Computes PI as a fixed-point number with `bits` fractional bits.
+    /// Returns the value and a bound on the error, in units of the last bit.
+    fn fixed_point_pi(bits: usize) -> (DynBigInt, u64) {
+        let (mut a5, err5) = Self::fixed_point_atan_inv(5, bits);
+        let (mut a239, err239) = Self::fixed_point_atan_inv(239, bits);
+        a5.inplace_mul(&DynBigInt::from_u64(16));
+        a239.inplace_mul(&DynBigInt::from_u64(4));
+        a5.inplace_sub(&a239);
+        (a5, 16 * err5 + 4 * err239)
+    }
+
+    /// Returns ln(2), correctly rounded with the rounding mode `rm`. The value
+    /// is computed as ln(2) = 2 * atanh(1/3).
+    pub fn ln2_with_rm(rm: RoundingMode) -> Self {
+        let ln2 = |bits| {
+            let (mut a3, err3) = Self::fixed_point_atanh_inv(3, bits);
+            a3.inplace_mul(&DynBigInt::from_u64(2));
+            (a3, 2 * err3)
+        };
+        Self::round_fixed_point_interval(ln2, rm)
+    }
+
+    /// Returns ln(10), correctly rounded with the rounding mode `rm`. The
+    /// value is computed as ln(10) = 6 * atanh(1/3) + 2 * atanh(1/9), which is
+    /// 3 * ln(2) + ln(5/4).
+    pub fn ln10_with_rm(rm: RoundingMode) -> Self {
+        let ln10 = |bits| {
+            let (mut a3, err3) = Self::fixed_point_atanh_inv(3, bits);
+            let (mut a9, err9) = Self::fixed_point_atanh_inv(9, bits);
+            a3.inplace_mul(&DynBigInt::from_u64(6));
+            a9.inplace_mul(&DynBigInt::from_u64(2));
+            a3.inplace_add(&a9);
+            (a3, 6 * err3 + 2 * err9)
+        };
+        Self::round_fixed_point_interval(ln10, rm)
+    }
+
+    /// Returns e, correctly rounded with the rounding mode `rm`. The value is
+    /// computed with the series e = sum(1/k!).
+    pub fn e_with_rm(rm: RoundingMode) -> Self {
+        let e = |bits| {
+            let mut term = DynBigInt::zero();
+            term.flip_bit(bits);
+            let mut sum = term.clone();
+            let mut k = 1;
+            loop {
+                term.inplace_div(&DynBigInt::from_u64(k));
+                if term.is_zero() {
+                    // Every term is truncated, and the error of the terms
+                    // stays below two units. The tail of the series is below
+                    // four units.
+                    return (sum, 2 * k + 4);
+                }
+                sum.inplace_add(&term);
+                k += 1;
+            }
+        };
+        Self::round_fixed_point_interval(e, rm)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -221,6 +1503,42 @@ fn test_pi() {
     assert_eq!(FP128::pi().as_f64(), std::f64::consts::PI);
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_agm() {
+    use super::utils;
+    use super::{FP128, FP256, FP64};
+
+    // Values from mpmath.
+    let check = |a: f64, b: f64, res: f64| {
+        let r0 = FP64::agm(FP64::from_f64(a), FP64::from_f64(b)).as_f64();
+        assert!(utils::is_close(r0, res, 1));
+        let r0 = FP64::agm(FP64::from_f64(b), FP64::from_f64(a)).as_f64();
+        assert!(utils::is_close(r0, res, 1));
+    };
+    check(1., std::f64::consts::SQRT_2, 1.1981402347355923);
+    check(24., 6., 13.458171481725616);
+    check(1e300, 1e308, 7.930521033434531e+306);
+    check(1e-5, 1., 0.12177452186538905);
+    check(3., 3., 3.);
+
+    // Compare to the mean at a higher precision.
+    let r0 = FP128::agm(FP128::from_u64(24), FP128::from_u64(6));
+    let r1 = FP256::agm(FP256::from_u64(24), FP256::from_u64(6));
+    let err = (r0 - r1.cast()).abs();
+    assert!(err <= r0.ulp());
+
+    // Test the special values.
+    let zero = FP64::zero(false);
+    let one = FP64::one(false);
+    let inf = FP64::inf(false);
+    assert!(FP64::agm(zero, one).is_zero());
+    assert!(FP64::agm(inf, one).is_inf());
+    assert!(FP64::agm(inf, zero).is_nan());
+    assert!(FP64::agm(one.neg(), one).is_nan());
+    assert!(FP64::agm(FP64::nan(false), one).is_nan());
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn test_e() {
@@ -229,6 +1547,104 @@ fn test_e() {
     assert_eq!(FP32::e().as_f32(), std::f32::consts::E);
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_constants_with_rm() {
+    use super::{FP128, FP16, FP256, FP32, FP64};
+    use RoundingMode::*;
+
+    // The f64 constants are rounded down.
+    let pi = std::f64::consts::PI;
+    assert_eq!(FP64::pi_with_rm(NearestTiesToEven).as_f64(), pi);
+    assert_eq!(FP64::pi_with_rm(Zero).as_f64(), pi);
+    assert_eq!(FP64::pi_with_rm(Positive).as_f64(), pi.next_up());
+    let e = std::f64::consts::E;
+    assert_eq!(FP64::e_with_rm(NearestTiesToAway).as_f64(), e);
+    assert_eq!(FP64::e_with_rm(Negative).as_f64(), e);
+    assert_eq!(FP64::e_with_rm(Positive).as_f64(), e.next_up());
+    let ln2 = std::f64::consts::LN_2;
+    assert_eq!(FP64::ln2_with_rm(NearestTiesToEven).as_f64(), ln2);
+    let ln10 = std::f64::consts::LN_10;
+    assert_eq!(FP64::ln10_with_rm(NearestTiesToEven).as_f64(), ln10);
+
+    macro_rules! check_type {
+        ($t:ty) => {
+            let funcs = [
+                <$t>::pi_with_rm,
+                <$t>::e_with_rm,
+                <$t>::ln2_with_rm,
+                <$t>::ln10_with_rm,
+            ];
+            for f in funcs {
+                let down = f(Zero);
+                let up = f(Positive);
+                assert_eq!(down, f(Negative));
+                assert_eq!(up, down.next_up());
+                let nearest = f(NearestTiesToEven);
+                assert!(nearest == down || nearest == up);
+                assert_eq!(nearest, f(NearestTiesToAway));
+            }
+            let pi: $t = FP256::pi_with_rm(NearestTiesToEven).cast();
+            assert_eq!(<$t>::pi_with_rm(NearestTiesToEven), pi);
+            let e: $t = FP256::e_with_rm(NearestTiesToEven).cast();
+            assert_eq!(<$t>::e_with_rm(NearestTiesToEven), e);
+            let ln2: $t = FP256::ln2_with_rm(NearestTiesToEven).cast();
+            assert_eq!(<$t>::ln2_with_rm(NearestTiesToEven), ln2);
+        };
+    }
+    check_type!(FP16);
+    check_type!(FP32);
+    check_type!(FP64);
+    check_type!(FP128);
+
+    // Check the last bits of the wide constants, from mpmath.
+    let pi = FP256::pi_with_rm(NearestTiesToEven)
+        .get_mantissa()
+        .get_part(0);
+    assert_eq!(pi, 0xf98e804177d4c762);
+    let e = FP256::e_with_rm(NearestTiesToEven)
+        .get_mantissa()
+        .get_part(0);
+    assert_eq!(e, 0xa6d2b53c26c8228d);
+
+    // An error bound that is too wide to round the first approximation
+    // takes more bits, and not a different result.
+    let calls = core::cell::Cell::new(0);
+    let wide_pi = |bits| {
+        calls.set(calls.get() + 1);
+        let (val, err) = FP64::fixed_point_pi(bits);
+        (val, if bits < 200 { err << 60 } else { err })
+    };
+    for rm in [NearestTiesToEven, Zero, Positive] {
+        calls.set(0);
+        let pi = FP64::round_fixed_point_interval(wide_pi, rm);
+        assert_eq!(pi, FP64::pi_with_rm(rm));
+        assert!(calls.get() > 1);
+    }
+}
+
+#[test]
+fn test_euler_gamma() {
+    use super::{FP128, FP32, FP64};
+    // The constant from mpmath.
+    let expected = 0.5772156649015329;
+    assert_eq!(FP128::euler_gamma().as_f64(), expected);
+    assert_eq!(FP64::euler_gamma().as_f64(), expected);
+    assert_eq!(FP32::euler_gamma().as_f32(), expected as f32);
+}
+
+#[test]
+fn test_catalan_zeta3() {
+    use super::{FP128, FP32, FP64};
+    // Values from mpmath.
+    assert_eq!(FP64::catalan().as_f64(), 0.915965594177219);
+    assert_eq!(FP64::zeta3().as_f64(), 1.2020569031595942);
+    assert_eq!(FP128::catalan().as_f64(), 0.915965594177219);
+    assert_eq!(FP128::zeta3().as_f64(), 1.2020569031595942);
+    assert_eq!(FP32::catalan().as_f32(), 0.915965594177219_f64 as f32);
+    assert_eq!(FP32::zeta3().as_f32(), 1.2020569031595942_f64 as f32);
+}
+
 impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     Float<EXPONENT, MANTISSA, PARTS>
 {
@@ -287,6 +1703,75 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         lhs.set_sign(self.get_sign());
         lhs
     }
+
+    /// Calculates the quotient of Euclidean division, which is self / rhs
+    /// rounded to the integer q, such that self = rhs * q + r and
+    /// 0 <= r < |rhs|. This matches f64::div_euclid.
+    pub fn div_euclid(&self, rhs: Self) -> Self {
+        let q = (*self / rhs).trunc();
+        if self.rem(rhs) < Self::zero(false) {
+            return if rhs > Self::zero(false) {
+                q - Self::one(false)
+            } else {
+                q + Self::one(false)
+            };
+        }
+        q
+    }
+
+    /// Calculates the least non-negative remainder of self (mod rhs). This
+    /// matches f64::rem_euclid.
+    pub fn rem_euclid(&self, rhs: Self) -> Self {
+        let r = self.rem(rhs);
+        if r < Self::zero(false) {
+            r + rhs.abs()
+        } else {
+            r
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_euclid() {
+    use super::utils;
+    use super::utils::Lfsr;
+    use super::FP64;
+
+    fn check_two_numbers(v0: f64, v1: f64) {
+        let f0 = FP64::from_f64(v0);
+        let f1 = FP64::from_f64(v1);
+        for (r0, r1) in [
+            (f0.div_euclid(f1).as_f64(), v0.div_euclid(v1)),
+            (f0.rem_euclid(f1).as_f64(), v0.rem_euclid(v1)),
+        ] {
+            assert_eq!(r0.is_nan(), r1.is_nan());
+            if !r0.is_nan() {
+                assert_eq!(r0, r1);
+            }
+        }
+    }
+
+    check_two_numbers(7., 4.);
+    check_two_numbers(-7., 4.);
+    check_two_numbers(7., -4.);
+    check_two_numbers(-7., -4.);
+    check_two_numbers(-1e-30, 3.);
+    check_two_numbers(100., std::f64::consts::PI);
+    check_two_numbers(-100., -std::f64::consts::PI);
+
+    let mut lfsr = Lfsr::new();
+    for _ in 0..500 {
+        let v0 = (lfsr.get64() % 100000) as f64 / 7. - 5000.;
+        let v1 = (lfsr.get64() % 1000) as f64 / 3. - 150.;
+        check_two_numbers(v0, v1);
+    }
+
+    for v0 in utils::get_special_test_values() {
+        for v1 in utils::get_special_test_values() {
+            check_two_numbers(v0, v1);
+        }
+    }
 }
 
 #[test]
@@ -433,11 +1918,894 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             res
         }
     }
-}
-
-#[test]
-fn test_sin_taylor() {
-    use super::FP128;
+
+    /// Return the cosine function.
+    pub fn cos(&self) -> Self {
+        if self.is_nan() || self.is_inf() {
+            return Self::nan(self.get_sign());
+        }
+        if self.is_zero() {
+            return Self::one(false);
+        }
+
+        let mut neg = false;
+
+        // Step1 range reduction. Cosine is an even function, so drop the
+        // sign.
+        let mut val = self.abs();
+
+        let pi = Self::pi();
+        let pi2 = pi.scale(1, RoundingMode::Zero);
+        let pi_half = pi.scale(-1, RoundingMode::Zero);
+
+        // Step 1
+        if val > pi2 {
+            val = val.rem(pi2);
+        }
+
+        debug_assert!(val <= pi2);
+        // Step 2. cos(2pi - x) = cos(x).
+        if val > pi {
+            val = pi2 - val;
+        }
+
+        debug_assert!(val <= pi);
+        // Step 3. cos(pi - x) = -cos(x).
+        if val > pi_half {
+            val = pi - val;
+            neg ^= true;
+        }
+        debug_assert!(val <= pi_half);
+
+        // Step 4. cos(x) = sin(pi/2 - x).
+        let res = Self::sin_step4_reduction(pi_half - val, 5);
+        if neg {
+            res.neg()
+        } else {
+            res
+        }
+    }
+
+    /// Return the sine and the cosine of the number. This is faster than
+    /// calling sin() and cos(), because the range reduction is done once.
+    pub fn sin_cos(&self) -> (Self, Self) {
+        if self.is_nan() || self.is_inf() {
+            let nan = Self::nan(self.get_sign());
+            return (nan, nan);
+        }
+        if self.is_zero() {
+            return (*self, Self::one(false));
+        }
+
+        let mut sin_neg = false;
+        let mut cos_neg = false;
+
+        // Step1 range reduction. Sine is odd and cosine is even.
+        let mut val = self.abs();
+        if self.is_negative() {
+            sin_neg ^= true;
+        }
+
+        let pi = Self::pi();
+        let pi2 = pi.scale(1, RoundingMode::Zero);
+        let pi_half = pi.scale(-1, RoundingMode::Zero);
+
+        // Step 1
+        if val > pi2 {
+            val = val.rem(pi2);
+        }
+
+        debug_assert!(val <= pi2);
+        // Step 2. sin(x + pi) = -sin(x), cos(x + pi) = -cos(x).
+        if val > pi {
+            val = val - pi;
+            sin_neg ^= true;
+            cos_neg ^= true;
+        }
+
+        debug_assert!(val <= pi);
+        // Step 3. sin(pi - x) = sin(x), cos(pi - x) = -cos(x).
+        if val > pi_half {
+            val = pi - val;
+            cos_neg ^= true;
+        }
+        debug_assert!(val <= pi_half);
+
+        // Step 4. cos(x) = sin(pi/2 - x).
+        let sin = Self::sin_step4_reduction(val, 5);
+        let cos = Self::sin_step4_reduction(pi_half - val, 5);
+        (
+            if sin_neg { sin.neg() } else { sin },
+            if cos_neg { cos.neg() } else { cos },
+        )
+    }
+
+    /// Computes sin(x) for a positive finite x in degrees, or cos(x) if
+    /// `cosine` is set. The argument is reduced modulo 360 exactly, and then
+    /// to the range [-45, 45] around a multiple of 90 degrees, before it is
+    /// converted to radians.
+    fn sin_cos_degrees(x: Self, cosine: bool) -> Self {
+        let a = x.rem(Self::from_u64(360));
+        // Find the nearest quadrant q, such that a = 90 * q + d. The
+        // subtraction is exact because |d| <= |a|.
+        let mut q = 0;
+        while q < 4 && a > Self::from_u64(45 + 90 * q) {
+            q += 1;
+        }
+        let d = a - Self::from_u64(90 * q);
+        let rad = d * Self::pi() / Self::from_u64(180);
+        let (s, c) = rad.sin_cos();
+
+        // cos(x) = sin(x + 90).
+        match (q + cosine as u64) % 4 {
+            0 => s,
+            1 => c,
+            2 => s.neg(),
+            _ => c.neg(),
+        }
+    }
+
+    /// Return the sine of the number, which is in degrees. Multiples of 90
+    /// degrees return exact results, and large arguments don't lose
+    /// accuracy, because the reduction modulo 360 is exact.
+    pub fn sind(&self) -> Self {
+        if self.is_nan() || self.is_inf() {
+            return Self::nan(self.get_sign());
+        }
+        if self.is_zero() {
+            return *self;
+        }
+        let mut r = if Self::has_wide_float() {
+            let x: WideFloat = self.abs().cast();
+            WideFloat::sin_cos_degrees(x, false).cast()
+        } else {
+            Self::sin_cos_degrees(self.abs(), false)
+        };
+        // Sine is an odd function. This also gives sind(+-180n) = +-0.
+        r.set_sign(r.get_sign() ^ self.get_sign());
+        if r.is_zero() {
+            r.set_sign(self.get_sign());
+        }
+        r
+    }
+
+    /// Return the cosine of the number, which is in degrees. Multiples of 90
+    /// degrees return exact results, and large arguments don't lose
+    /// accuracy, because the reduction modulo 360 is exact.
+    pub fn cosd(&self) -> Self {
+        if self.is_nan() || self.is_inf() {
+            return Self::nan(self.get_sign());
+        }
+        let mut r = if Self::has_wide_float() {
+            let x: WideFloat = self.abs().cast();
+            WideFloat::sin_cos_degrees(x, true).cast()
+        } else {
+            Self::sin_cos_degrees(self.abs(), true)
+        };
+        // cosd(90 + 180n) is +0.
+        if r.is_zero() {
+            r.set_sign(false);
+        }
+        r
+    }
+
+    /// Converts the number from radians to degrees.
+    pub fn to_degrees(&self) -> Self {
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            let r = x * WideFloat::from_u64(180) / WideFloat::pi();
+            return r.cast();
+        }
+        *self * Self::from_u64(180) / Self::pi()
+    }
+
+    /// Converts the number from degrees to radians.
+    pub fn to_radians(&self) -> Self {
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            let r = x * WideFloat::pi() / WideFloat::from_u64(180);
+            return r.cast();
+        }
+        *self * Self::pi() / Self::from_u64(180)
+    }
+
+    /// Return the tangent function.
+    pub fn tan(&self) -> Self {
+        if self.is_nan() || self.is_inf() {
+            return Self::nan(self.get_sign());
+        }
+        if self.is_zero() {
+            return *self; // (+/-) zero
+        }
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return x.tan().cast();
+        }
+
+        let mut neg = false;
+
+        // Step1 range reduction. Tangent is an odd function.
+        let mut val = *self;
+        if val.is_negative() {
+            val = val.neg();
+            neg ^= true;
+        }
+
+        let pi = Self::pi();
+        let pi_half = pi.scale(-1, RoundingMode::Zero);
+
+        // Step 1. The period of tan is pi.
+        if val > pi {
+            val = val.rem(pi);
+        }
+
+        debug_assert!(val <= pi);
+        // Step 2. tan(pi - x) = -tan(x).
+        if val > pi_half {
+            val = pi - val;
+            neg ^= true;
+        }
+        debug_assert!(val <= pi_half);
+
+        // Step 3. tan(x) = sin(x) / cos(x), where cos(x) = sin(pi/2 - x).
+        // The subtraction is exact near the pole, so the only error there
+        // comes from the rounding of pi/2.
+        let cos_arg = pi_half - val;
+        let sin = Self::sin_step4_reduction(val, 5);
+        let cos = Self::sin_step4_reduction(cos_arg, 5);
+        let res = sin / cos;
+        if neg {
+            res.neg()
+        } else {
+            res
+        }
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// asin(x) = x + (1/2) x^3/3 + (1*3)/(2*4) x^5/5 + ...
+    /// The series is evaluated until the terms stop changing the sum.
+    fn asin_taylor(x: Self) -> Self {
+        let x2 = x.sqr();
+        let mut coef = Self::one(false);
+        let mut top = x;
+        let mut sum = x;
+        let mut i = 0;
+        loop {
+            // Prepare the next element.
+            coef = coef * Self::from_u64(2 * i + 1) / Self::from_u64(2 * i + 2);
+            top = top * x2;
+            let elem = coef * top / Self::from_u64(2 * i + 3);
+
+            // Update sum.
+            let prev = sum;
+            sum = sum + elem;
+            if sum == prev {
+                return sum;
+            }
+            i += 1;
+        }
+    }
+
+    /// Reduce asin(x) in the range 0..1, using the half-angle identity:
+    /// asin(x) = 2 * asin(x / sqrt(2 * (1 + sqrt(1 - x^2)))).
+    fn asin_reduction(x: Self) -> Self {
+        let limit = Self::one(false).scale(-3, RoundingMode::Zero);
+        if x < limit {
+            return Self::asin_taylor(x);
+        }
+        let one = Self::one(false);
+        let two = Self::from_u64(2);
+        // Compute 1 - x^2 as (1 - x) * (1 + x) to prevent cancellation.
+        let cos = ((one - x) * (one + x)).sqrt();
+        let half = x / (two * (one + cos)).sqrt();
+        Self::asin_reduction(half).scale(1, RoundingMode::Zero)
+    }
+
+    /// Return the arcsine function. Values outside of [-1, 1] return NaN.
+    pub fn asin(&self) -> Self {
+        if self.is_nan() || self.is_inf() {
+            return Self::nan(self.get_sign());
+        }
+        if self.is_zero() {
+            return *self; // (+/-) zero
+        }
+
+        let one = Self::one(false);
+        let val = self.abs();
+        if val > one {
+            return Self::nan(self.get_sign());
+        }
+
+        // Handle the negatives. asin is an odd function.
+        let res = Self::asin_reduction(val);
+        if self.is_negative() {
+            res.neg()
+        } else {
+            res
+        }
+    }
+
+    /// Return the arccosine function. Values outside of [-1, 1] return NaN.
+    pub fn acos(&self) -> Self {
+        if self.is_nan() || self.is_inf() {
+            return Self::nan(self.get_sign());
+        }
+
+        let one = Self::one(false);
+        if self.abs() > one {
+            return Self::nan(self.get_sign());
+        }
+
+        let half = one.scale(-1, RoundingMode::Zero);
+        let pi = Self::pi();
+
+        // Near +-1 the identity acos(x) = pi/2 - asin(x) cancels, so use
+        // acos(x) = 2 * asin(sqrt((1 - x) / 2)) instead. The subtraction
+        // 1 - x is exact in this range.
+        if *self > half {
+            let s = ((one - *self) * half).sqrt();
+            return Self::asin_reduction(s).scale(1, RoundingMode::Zero);
+        }
+        if *self < half.neg() {
+            let s = ((one + *self) * half).sqrt();
+            return pi - Self::asin_reduction(s).scale(1, RoundingMode::Zero);
+        }
+        pi.scale(-1, RoundingMode::Zero) - self.asin()
+    }
+
+    /// atan(x) = x - x^3/3 + x^5/5 - x^7/7 ...
+    /// The series is evaluated until the terms stop changing the sum.
+    fn atan_taylor(x: Self) -> Self {
+        let x2 = x.sqr();
+        let mut neg = true;
+        let mut top = x;
+        let mut sum = x;
+        let mut i = 1;
+        loop {
+            // Prepare the next element.
+            top = top * x2;
+            let elem = top / Self::from_u64(2 * i + 1);
+
+            // Update sum.
+            let prev = sum;
+            sum = if neg { sum - elem } else { sum + elem };
+            if sum == prev {
+                return sum;
+            }
+            neg ^= true;
+            i += 1;
+        }
+    }
+
+    /// Reduce atan(x) in the range 0..1, using the half-angle identity:
+    /// atan(x) = 2 * atan(x / (1 + sqrt(1 + x^2))).
+    fn atan_reduction(x: Self) -> Self {
+        let limit = Self::one(false).scale(-3, RoundingMode::Zero);
+        if x < limit {
+            return Self::atan_taylor(x);
+        }
+        let one = Self::one(false);
+        let half = x / (one + (one + x.sqr()).sqrt());
+        Self::atan_reduction(half).scale(1, RoundingMode::Zero)
+    }
+
+    /// Return the arctangent function.
+    pub fn atan(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_zero() {
+            return *self; // (+/-) zero
+        }
+
+        let pi_half = Self::pi().scale(-1, RoundingMode::Zero);
+        if self.is_inf() {
+            return if self.is_negative() {
+                pi_half.neg()
+            } else {
+                pi_half
+            };
+        }
+
+        let one = Self::one(false);
+        let val = self.abs();
+
+        // Large values converge slowly, so use the identity:
+        // atan(x) = pi/2 - atan(1/x).
+        let res = if val > one {
+            pi_half - Self::atan_reduction(one / val)
+        } else {
+            Self::atan_reduction(val)
+        };
+
+        // Handle the negatives. atan is an odd function.
+        if self.is_negative() {
+            res.neg()
+        } else {
+            res
+        }
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// atanh(x) = x + x^3/3 + x^5/5 + x^7/7 ...
+    /// The series is evaluated until the terms stop changing the sum.
+    fn atanh_taylor(x: Self) -> Self {
+        let x2 = x.sqr();
+        let mut top = x;
+        let mut sum = x;
+        let mut i = 1;
+        loop {
+            // Prepare the next element.
+            top = top * x2;
+            let elem = top / Self::from_u64(2 * i + 1);
+
+            // Update sum.
+            let prev = sum;
+            sum = sum + elem;
+            if sum == prev {
+                return sum;
+            }
+            i += 1;
+        }
+    }
+
+    /// Returns ln(2). The value is computed once for each type, and cached.
+    fn ln2() -> Self {
+        memoize("ln2", || Self::ln2_with_rm(RoundingMode::NearestTiesToEven))
+    }
+
+    /// Split the positive normal number into x = m * 2^k, where m is in the
+    /// range [sqrt(1/2), sqrt(2)). Returns the pair (k, m).
+    fn split_log_exponent(&self) -> (i64, Self) {
+        debug_assert!(self.is_normal() && !self.is_negative());
+
+        let (mut k, mut m) = self.split_exponent();
+        if m > Self::from_u64(2).sqrt() {
+            m = m.scale(-1, RoundingMode::Zero);
+            k += 1;
+        }
+        (k, m)
+    }
+
+    /// Computes ln(m) = 2 * atanh((m - 1) / (m + 1)), which converges
+    /// quickly for m in the range [sqrt(1/2), sqrt(2)).
+    fn ln_reduced(m: Self) -> Self {
+        let one = Self::one(false);
+        let t = (m - one) / (m + one);
+        Self::atanh_taylor(t).scale(1, RoundingMode::Zero)
+    }
+
+    /// Computes the natural logarithm of a positive normal number, using
+    /// ln(x) = k * ln(2) + ln(m), where x = m * 2^k.
+    fn ln_normal(&self) -> Self {
+        let (k, m) = self.split_log_exponent();
+        let res = Self::ln_reduced(m);
+        if k == 0 {
+            return res;
+        }
+        Self::from_i64(k) * Self::ln2() + res
+    }
+
+    /// Return the natural logarithm of the number. Negative numbers return
+    /// NaN, and zero returns negative infinity.
+    pub fn ln(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_zero() {
+            return Self::inf(true); // (+/-) zero.
+        }
+        if self.is_negative() {
+            return Self::nan(true); // -Inf, -Number.
+        }
+        if self.is_inf() {
+            return *self; // Inf+.
+        }
+        if *self == Self::one(false) {
+            return Self::zero(false);
+        }
+        self.ln_normal()
+    }
+
+    /// Return ln(1 + x), in a way that is accurate even if x is close to
+    /// zero.
+    pub fn ln_1p(&self) -> Self {
+        if self.is_nan() || self.is_zero() {
+            return *self;
+        }
+        let one = Self::one(false);
+        if *self < one.neg() {
+            return Self::nan(true); // -Inf, x < -1.
+        }
+        if self.is_inf() {
+            return *self; // Inf+.
+        }
+        if *self == one.neg() {
+            return Self::inf(true);
+        }
+
+        // Adding one to values that are close to zero loses their low bits, so
+        // use ln(1 + x) = 2 * atanh(x / (2 + x)).
+        let half = one.scale(-1, RoundingMode::Zero);
+        if self.abs() < half {
+            let t = *self / (Self::from_u64(2) + *self);
+            return Self::atanh_taylor(t).scale(1, RoundingMode::Zero);
+        }
+        (one + *self).ln_normal()
+    }
+
+    /// Return the logarithm of the number with respect to an arbitrary base.
+    /// The two logarithms are computed with extra precision, so the result is
+    /// only rounded once, when converting to the target format.
+    pub fn log(&self, base: Self) -> Self {
+        // Use the division of the logarithms to handle the special values.
+        let regular = |x: &Self| x.is_normal() && !x.is_negative();
+        if !regular(self) || !regular(&base) {
+            return self.ln() / base.ln();
+        }
+
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            let b: WideFloat = base.cast();
+            return (x.ln() / b.ln()).cast();
+        }
+        self.ln() / base.ln()
+    }
+
+    /// Return the base-2 logarithm of the number. The integral part of the
+    /// result is taken from the exponent, so powers of two are exact, and only
+    /// the logarithm of the significand is computed.
+    pub fn log2(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_zero() {
+            return Self::inf(true); // (+/-) zero.
+        }
+        if self.is_negative() {
+            return Self::nan(true); // -Inf, -Number.
+        }
+        if self.is_inf() {
+            return *self; // Inf+.
+        }
+
+        // log2(x) = k + ln(m) / ln(2), where x = m * 2^k.
+        let (k, m) = self.split_log_exponent();
+        let k = Self::from_i64(k);
+        if m == Self::one(false) {
+            return k;
+        }
+        k + Self::ln_reduced(m) / Self::ln2()
+    }
+
+    /// Return the base-10 logarithm of the number. Exact powers of ten
+    /// return exact integers.
+    pub fn log10(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_zero() {
+            return Self::inf(true); // (+/-) zero.
+        }
+        if self.is_negative() {
+            return Self::nan(true); // -Inf, -Number.
+        }
+        if self.is_inf() {
+            return *self; // Inf+.
+        }
+
+        let res = self.ln_normal() / Self::ln10();
+
+        // Check if the number is an exact power of ten, 10^n. These are
+        // representable while 5^n fits in the mantissa.
+        let n = res.round();
+        let max_n = (Self::get_precision() * 3 / 7) as i64;
+        let n_int = n.to_i64(RoundingMode::Zero);
+        if self.trunc() == *self && n_int > 0 && n_int <= max_n {
+            let ten = Self::from_u64(10);
+            let mut pow = ten;
+            for _ in 1..n_int {
+                pow = pow * ten;
+            }
+            if pow == *self {
+                return n;
+            }
+        }
+        res
+    }
+
+    /// Return the inverse hyperbolic tangent function. Values outside of
+    /// [-1, 1] return NaN.
+    pub fn atanh(&self) -> Self {
+        if self.is_nan() || self.is_inf() {
+            return Self::nan(self.get_sign());
+        }
+        if self.is_zero() {
+            return *self; // (+/-) zero
+        }
+
+        let one = Self::one(false);
+        let val = self.abs();
+        let res = match val.partial_cmp(&one) {
+            Some(Ordering::Greater) => return Self::nan(self.get_sign()),
+            Some(Ordering::Equal) => return Self::inf(self.get_sign()),
+            _ => {
+                // Small values converge quickly. For the rest, use:
+                // atanh(x) = ln((1 + x) / (1 - x)) / 2.
+                if val < one.scale(-3, RoundingMode::Zero) {
+                    Self::atanh_taylor(val)
+                } else {
+                    let r = (one + val) / (one - val);
+                    r.ln_normal().scale(-1, RoundingMode::Zero)
+                }
+            }
+        };
+
+        // Handle the negatives. atanh is an odd function.
+        if self.is_negative() {
+            res.neg()
+        } else {
+            res
+        }
+    }
+
+    /// Return the inverse hyperbolic sine function.
+    pub fn asinh(&self) -> Self {
+        if self.is_nan() || self.is_inf() || self.is_zero() {
+            return *self;
+        }
+
+        let one = Self::one(false);
+        let val = self.abs();
+
+        let res = if val < one.scale(-3, RoundingMode::Zero) {
+            // The logarithm below would cancel for small values, so use:
+            // asinh(x) = atanh(x / sqrt(1 + x^2)).
+            Self::atanh_taylor(val / (one + val.sqr()).sqrt())
+        } else if val.get_exp() > (MANTISSA / 2 + 1) as i64 {
+            // x^2 + 1 is rounded to x^2 (and may overflow), so use:
+            // asinh(x) = ln(2x) = ln(x) + ln(2).
+            val.ln_normal() + Self::ln2()
+        } else {
+            // asinh(x) = ln(x + sqrt(x^2 + 1)).
+            (val + (val.sqr() + one).sqrt()).ln_normal()
+        };
+
+        // Handle the negatives. asinh is an odd function.
+        if self.is_negative() {
+            res.neg()
+        } else {
+            res
+        }
+    }
+
+    /// Return the inverse hyperbolic cosine function. Values below one
+    /// return NaN.
+    pub fn acosh(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        let one = Self::one(false);
+        let two = Self::from_u64(2);
+        if *self < one {
+            return Self::nan(self.get_sign());
+        }
+        if self.is_inf() {
+            return *self;
+        }
+
+        let val = *self;
+        if val < two {
+            // Near one, use acosh(x) = 2 * atanh(sqrt((x - 1) / (x + 1))),
+            // because x - 1 is exact.
+            let t = ((val - one) / (val + one)).sqrt();
+            if t.is_zero() {
+                return Self::zero(false);
+            }
+            return t.atanh().scale(1, RoundingMode::Zero);
+        }
+        if val.get_exp() > (MANTISSA / 2 + 1) as i64 {
+            // x^2 - 1 is rounded to x^2 (and may overflow), so use:
+            // acosh(x) = ln(2x) = ln(x) + ln(2).
+            return val.ln_normal() + Self::ln2();
+        }
+        // acosh(x) = ln(x + sqrt((x - 1) * (x + 1))).
+        (val + ((val - one) * (val + one)).sqrt()).ln_normal()
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// expm1(x) = x + x^2/2! + x^3/3! + x^4/4! ...
+    /// The series is evaluated until the terms stop changing the sum.
+    fn expm1_taylor(x: Self) -> Self {
+        let mut top = x;
+        let mut sum = x;
+        let mut i = 2;
+        loop {
+            // Prepare the next element.
+            top = top * x / Self::from_u64(i);
+
+            // Update sum.
+            let prev = sum;
+            sum = sum + top;
+            if sum == prev {
+                return sum;
+            }
+            i += 1;
+        }
+    }
+
+    /// Reduce expm1(x) to small values, using the identity:
+    /// expm1(2x) = expm1(x) * (expm1(x) + 2).
+    /// Unlike squaring exp(x), this does not lose the low bits of the result.
+    fn expm1_reduction(x: Self) -> Self {
+        let limit = Self::one(false).scale(-4, RoundingMode::Zero);
+        if x.abs() < limit {
+            return Self::expm1_taylor(x);
+        }
+        let half = Self::expm1_reduction(x.scale(-1, RoundingMode::Zero));
+        half * (half + Self::from_u64(2))
+    }
+
+    /// Return the exponential function e^x.
+    pub fn exp(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_zero() {
+            return Self::one(false);
+        }
+        if self.is_inf() {
+            return if self.is_negative() {
+                Self::zero(false)
+            } else {
+                *self
+            };
+        }
+
+        // Handle values that obviously overflow or underflow. The result
+        // exponent is at most 2^EXPONENT, and ln(2) < 1.
+        if self.get_exp() >= EXPONENT as i64 {
+            return if self.is_negative() {
+                Self::zero(false)
+            } else {
+                Self::inf(false)
+            };
+        }
+
+        // The reduction below subtracts k * ln(2) from x, which cancels the
+        // leading bits of x. Do it in the wide format, so the rounding error
+        // of ln(2) does not show up in the result.
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return x.exp().cast();
+        }
+
+        // Split x into k * ln(2) + r, where |r| <= ln(2)/2, and compute
+        // e^x = 2^k * e^r.
+        let ln2 = Self::ln2();
+        let k = (*self / ln2).to_i64(RoundingMode::NearestTiesToEven);
+
+        // Handle the rest of the values that overflow or underflow, before k
+        // gets too large to compute r accurately.
+        let (exp_min, exp_max) = Self::get_exp_bounds();
+        if k > exp_max + 1 {
+            return Self::inf(false);
+        }
+        if k < exp_min - MANTISSA as i64 - 2 {
+            return Self::zero(false);
+        }
+
+        let r = *self - Self::from_i64(k) * ln2;
+        let er = Self::expm1_reduction(r) + Self::one(false);
+        er.scale(k, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Return e^x - 1, in a way that is accurate even if x is close to zero.
+    pub fn exp_m1(&self) -> Self {
+        if self.is_nan() || self.is_zero() {
+            return *self;
+        }
+        if self.is_inf() {
+            return if self.is_negative() {
+                Self::one(true)
+            } else {
+                *self
+            };
+        }
+
+        // Values close to zero would cancel when subtracting one from e^x.
+        if self.abs() < Self::one(false) {
+            return Self::expm1_reduction(*self);
+        }
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return (x.exp() - WideFloat::one(false)).cast();
+        }
+        self.exp() - Self::one(false)
+    }
+
+    /// Returns true if the number is an integer.
+    pub(crate) fn is_integer(&self) -> bool {
+        self.is_zero() || (self.is_normal() && self.trunc() == *self)
+    }
+
+    /// Returns true if the number is an odd integer.
+    pub(crate) fn is_odd_integer(&self) -> bool {
+        let half = self.scale(-1, RoundingMode::NearestTiesToEven);
+        self.is_normal() && self.is_integer() && !half.is_integer()
+    }
+
+    /// Raise the number to the power `y`. The special values follow the
+    /// IEEE 754 'pow' function, and the rest of the values are computed as
+    /// e^(y * ln(x)) with extra precision.
+    pub fn powf(&self, y: Self) -> Self {
+        let one = Self::one(false);
+        // pow(x, +-0) = 1 and pow(1, y) = 1, even for NaN.
+        if y.is_zero() || *self == one {
+            return one;
+        }
+        if self.is_nan() || y.is_nan() {
+            return Self::nan(false);
+        }
+
+        let odd = y.is_odd_integer();
+        if self.is_zero() {
+            // pow(+-0, y) keeps the sign of zero only for odd integers.
+            let sign = odd && self.is_negative();
+            return if y.is_negative() {
+                Self::inf(sign)
+            } else {
+                Self::zero(sign)
+            };
+        }
+        if y.is_inf() {
+            let cmp = self.abs().partial_cmp(&one);
+            return match (cmp, y.is_negative()) {
+                (Some(Ordering::Equal), _) => one, // pow(-1, +-inf) = 1.
+                (Some(Ordering::Less), false) => Self::zero(false),
+                (Some(Ordering::Less), true) => Self::inf(false),
+                (_, false) => Self::inf(false),
+                (_, true) => Self::zero(false),
+            };
+        }
+        if self.is_inf() {
+            let sign = odd && self.is_negative();
+            return if y.is_negative() {
+                Self::zero(sign)
+            } else {
+                Self::inf(sign)
+            };
+        }
+        // Negative bases only have real powers for integer exponents.
+        if self.is_negative() && !y.is_integer() {
+            return Self::nan(false);
+        }
+
+        let x = self.abs();
+        let mut r = if Self::has_wide_float() {
+            let x: WideFloat = x.cast();
+            let y: WideFloat = y.cast();
+            (y * x.ln()).exp().cast()
+        } else {
+            (y * x.ln()).exp()
+        };
+        r.set_sign(odd && self.is_negative());
+        r
+    }
+}
+
+#[test]
+fn test_sin_taylor() {
+    use super::FP128;
 
     for i in -10..100 {
         let f0 = i as f64;
@@ -446,3 +2814,581 @@ fn test_sin_taylor() {
         assert_eq!(r0, r1);
     }
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn test_cos() {
+    use super::utils;
+    use super::FP128;
+
+    for i in -10..100 {
+        let f0 = i as f64;
+        let r0 = f0.cos();
+        let r1 = FP128::from_f64(f0).cos().as_f64();
+        assert_eq!(r0, r1);
+    }
+
+    // Test the special values. Very large arguments are skipped because the
+    // range reduction uses a value of pi that is only as accurate as FP128.
+    for v in utils::get_special_test_values() {
+        let r0 = v.cos();
+        let r1 = FP128::from_f64(v).cos().as_f64();
+        assert_eq!(r0.is_nan(), r1.is_nan());
+        if !r0.is_nan() && v.abs() < 1e6 {
+            assert_eq!(r0, r1);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_sin_cos() {
+    use super::utils;
+    use super::{FP128, FP64};
+
+    for i in -100..100 {
+        let f0 = i as f64 / 7.;
+        let (s, c) = FP128::from_f64(f0).sin_cos();
+        assert_eq!(s.as_f64(), f0.sin());
+        assert_eq!(c.as_f64(), f0.cos());
+    }
+
+    // Check that the results match the separate functions.
+    for i in -100..100 {
+        let x = FP64::from_f64(i as f64 * 0.37);
+        let (s, c) = x.sin_cos();
+        assert!(utils::is_close(s.as_f64(), x.sin().as_f64(), 1));
+        assert!(utils::is_close(c.as_f64(), x.cos().as_f64(), 1));
+    }
+
+    // Test the special values. Very large arguments are skipped, like in the
+    // test of cos.
+    for v in utils::get_special_test_values() {
+        let (s, c) = FP128::from_f64(v).sin_cos();
+        let (s, c) = (s.as_f64(), c.as_f64());
+        assert_eq!(s.is_nan(), v.sin().is_nan());
+        assert_eq!(c.is_nan(), v.cos().is_nan());
+        if !s.is_nan() && v.abs() < 1e6 {
+            assert_eq!(s, v.sin());
+            assert_eq!(c, v.cos());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_degrees() {
+    use super::utils;
+    use super::FP64;
+
+    // Exact values.
+    let f = FP64::from_f64;
+    assert_eq!(f(30.).sind().as_f64(), 0.5);
+    assert_eq!(f(-30.).sind().as_f64(), -0.5);
+    assert_eq!(f(60.).cosd().as_f64(), 0.5);
+    assert_eq!(f(90.).sind().as_f64(), 1.);
+    assert_eq!(f(-270.).sind().as_f64(), 1.);
+    assert_eq!(f(3600.).cosd().as_f64(), 1.);
+    assert_eq!(f(180.).cosd().as_f64(), -1.);
+    let r0 = f(180.).sind();
+    assert!(r0.is_zero() && !r0.is_negative());
+    let r0 = f(-540.).sind();
+    assert!(r0.is_zero() && r0.is_negative());
+    let r0 = f(-90.).cosd();
+    assert!(r0.is_zero() && !r0.is_negative());
+
+    // Values from mpmath, including large arguments.
+    let check = |x: f64, s: f64, c: f64| {
+        assert!(utils::is_close(f(x).sind().as_f64(), s, 1));
+        assert!(utils::is_close(f(x).cosd().as_f64(), c, 1));
+    };
+    check(1e22, -0.984807753012208, 0.17364817766693036);
+    check(12345.678, 0.9627955785690516, -0.27023077894252734);
+    check(0.1, 0.001745328365898309, 0.9999984769132877);
+    check(44.99, 0.7069833570023731, 0.7072301838310177);
+
+    // The reference loses accuracy when converting to radians.
+    for i in -50..50 {
+        let v = i as f64 * 7.25;
+        let r0 = f(v).sind().as_f64();
+        let r1 = v.to_radians().sin();
+        assert!((r0 - r1).abs() < 1e-14);
+        let r0 = f(v).cosd().as_f64();
+        let r1 = v.to_radians().cos();
+        assert!((r0 - r1).abs() < 1e-14);
+    }
+
+    // Unit conversion.
+    assert_eq!(f(1.).to_degrees().as_f64(), 57.29577951308232);
+    assert_eq!(f(123.456).to_radians().as_f64(), 2.1547136813421197);
+    for v in utils::get_special_test_values() {
+        let r0 = f(v).to_degrees().as_f64();
+        assert!(utils::is_close(r0, v.to_degrees(), 1));
+        let r0 = f(v).to_radians().as_f64();
+        assert!(utils::is_close(r0, v.to_radians(), 1));
+        assert_eq!(f(v).sind().is_nan(), v.sin().is_nan());
+        assert_eq!(f(v).cosd().is_nan(), v.cos().is_nan());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_tan() {
+    use super::utils;
+    use super::{FP128, FP32, FP64};
+    use core::f64::consts::FRAC_PI_2;
+
+    for i in -10..100 {
+        let f0 = i as f64;
+        let r0 = f0.tan();
+        let r1 = FP128::from_f64(f0).tan().as_f64();
+        assert_eq!(r0, r1);
+
+        let r2 = FP64::from_f64(f0).tan().as_f64();
+        assert!(utils::is_close(r0, r2, 1));
+    }
+
+    // Check values that are close to the poles at odd multiples of pi/2.
+    let pi_half = FP128::pi().scale(-1, RoundingMode::NearestTiesToEven);
+    let near_pole = pi_half.as_f64();
+    assert_eq!(FP128::from_f64(near_pole).tan().as_f64(), near_pole.tan());
+    assert_eq!(FP128::from_f64(-near_pole).tan().as_f64(), -near_pole.tan());
+    let three = FP128::from_u64(3);
+    let far_pole = (pi_half * three).as_f64();
+    assert_eq!(FP128::from_f64(far_pole).tan().as_f64(), far_pole.tan());
+    assert!(pi_half.tan().abs() > FP128::from_u64(1 << 60));
+
+    // Check the poles and a large argument in the narrow formats, against
+    // the native tangent of the same input.
+    for v in [FRAC_PI_2, 3. * FRAC_PI_2, -3. * FRAC_PI_2, 100., 1e4] {
+        let r0 = FP64::from_f64(v).tan().as_f64();
+        assert!(utils::is_close(r0, v.tan(), 1));
+        let v = v as f32;
+        let r0 = FP32::from_f32(v).tan().as_f32();
+        assert!(utils::is_close(
+            r0 as f64,
+            (v as f64).tan() as f32 as f64,
+            1
+        ));
+    }
+    let r0 = FP32::from_f32(core::f32::consts::FRAC_PI_2).tan().as_f32();
+    assert_eq!(r0, -2.2877332e7);
+
+    // Test the category and sign of the special values.
+    for v in utils::get_special_test_values() {
+        let r0 = v.tan();
+        let r1 = FP128::from_f64(v).tan().as_f64();
+        assert_eq!(r0.is_nan(), r1.is_nan());
+        if !r0.is_nan() && v.abs() < 1e6 {
+            assert_eq!(r0, r1);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_asin_acos() {
+    use super::utils;
+    use super::FP128;
+
+    for i in -100..101 {
+        let f0 = i as f64 / 100.;
+        let asin = FP128::from_f64(f0).asin().as_f64();
+        let acos = FP128::from_f64(f0).acos().as_f64();
+        assert!(utils::is_close(asin, f0.asin(), 1));
+        assert!(utils::is_close(acos, f0.acos(), 1));
+    }
+    // The system libm is off by one ulp here.
+    assert_eq!(FP128::from_f64(0.07).acos().as_f64(), 1.5007390337068463);
+
+    // Test the category of the special values.
+    for v in utils::get_special_test_values() {
+        let r0 = v.asin();
+        let r1 = FP128::from_f64(v).asin().as_f64();
+        assert!(utils::is_close(r0, r1, 1));
+        let r0 = v.acos();
+        let r1 = FP128::from_f64(v).acos().as_f64();
+        assert!(utils::is_close(r0, r1, 1));
+    }
+
+    // Check the results at FP128 precision, using known values of pi.
+    fn check(a: FP128, b: FP128) {
+        let err = (a - b).abs();
+        assert!(err < b.abs().scale(-108, RoundingMode::Zero));
+    }
+    let one = FP128::one(false);
+    let half = one.scale(-1, RoundingMode::Zero);
+    let pi = FP128::pi();
+    check(one.asin(), pi.scale(-1, RoundingMode::Zero));
+    check(half.asin(), pi / FP128::from_u64(6));
+    check(half.acos(), pi / FP128::from_u64(3));
+    check(one.neg().acos(), pi);
+    check(
+        half.neg().acos(),
+        pi * FP128::from_u64(2) / FP128::from_u64(3),
+    );
+    assert!(one.acos().is_zero());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_atan() {
+    use super::utils;
+    use super::FP128;
+
+    for i in -100..101 {
+        let f0 = i as f64 / 10.;
+        let r0 = FP128::from_f64(f0).atan().as_f64();
+        assert!(utils::is_close(r0, f0.atan(), 1));
+    }
+
+    for v in utils::get_special_test_values() {
+        let r0 = v.atan();
+        let r1 = FP128::from_f64(v).atan().as_f64();
+        assert!(utils::is_close(r0, r1, 1));
+    }
+
+    // Check the results at FP128 precision, using known values of pi.
+    fn check(a: FP128, b: FP128) {
+        let err = (a - b).abs();
+        assert!(err < b.abs().scale(-108, RoundingMode::Zero));
+    }
+    let one = FP128::one(false);
+    let pi = FP128::pi();
+    let three = FP128::from_u64(3);
+    check(one.atan(), pi.scale(-2, RoundingMode::Zero));
+    check(three.sqrt().atan(), pi / three);
+    check(
+        (one / three.sqrt()).neg().atan(),
+        (pi / FP128::from_u64(6)).neg(),
+    );
+    // atan(x) = pi/2 - 1/x + 1/(3x^3) ..., for large x.
+    let large = FP128::from_u64(1 << 40);
+    check(large.atan(), pi.scale(-1, RoundingMode::Zero) - one / large);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_inverse_hyperbolic() {
+    use super::utils;
+    use super::FP128;
+
+    for i in -100..101 {
+        let f0 = i as f64 / 10.;
+        let r0 = FP128::from_f64(f0).asinh().as_f64();
+        assert!(utils::is_close(r0, f0.asinh(), 1));
+        let r0 = FP128::from_f64(f0).acosh().as_f64();
+        assert!(utils::is_close(r0, f0.acosh(), 1));
+        // The system libm's atanh is a few ulps off near one.
+        let f1 = i as f64 / 101.;
+        let r0 = FP128::from_f64(f1).atanh().as_f64();
+        assert!(utils::is_close(r0, f1.atanh(), 16));
+    }
+    let check_atanh = |x: f64, res: f64| {
+        assert_eq!(FP128::from_f64(x).atanh().as_f64(), res);
+    };
+    check_atanh(-0.9900990099009901, -2.6516524540295374);
+    check_atanh(-0.9702970297029703, -2.0973462680281907);
+    check_atanh(-0.9306930693069307, -1.6635447047542171);
+
+    for v in utils::get_special_test_values() {
+        // The std implementation overflows for f64::MAX.
+        let r0 = FP128::from_f64(v).asinh().as_f64();
+        assert!(utils::is_close(r0, v.asinh(), 1) || v.abs() == f64::MAX);
+        let r0 = FP128::from_f64(v).acosh().as_f64();
+        assert!(utils::is_close(r0, v.acosh(), 1) || v == f64::MAX);
+        let r0 = FP128::from_f64(v).atanh().as_f64();
+        assert!(utils::is_close(r0, v.atanh(), 16));
+    }
+
+    let r0 = FP128::from_f64(f64::MAX).asinh().as_f64();
+    assert_eq!(r0, 710.475860073944);
+    let r0 = FP128::from_f64(f64::MAX).acosh().as_f64();
+    assert_eq!(r0, 710.475860073944);
+
+    // Check the domain of the functions.
+    assert!(FP128::from_f64(0.5).acosh().is_nan());
+    assert!(FP128::from_f64(1.5).atanh().is_nan());
+    assert!(FP128::from_f64(-1.0).atanh().is_inf());
+    assert!(FP128::from_f64(-1.0).atanh().is_negative());
+    assert!(FP128::from_f64(1.0).acosh().is_zero());
+
+    // Check the results at FP128 precision, using the identities
+    // asinh(sinh(1)) = acosh(cosh(1)) = 1 and atanh(tanh(1)) = 1.
+    fn check(a: FP128, b: FP128) {
+        let err = (a - b).abs();
+        assert!(err < b.abs().scale(-108, RoundingMode::Zero));
+    }
+    let one = FP128::one(false);
+    let two = FP128::from_u64(2);
+    let e = FP128::e();
+    let sinh = (e - one / e) / two;
+    let cosh = (e + one / e) / two;
+    check(sinh.asinh(), one);
+    check(cosh.acosh(), one);
+    check((sinh / cosh).atanh(), one);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_exp() {
+    use super::utils;
+    use super::{FP128, FP64};
+
+    for i in -100..101 {
+        let f0 = i as f64 / 10.;
+        let r0 = FP128::from_f64(f0).exp().as_f64();
+        assert!(utils::is_close(r0, f0.exp(), 1));
+        let r0 = FP128::from_f64(f0).exp_m1().as_f64();
+        assert!(utils::is_close(r0, f0.exp_m1(), 1));
+    }
+
+    // Test values that are close to zero, and to the overflow point.
+    for v in [1e-300, -1e-20, 3.5e-10, 1e-5, 709.7, -708.3, -745.0] {
+        let r0 = FP128::from_f64(v).exp().as_f64();
+        assert!(utils::is_close(r0, v.exp(), 1));
+        let r0 = FP128::from_f64(v).exp_m1().as_f64();
+        assert!(utils::is_close(r0, v.exp_m1(), 1));
+    }
+
+    for v in utils::get_special_test_values() {
+        let r0 = FP128::from_f64(v).exp().as_f64();
+        assert!(utils::is_close(r0, v.exp(), 1));
+        let r0 = FP128::from_f64(v).exp_m1().as_f64();
+        assert!(utils::is_close(r0, v.exp_m1(), 1));
+    }
+
+    // The system libm is off by one ulp here.
+    let eps = FP128::from_f64(f64::EPSILON);
+    assert_eq!(eps.exp_m1().as_f64(), 2.2204460492503136e-16);
+
+    // Large arguments, where the range reduction cancels most of the bits,
+    // and results in the subnormal range.
+    for v in [500.7, 700.1, -700.2, 709.5, -709.9, -744.1, 43.21] {
+        let r0 = FP64::from_f64(v).exp().as_f64();
+        assert!(utils::is_close(r0, v.exp(), 1));
+        let r0 = FP64::from_f64(v).exp_m1().as_f64();
+        assert!(utils::is_close(r0, v.exp_m1(), 1));
+    }
+
+    // Overflow and underflow in the target format.
+    assert!(FP64::from_f64(710.).exp().is_inf());
+    assert!(FP64::from_f64(-746.).exp().is_zero());
+    assert!(FP64::from_f64(-746.).exp_m1() == FP64::one(true));
+
+    // Check the results at FP128 precision.
+    let err = (FP128::one(false).exp() - FP128::e()).abs();
+    assert!(err < FP128::e().scale(-108, RoundingMode::Zero));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_ln() {
+    use super::utils;
+    use super::{FP128, FP16, FP64};
+
+    for i in 1..200 {
+        let f0 = i as f64 / 7.;
+        let r0 = FP128::from_f64(f0).ln().as_f64();
+        assert!(utils::is_close(r0, f0.ln(), 1));
+    }
+
+    // Test large and small values, including denormals.
+    for v in [1e-300, 5e-324, 1e-310, 1e300, 0.999999999, 1.0000001] {
+        let r0 = FP128::from_f64(v).ln().as_f64();
+        assert!(utils::is_close(r0, v.ln(), 1));
+    }
+
+    for v in utils::get_special_test_values() {
+        let r0 = FP128::from_f64(v).ln().as_f64();
+        assert!(utils::is_close(r0, v.ln(), 1));
+        assert_eq!(r0.is_sign_negative(), v.ln().is_sign_negative());
+    }
+
+    // Check the results at FP128 precision.
+    let err = (FP128::e().ln() - FP128::one(false)).abs();
+    assert!(err < FP128::one(false).scale(-110, RoundingMode::Zero));
+
+    // Check the accuracy when computing in the native precision.
+    let r0 = FP64::from_f64(10.).ln().as_f64();
+    assert!(utils::is_close(r0, 10f64.ln(), 4));
+    let r0 = FP16::from_f64(10.).ln().as_f64();
+    assert!((r0 - 10f64.ln()).abs() < 1e-2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_log2() {
+    use super::utils;
+    use super::{FP128, FP32};
+
+    for i in 1..200 {
+        let f0 = i as f64 / 7.;
+        let r0 = FP128::from_f64(f0).log2().as_f64();
+        assert!(utils::is_close(r0, f0.log2(), 1));
+    }
+
+    for v in utils::get_special_test_values() {
+        let r0 = FP128::from_f64(v).log2().as_f64();
+        assert!(utils::is_close(r0, v.log2(), 1));
+        assert_eq!(r0.is_sign_negative(), v.log2().is_sign_negative());
+    }
+
+    // Powers of two are exact, including denormals.
+    for i in -149..128 {
+        let v = 2f64.powi(i);
+        assert_eq!(FP32::from_f64(v).log2().as_f32(), i as f32);
+    }
+    assert!(FP32::one(false).log2().is_zero());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_log10() {
+    use super::utils;
+    use super::{FP128, FP16, FP256, FP32, FP64};
+    use std::string::ToString;
+
+    // Check that the constant is correctly rounded.
+    assert_eq!(FP16::ln10().as_f32(), 2.3027344);
+    assert_eq!(FP32::ln10().as_f32(), std::f32::consts::LN_10);
+    assert_eq!(FP64::ln10().as_f64(), std::f64::consts::LN_10);
+    assert_eq!(FP128::ln10().as_f64(), std::f64::consts::LN_10);
+    let ln10 =
+        "2.302585092994045684017991454684364207601101488628772976033327900967";
+    assert!(ln10.starts_with(&FP256::ln10().to_string()[..60]));
+
+    for i in 1..200 {
+        let f0 = i as f64 / 7.;
+        let r0 = FP128::from_f64(f0).log10().as_f64();
+        assert!(utils::is_close(r0, f0.log10(), 1));
+    }
+
+    for v in utils::get_special_test_values() {
+        let r0 = FP128::from_f64(v).log10().as_f64();
+        assert!(utils::is_close(r0, v.log10(), 1));
+    }
+
+    // Powers of ten are exact.
+    for i in 1..23 {
+        let v = 10f64.powi(i);
+        assert_eq!(FP64::from_f64(v).log10().as_f64(), i as f64);
+    }
+    assert!(FP64::one(false).log10().is_zero());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_ln_1p() {
+    use super::utils;
+    use super::FP128;
+
+    for i in -99..200 {
+        let f0 = i as f64 / 100.;
+        let r0 = FP128::from_f64(f0).ln_1p().as_f64();
+        assert!(utils::is_close(r0, f0.ln_1p(), 1));
+    }
+
+    // Values close to zero.
+    for v in [1e-300, -1e-20, 3.5e-10, 1e-5, -1e-5, 5e-324] {
+        let r0 = FP128::from_f64(v).ln_1p().as_f64();
+        assert!(utils::is_close(r0, v.ln_1p(), 1));
+    }
+
+    for v in utils::get_special_test_values() {
+        let r0 = FP128::from_f64(v).ln_1p().as_f64();
+        assert!(utils::is_close(r0, v.ln_1p(), 1));
+        assert_eq!(r0.is_sign_negative(), v.ln_1p().is_sign_negative());
+    }
+
+    assert!(FP128::from_f64(-1.).ln_1p().is_inf());
+    assert!(FP128::from_f64(-1.5).ln_1p().is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_log_base() {
+    use super::utils;
+    use super::{FP128, FP32, FP64};
+
+    for i in 1..50 {
+        for base in [0.5, 2., 3., 7.5, 10., 1000.] {
+            let f0 = i as f64 / 7.;
+            let r0 = FP64::from_f64(f0).log(FP64::from_f64(base)).as_f64();
+            assert!(utils::is_close(r0, f0.log(base), 2));
+        }
+    }
+
+    // Check exact results, that a division at working precision gets wrong.
+    let ten = FP32::from_u64(10);
+    assert_eq!(FP32::from_u64(1000).log(ten).as_f32(), 3.);
+    let three = FP64::from_u64(3);
+    assert_eq!(FP64::from_u64(243).log(three).as_f64(), 5.);
+    assert_eq!(FP64::from_f64(0.125).log(FP64::from_u64(2)).as_f64(), -3.);
+
+    // Check a value from mpmath, where the correctly rounded result is not
+    // the same as the f64 division of the logarithms.
+    let r0 = FP64::from_f64(2.5).log(FP64::from_f64(3.0)).as_f64();
+    assert_eq!(r0, 0.8340437671464698);
+    let r0 = FP64::from_f64(17.0).log(FP64::from_f64(5.0)).as_f64();
+    assert_eq!(r0, 1.760374427722588);
+
+    // Test the special values.
+    for v in utils::get_special_test_values() {
+        for base in [0.0, 1.0, 2.0, f64::INFINITY, -2.0] {
+            let r0 = FP128::from_f64(v).log(FP128::from_f64(base)).as_f64();
+            assert!(utils::is_close(r0, v.log(base), 1));
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_powf() {
+    use super::utils;
+    use super::{FP128, FP32, FP64};
+
+    // Exact results.
+    let two = FP64::from_u64(2);
+    assert_eq!(two.powf(FP64::from_u64(10)).as_f64(), 1024.);
+    assert_eq!(two.powf(FP64::from_i64(-3)).as_f64(), 0.125);
+    let r0 = FP64::from_i64(-3).powf(FP64::from_u64(5)).as_f64();
+    assert_eq!(r0, -243.);
+    let r0 = FP32::from_u64(81).powf(FP32::from_f32(0.25)).as_f32();
+    assert_eq!(r0, 3.);
+
+    let values = [0.001, 0.3, 0.99, 1.5, 2., 7.25, 100.5, 1e10];
+    for x in values {
+        for y in [-20.5, -3., -0.5, 0.1, 0.5, 1.75, 3., 15.25] {
+            let r0 = FP64::from_f64(x).powf(FP64::from_f64(y)).as_f64();
+            assert!(utils::is_close(r0, x.powf(y), 1));
+        }
+    }
+
+    // Check overflow and underflow.
+    let ten = FP32::from_u64(10);
+    assert!(ten.powf(FP32::from_f32(38.6)).is_inf());
+    assert!(ten.powf(FP32::from_f32(-46.)).is_zero());
+
+    // Test the special values.
+    for x in utils::get_special_test_values() {
+        for y in utils::get_special_test_values() {
+            let r0 = FP128::from_f64(x).powf(FP128::from_f64(y)).as_f64();
+            let r1 = x.powf(y);
+            assert!(utils::is_close(r0, r1, 1), "pow({x}, {y})");
+            assert!(
+                r1.is_nan() || r0.is_sign_negative() == r1.is_sign_negative()
+            );
+        }
+    }
+    for y in [-1., 1., 2., -2., 0.5, f64::INFINITY, f64::NEG_INFINITY] {
+        for x in [-1., -0., 0., f64::NEG_INFINITY, 1., 0.5, -0.5] {
+            let r0 = FP64::from_f64(x).powf(FP64::from_f64(y)).as_f64();
+            let r1 = x.powf(y);
+            assert!(utils::is_close(r0, r1, 0), "pow({x}, {y})");
+            assert!(
+                r1.is_nan() || r0.is_sign_negative() == r1.is_sign_negative()
+            );
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! The IBM System/360 hexadecimal floating point formats (HFP). The numbers
+//! have a sign bit, a 7-bit exponent with a bias of 64, and a fraction of hex
+//! digits, and their value is 0.fraction * 16^(exponent - 64). The short,
+//! long and extended formats have 6, 14 and 28 hex digits. There are no
+//! infinities, NaNs or subnormals, and the fraction is not required to be
+//! normalized, so numbers can have several encodings.
+//!
+//! All of the HFP numbers are represented exactly by FP128, which makes it a
+//! good intermediate format for converting HFP data.
+//!```
+//!    use arpfloat::{HfpShort, FP128, RoundingMode};
+//!    let x: FP128 = HfpShort::from_bits(0xc276a000).to_float();
+//!    assert_eq!(x.as_f64(), -118.625);
+//!    let rm = RoundingMode::NearestTiesToEven;
+//!    assert_eq!(HfpShort::from_float(&x, rm).to_bits(), 0xc276a000);
+//!```
+
+use super::bigint::BigInt;
+use super::float::{self, Float, RoundingMode, FP128};
+
+/// Returns the value of the number with the sign `sign`, the biased exponent
+/// `exp` and the fraction `fraction` of `digits` hex digits. The conversion to
+/// FP128 is exact.
+fn decode<const E: usize, const M: usize, const P: usize>(
+    sign: bool,
+    exp: u32,
+    fraction: u128,
+    digits: i64,
+) -> Float<E, M, P> {
+    if fraction == 0 {
+        return Float::zero(sign);
+    }
+    let rm = RoundingMode::NearestTiesToEven;
+    let mut x =
+        FP128::from_u128(fraction).scale(4 * (exp as i64 - 64 - digits), rm);
+    x.set_sign(sign);
+    x.cast()
+}
+
+/// Converts `val` to a normalized fraction of `digits` hex digits, rounded
+/// with the rounding mode `rm`, and returns the sign, the biased exponent and
+/// the fraction. Numbers that overflow saturate to the largest number, and
+/// numbers below the smallest normalized number are flushed to zero. NaN is
+/// converted to zero.
+fn encode<const E: usize, const M: usize, const P: usize>(
+    val: &Float<E, M, P>,
+    rm: RoundingMode,
+    digits: i64,
+) -> (bool, u32, u128) {
+    let sign = val.get_sign();
+    let largest = (1 << (4 * digits)) - 1;
+    if val.is_nan() || val.is_zero() {
+        return (sign, 0, 0);
+    } else if val.is_inf() {
+        return (sign, 127, largest);
+    }
+
+    // Find the hex exponent q, such that 16^(q-1) <= |val| < 16^q, and scale
+    // the number to an integer fraction of 'digits' hex digits.
+    let mantissa = val.get_mantissa();
+    let exp = val.get_exp() - M as i64;
+    let log2 = exp + mantissa.msb_index() as i64 - 1;
+    let mut q = log2.div_euclid(4) + 1;
+    let shift = exp + 4 * (digits - q);
+    let mut fraction = if shift >= 0 {
+        mantissa.as_u128() << shift
+    } else {
+        let (m, loss) = float::shift_right_with_loss(mantissa, -shift as u64);
+        let mut fraction = m.as_u128();
+        // The mantissa of 't' holds the fraction, which decides the rounding
+        // of ties to even.
+        let t = FP128::new(sign, 112, BigInt::from_u128(fraction));
+        if !loss.is_exactly_zero() && t.need_round_away_from_zero(rm, loss) {
+            fraction += 1;
+        }
+        fraction
+    };
+
+    // Rounding up can carry into a new hex digit.
+    if fraction > largest {
+        fraction >>= 4;
+        q += 1;
+    }
+    if q + 64 > 127 {
+        return (sign, 127, largest);
+    } else if q + 64 < 0 {
+        return (sign, 0, 0);
+    }
+    (sign, (q + 64) as u32, fraction)
+}
+
+/// The 32-bit short HFP format, with 6 hex digits.
+#[derive(Debug, Clone, Copy)]
+pub struct HfpShort(u32);
+
+impl HfpShort {
+    /// The largest finite number (about 7.2e75).
+    pub const MAX: Self = HfpShort(0x7fffffff);
+
+    /// Creates a number from its 32-bit encoding.
+    pub fn from_bits(bits: u32) -> Self {
+        HfpShort(bits)
+    }
+
+    /// Returns the 32-bit encoding of the number.
+    pub fn to_bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Converts `val` to a normalized short HFP number, with the rounding
+    /// mode `rm`. Infinities and numbers that overflow are converted to the
+    /// largest number, numbers below the smallest normalized number are
+    /// flushed to zero, and NaN is converted to zero.
+    pub fn from_float<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self {
+        let (sign, exp, fraction) = encode(val, rm, 6);
+        HfpShort((sign as u32) << 31 | exp << 24 | fraction as u32)
+    }
+
+    /// Converts the number to a Float. The conversion is exact, unless the
+    /// target format is narrower than the short format.
+    pub fn to_float<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        let sign = self.0 >> 31 != 0;
+        let exp = (self.0 >> 24) & 0x7f;
+        decode(sign, exp, (self.0 & 0xffffff) as u128, 6)
+    }
+}
+
+/// The 64-bit long HFP format, with 14 hex digits.
+#[derive(Debug, Clone, Copy)]
+pub struct HfpLong(u64);
+
+impl HfpLong {
+    /// The largest finite number (about 7.2e75).
+    pub const MAX: Self = HfpLong(0x7fffffffffffffff);
+
+    /// Creates a number from its 64-bit encoding.
+    pub fn from_bits(bits: u64) -> Self {
+        HfpLong(bits)
+    }
+
+    /// Returns the 64-bit encoding of the number.
+    pub fn to_bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Converts `val` to a normalized long HFP number, with the rounding mode
+    /// `rm`. See [`HfpShort::from_float`].
+    pub fn from_float<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self {
+        let (sign, exp, fraction) = encode(val, rm, 14);
+        HfpLong((sign as u64) << 63 | (exp as u64) << 56 | fraction as u64)
+    }
+
+    /// Converts the number to a Float. The conversion is exact, unless the
+    /// target format is narrower than the long format.
+    pub fn to_float<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        let sign = self.0 >> 63 != 0;
+        let exp = (self.0 >> 56) as u32 & 0x7f;
+        let fraction = self.0 & ((1 << 56) - 1);
+        decode(sign, exp, fraction as u128, 14)
+    }
+}
+
+/// The 128-bit extended HFP format, with 28 hex digits. The number is stored
+/// as two long numbers, where the high 64 bits hold the sign, the exponent and
+/// the high part of the fraction. The low 64 bits hold the low part of the
+/// fraction, with the same sign, and an exponent that is 14 lower (modulo
+/// 128), which is ignored when the number is decoded.
+#[derive(Debug, Clone, Copy)]
+pub struct HfpExtended(u128);
+
+impl HfpExtended {
+    /// The largest finite number (about 7.2e75).
+    pub const MAX: Self = HfpExtended(0x7fffffffffffffff71ffffffffffffff);
+
+    /// Creates a number from its 128-bit encoding.
+    pub fn from_bits(bits: u128) -> Self {
+        HfpExtended(bits)
+    }
+
+    /// Returns the 128-bit encoding of the number.
+    pub fn to_bits(&self) -> u128 {
+        self.0
+    }
+
+    /// Converts `val` to a normalized extended HFP number, with the rounding
+    /// mode `rm`. See [`HfpShort::from_float`].
+    pub fn from_float<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self {
+        let (sign, exp, fraction) = encode(val, rm, 28);
+        let mask = (1 << 56) - 1;
+        let sign = (sign as u64) << 63;
+        let hi = sign | (exp as u64) << 56 | (fraction >> 56) as u64;
+        if fraction == 0 {
+            return HfpExtended((hi as u128) << 64 | (sign as u128));
+        }
+        let lo_exp = (exp + 128 - 14) as u64 & 0x7f;
+        let lo = sign | lo_exp << 56 | (fraction as u64 & mask);
+        HfpExtended((hi as u128) << 64 | lo as u128)
+    }
+
+    /// Converts the number to a Float. The conversion is exact, unless the
+    /// target format is narrower than the extended format.
+    pub fn to_float<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        let mask = (1 << 56) - 1;
+        let (hi, lo) = ((self.0 >> 64) as u64, self.0 as u64);
+        let sign = hi >> 63 != 0;
+        let exp = (hi >> 56) as u32 & 0x7f;
+        let fraction = ((hi & mask) as u128) << 56 | (lo & mask) as u128;
+        decode(sign, exp, fraction, 28)
+    }
+}
+
+#[test]
+fn test_hfp_decode() {
+    use crate::FP64;
+
+    // Known values of the encodings.
+    let short = |bits: u32| HfpShort::from_bits(bits).to_float::<11, 52, 2>();
+    let long = |bits: u64| HfpLong::from_bits(bits).to_float::<11, 52, 2>();
+    assert_eq!(short(0x41100000).as_f64(), 1.);
+    assert_eq!(short(0xc276a000).as_f64(), -118.625);
+    assert_eq!(short(0x42010000).as_f64(), 1.);
+    assert_eq!(short(0x00100000).as_f64(), 16f64.powi(-65));
+    let max = 16f64.powi(63) * (1. - 2f64.powi(-24));
+    assert_eq!(short(0x7fffffff).as_f64(), max);
+    assert!(short(0x80000000).is_zero() && short(0x80000000).is_negative());
+    assert!(short(0x41000000).is_zero());
+    assert_eq!(long(0x4110000000000000).as_f64(), 1.);
+    assert_eq!(long(0x401999999999999a).as_f64(), 0.1);
+
+    // The extended format ignores the exponent of the low part.
+    let rm = RoundingMode::Zero;
+    let one = FP128::from_u64(1);
+    let bits = 0x4110000000000000_3300000000000001;
+    let x: FP128 = HfpExtended::from_bits(bits).to_float();
+    assert_eq!(x, one + one.scale(-108, rm));
+    let x: FP128 = HfpExtended::from_bits(bits & !(0x7f << 56)).to_float();
+    assert_eq!(x, one + one.scale(-108, rm));
+    let x: FP128 = HfpExtended::MAX.to_float();
+    assert_eq!(x, one.scale(252, rm) - one.scale(140, rm));
+
+    // Encoding rounds once, and normalizes the fraction.
+    let rm = RoundingMode::NearestTiesToEven;
+    let x = FP64::from_f64(0.1);
+    assert_eq!(HfpShort::from_float(&x, rm).to_bits(), 0x4019999a);
+    let rz = RoundingMode::Zero;
+    assert_eq!(HfpShort::from_float(&x, rz).to_bits(), 0x40199999);
+    assert_eq!(HfpLong::from_float(&x, rm).to_bits(), 0x401999999999999a);
+    let x: FP64 = HfpShort::from_bits(0x42010000).to_float();
+    assert_eq!(HfpShort::from_float(&x, rm).to_bits(), 0x41100000);
+    let x = FP64::from_f64(1. - 2f64.powi(-30));
+    assert_eq!(HfpShort::from_float(&x, rm).to_bits(), 0x41100000);
+    let x = HfpExtended::from_float(&FP64::from_f64(-1.), rm);
+    assert_eq!(x.to_bits(), 0xc110000000000000_b300000000000000);
+}
+
+#[test]
+fn test_hfp_range() {
+    use crate::FP64;
+    let rm = RoundingMode::NearestTiesToEven;
+    let enc = |x: f64| HfpShort::from_float(&FP64::from_f64(x), rm).to_bits();
+
+    // Overflow saturates, and underflow is flushed to zero.
+    assert_eq!(enc(1e76), 0x7fffffff);
+    assert_eq!(enc(f64::NEG_INFINITY), 0xffffffff);
+    assert_eq!(enc(1e-80), 0);
+    assert_eq!(enc(-1e-80), 0x80000000);
+    assert_eq!(enc(f64::NAN), 0);
+    assert_eq!(enc(16f64.powi(-65)), 0x00100000);
+    let x = FP64::from_f64(f64::MAX);
+    assert_eq!(
+        HfpExtended::from_float(&x, rm).to_bits(),
+        HfpExtended::MAX.0
+    );
+}
+
+#[test]
+fn test_hfp_round_trip() {
+    use super::utils::Lfsr;
+    use crate::FP64;
+    use RoundingMode::*;
+
+    let mut lfsr = Lfsr::new();
+    for _ in 0..2000 {
+        // Normalized encodings convert back to themselves.
+        let bits = lfsr.get64() | 1 << 52;
+        let x: FP64 = HfpShort::from_bits(bits as u32 | 1 << 20).to_float();
+        let y = HfpShort::from_float(&x, NearestTiesToEven);
+        assert_eq!(y.to_bits(), bits as u32 | 1 << 20);
+        let x: FP128 = HfpLong::from_bits(bits).to_float();
+        assert_eq!(HfpLong::from_float(&x, Zero).to_bits(), bits);
+        let bits = (bits as u128) << 64 | (lfsr.get64() as u128);
+        let bits = bits & !(0xff << 56);
+        let x: FP128 = HfpExtended::from_bits(bits).to_float();
+        let y = HfpExtended::from_float(&x, NearestTiesToEven).to_bits();
+        assert_eq!(y & !(0xff << 56), bits);
+
+        // Doubles in the range of HFP are represented exactly by the long
+        // format, and the short format brackets them.
+        let f = f64::from_bits(lfsr.get64());
+        if !(1e-78..1e75).contains(&f.abs()) {
+            continue;
+        }
+        let x = FP64::from_f64(f);
+        let y: FP64 = HfpLong::from_float(&x, NearestTiesToEven).to_float();
+        assert_eq!(y.as_f64(), f);
+        let lo: FP64 = HfpShort::from_float(&x, Negative).to_float();
+        let hi: FP64 = HfpShort::from_float(&x, Positive).to_float();
+        assert!(lo.as_f64() <= f && f <= hi.as_f64());
+        assert!((hi.as_f64() - lo.as_f64()).abs() <= f.abs() * 2f64.powi(-20));
+    }
+}
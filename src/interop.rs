@@ -0,0 +1,1401 @@
+//! Conversions between Float and the numeric types of other crates, that are
+//! enabled by features.
+//!
+//! The `half` feature converts between `half::f16` and FP16, and between
+//! `half::bf16` and BF16. The conversions are exact, and preserve the NaN
+//! payloads.
+//!
+//! The `nightly` feature converts to and from the `f16` and `f128` primitive
+//! types, which are only available in the nightly compiler.
+//!
+//! The `num` feature converts floats to `num_rational::BigRational` exactly,
+//! and converts `num_bigint::BigUint` and `BigRational` values to correctly
+//! rounded floats.
+//!
+//! The `rug` feature converts between Float and `rug::Float`, which is backed
+//! by MPFR, and allows to check the results of this crate against MPFR.
+//!
+//! The `defmt` feature implements `defmt::Format` for Float, Category and
+//! RoundingMode, for logging on embedded targets. Floats are logged in their
+//! internal representation, like their Debug output, so that the target only
+//! sends the integers, and does not format decimal digits.
+//!
+//! The `num-traits` feature implements the numeric traits of `num-traits`,
+//! such as `Num`, `Signed`, `NumCast` and `FloatCore`, and `Float` when the
+//! `std` feature is enabled, so that floats can be used in generic numeric
+//! code. The conversions are exact when the target can hold the value, and the
+//! methods call the functions of this crate. The hyperbolic functions, `atan2`
+//! and `exp2` are computed from the exponential and arctangent functions, and
+//! are not correctly rounded. `sinh` and `cosh` are evaluated in a wider
+//! format, or from e^(|x|/2) in the widest formats, so they overflow only when
+//! the result does, and not when e^|x| does.
+//!
+//! The `serde` feature implements `Serialize` and `Deserialize` for Float and
+//! RoundingMode. Floats are serialized as a tuple of the 64-bit words of their
+//! IEEE754 interchange encoding (see `to_bits`), with the least significant
+//! word first, like `[15872]` for 1.5 in FP16. The encoding is exact, keeps
+//! the NaN payloads, and does not depend on the internal representation.
+//!
+//! The `rand` feature implements the `StandardUniform` and `Open01`
+//! distributions of `rand` for Float, and `SampleUniform`, so that `Uniform`
+//! and `random_range` work with any format. `StandardUniform` returns the
+//! multiples of 2^-p in [0, 1), where p is the precision of the format, and
+//! `Open01` returns the odd multiples of 2^-p, like the native floats of rand.
+//! The uniform ranges scale these numbers, and round the result, which never
+//! leaves the range.
+
+#[cfg(any(feature = "num", feature = "rand", feature = "rug"))]
+use super::bigint::{BigInt, LossFraction};
+#[cfg(any(
+    feature = "defmt",
+    feature = "nightly",
+    feature = "num",
+    feature = "num-traits",
+    feature = "rand",
+    feature = "rug",
+    feature = "serde"
+))]
+use super::float::Float;
+#[cfg(any(
+    feature = "num",
+    feature = "num-traits",
+    feature = "rand",
+    feature = "rug"
+))]
+use super::float::RoundingMode;
+#[cfg(feature = "num")]
+use super::float::TryFromFloatError;
+#[cfg(feature = "half")]
+use super::float::BF16;
+#[cfg(feature = "nightly")]
+use super::float::FP128;
+#[cfg(any(feature = "half", feature = "nightly"))]
+use super::float::FP16;
+#[cfg(feature = "num-traits")]
+use super::float::{OpStatus, ParseFloatError, FP64};
+#[cfg(all(feature = "num-traits", feature = "std"))]
+use super::functions::WideFloat;
+#[cfg(feature = "num")]
+use num_bigint::{BigInt as NumBigInt, BigUint, Sign};
+#[cfg(feature = "num")]
+use num_rational::BigRational;
+#[cfg(feature = "rug")]
+use rug::{float::Special, integer::Order, Integer};
+
+#[cfg(feature = "nightly")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Loads and converts a native f16 value. Notice that the number may
+    /// overflow or rounded to the nearest even.
+    pub fn from_f16(float: f16) -> Self {
+        FP16::from_native_float(float.to_bits() as u64).cast()
+    }
+
+    /// Convert this float to f16. Notice that the number may overflow or
+    /// rounded to the nearest even.
+    pub fn as_f16(&self) -> f16 {
+        let x: FP16 = self.cast();
+        f16::from_bits(x.as_native_float() as u16)
+    }
+
+    /// Loads and converts a native f128 value. Notice that the number may
+    /// overflow or rounded to the nearest even.
+    pub fn from_f128(float: f128) -> Self {
+        let bits = float.to_bits();
+        FP128::from_bits([bits as u64, (bits >> 64) as u64, 0, 0]).cast()
+    }
+
+    /// Convert this float to f128. Notice that the number may overflow or
+    /// rounded to the nearest even.
+    pub fn as_f128(&self) -> f128 {
+        let x: FP128 = self.cast();
+        let bits = x.to_bits();
+        f128::from_bits(bits[0] as u128 | (bits[1] as u128) << 64)
+    }
+}
+
+#[cfg(feature = "num")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Converts the float to a rational number. The conversion is exact,
+    /// except that negative zero becomes zero. Returns None for infinity and
+    /// NaN.
+    pub fn to_rational(&self) -> Option<BigRational> {
+        if !self.is_finite() {
+            return None;
+        }
+        let mantissa = self.get_mantissa();
+        let mut val = BigUint::default();
+        for i in (0..PARTS).rev() {
+            val = (val << 64u32) | BigUint::from(mantissa.get_part(i));
+        }
+        let one = BigUint::from(1u8);
+        let exp = self.get_exp() - MANTISSA as i64;
+        let (num, den) = if exp >= 0 {
+            (val << exp as u64, one)
+        } else {
+            (val, one << exp.unsigned_abs())
+        };
+        let sign = if self.get_sign() {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        let num = NumBigInt::from_biguint(sign, num);
+        Some(BigRational::new(num, NumBigInt::from(den)))
+    }
+
+    /// Converts the rational number `val` to the nearest float, rounded with
+    /// the rounding mode `rm`.
+    pub fn from_big_rational(val: &BigRational, rm: RoundingMode) -> Self {
+        let sign = val.numer().sign() == Sign::Minus;
+        let (num, den) = (val.numer().magnitude(), val.denom().magnitude());
+        Self::from_ratio(sign, num, den, rm)
+    }
+
+    /// Converts the integer `val` to the nearest float, rounded with the
+    /// rounding mode `rm`.
+    pub fn from_biguint(val: &BigUint, rm: RoundingMode) -> Self {
+        Self::from_ratio(false, val, &BigUint::from(1u8), rm)
+    }
+
+    /// Returns the rounded value of `num / den`.
+    fn from_ratio(
+        sign: bool,
+        num: &BigUint,
+        den: &BigUint,
+        rm: RoundingMode,
+    ) -> Self {
+        if num.bits() == 0 {
+            return Self::zero(sign);
+        }
+
+        // Scale the ratio so that the quotient has a few more bits than the
+        // mantissa. A non-zero remainder is folded into a sticky bit below
+        // the quotient, which is all that rounding needs to know about it.
+        let shift =
+            (MANTISSA + 3) as i64 + den.bits() as i64 - num.bits() as i64;
+        let (num, den) = if shift >= 0 {
+            (num << shift as u64, den.clone())
+        } else {
+            (num.clone(), den << shift.unsigned_abs())
+        };
+        let sticky = (&num % &den).bits() != 0;
+        let quot = ((num / den) << 1u32) | BigUint::from(sticky as u8);
+
+        let mut parts = [0; PARTS];
+        for (part, digit) in parts.iter_mut().zip(quot.iter_u64_digits()) {
+            *part = digit;
+        }
+        let mantissa = BigInt::from_parts(&parts);
+        let mut a = Self::new(sign, MANTISSA as i64 - shift - 1, mantissa);
+        a.normalize(rm, LossFraction::ExactlyZero);
+        a
+    }
+}
+
+#[cfg(feature = "num")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    TryFrom<Float<EXPONENT, MANTISSA, PARTS>> for BigRational
+{
+    type Error = TryFromFloatError;
+
+    /// Converts the float to a rational number. Infinity and NaN can't be
+    /// converted.
+    fn try_from(
+        val: Float<EXPONENT, MANTISSA, PARTS>,
+    ) -> Result<Self, Self::Error> {
+        val.to_rational().ok_or(TryFromFloatError(()))
+    }
+}
+
+#[cfg(feature = "rug")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Converts the float to a `rug::Float` with the precision of this
+    /// format. The conversion is exact, except for the NaN payload.
+    pub fn to_rug(&self) -> rug::Float {
+        let prec = Self::get_precision() as u32;
+        let sign = self.get_sign();
+        if self.is_nan() {
+            return rug::Float::with_val(prec, Special::Nan);
+        }
+        if self.is_inf() {
+            let inf = [Special::Infinity, Special::NegInfinity];
+            return rug::Float::with_val(prec, inf[sign as usize]);
+        }
+        if self.is_zero() {
+            let zero = [Special::Zero, Special::NegZero];
+            return rug::Float::with_val(prec, zero[sign as usize]);
+        }
+
+        let mantissa = self.get_mantissa();
+        let mut val = Integer::new();
+        for i in (0..PARTS).rev() {
+            val <<= 64u32;
+            val += mantissa.get_part(i);
+        }
+        let mut val = rug::Float::with_val(prec, val);
+        val <<= (self.get_exp() - MANTISSA as i64) as i32;
+        if sign {
+            val = -val;
+        }
+        val
+    }
+
+    /// Converts the `rug::Float` value `val` to the nearest float, rounded
+    /// with the rounding mode `rm`. Values of any precision are rounded
+    /// once, and values that are out of range overflow or become subnormal.
+    pub fn from_rug(val: &rug::Float, rm: RoundingMode) -> Self {
+        let sign = val.is_sign_negative();
+        if val.is_nan() {
+            return Self::nan(sign);
+        }
+        if val.is_infinite() {
+            return Self::inf(sign);
+        }
+        if val.is_zero() {
+            return Self::zero(sign);
+        }
+
+        // Keep a few more bits than the mantissa, and fold the bits that are
+        // shifted out into a sticky bit, which is all that rounding needs to
+        // know about them.
+        let (mut int, exp) = val.to_integer_exp().unwrap();
+        int.abs_mut();
+        let bits = int.significant_bits() as i64;
+        let shift = (bits - (MANTISSA + 3) as i64).max(0);
+        let sticky = int.find_one(0).unwrap() < shift as u32;
+        int >>= shift as u32;
+        int <<= 1u32;
+        int += sticky as u32;
+
+        let mut parts = [0; PARTS];
+        int.write_digits(&mut parts, Order::Lsf);
+        let exp = MANTISSA as i64 + exp as i64 + shift - 1;
+        let mut a = Self::new(sign, exp, BigInt::from_parts(&parts));
+        a.normalize(rm, LossFraction::ExactlyZero);
+        a
+    }
+
+    /// Returns true if this float is equal to the reference value
+    /// `reference`, rounded to the nearest even value of this format. This
+    /// allows to validate the result of an operation against MPFR, computed
+    /// at the precision of this format (see [`Self::to_rug`]). Zeros must
+    /// have the same sign, and all NaNs are equal.
+    pub fn matches_rug(&self, reference: &rug::Float) -> bool {
+        let rm = RoundingMode::NearestTiesToEven;
+        let expected = Self::from_rug(reference, rm);
+        if self.is_nan() || expected.is_nan() {
+            return self.is_nan() && expected.is_nan();
+        }
+        self.to_bits() == expected.to_bits()
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<half::f16> for FP16 {
+    fn from(val: half::f16) -> Self {
+        FP16::from_native_float(val.to_bits() as u64)
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<FP16> for half::f16 {
+    fn from(val: FP16) -> Self {
+        half::f16::from_bits(val.as_native_float() as u16)
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<half::bf16> for BF16 {
+    fn from(val: half::bf16) -> Self {
+        BF16::from_native_float(val.to_bits() as u64)
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<BF16> for half::bf16 {
+    fn from(val: BF16) -> Self {
+        half::bf16::from_bits(val.as_native_float() as u16)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    defmt::Format for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn format(&self, fmt: defmt::Formatter) {
+        // Send the parts starting at the most significant part, like Debug.
+        let m = self.get_mantissa();
+        let parts: [u64; PARTS] =
+            core::array::from_fn(|i| m.get_part(PARTS - 1 - i));
+        defmt::write!(
+            fmt,
+            "Float {{ sign: {=bool}, category: {}, exp: {=i64}, mantissa: {=[?]:#x} }}",
+            self.get_sign(),
+            self.get_category(),
+            self.get_exp(),
+            parts[..],
+        );
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Returns the mantissa, the exponent and the sign of the number, like
+    /// `integer_decode` of num-traits. Mantissas that are wider than 64 bits
+    /// are truncated to their top 64 bits, and the exponent saturates.
+    /// Infinity and NaN return a zero mantissa.
+    fn integer_decode_u64(&self) -> (u64, i16, i8) {
+        let sign = if self.get_sign() { -1 } else { 1 };
+        if !self.is_finite() || self.is_zero() {
+            return (0, 0, sign);
+        }
+        let mut mantissa = self.get_mantissa();
+        let shift = mantissa.msb_index().saturating_sub(64);
+        mantissa.shift_right(shift);
+        let exp = self.get_exp() - MANTISSA as i64 + shift as i64;
+        let exp = exp.clamp(i16::MIN as i64, i16::MAX as i64) as i16;
+        (mantissa.as_u64(), exp, sign)
+    }
+
+    /// Returns true if the number is in the range of the integer type, after
+    /// it is rounded toward zero.
+    fn is_valid_integer(status: OpStatus) -> bool {
+        !status.contains(OpStatus::INVALID_OP)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::Zero for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn zero() -> Self {
+        Self::zero(false)
+    }
+
+    fn is_zero(&self) -> bool {
+        Self::is_zero(self)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::One for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn one() -> Self {
+        Self::one(false)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::Num for Float<EXPONENT, MANTISSA, PARTS>
+{
+    type FromStrRadixErr = ParseFloatError;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, ParseFloatError> {
+        Self::from_str_radix(str, radix, RoundingMode::NearestTiesToEven)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::Signed for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn abs(&self) -> Self {
+        Self::abs(self)
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        self.fdim(*other)
+    }
+
+    fn signum(&self) -> Self {
+        Self::signum(self)
+    }
+
+    fn is_positive(&self) -> bool {
+        !self.get_sign()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.get_sign()
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::ToPrimitive for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn to_i64(&self) -> Option<i64> {
+        let (val, status) = self.to_i64_with_status(RoundingMode::Zero);
+        Self::is_valid_integer(status).then_some(val)
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        let (val, status) = self.to_u64_with_status(RoundingMode::Zero);
+        Self::is_valid_integer(status).then_some(val)
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        let (val, status) = self.to_i128_with_status(RoundingMode::Zero);
+        Self::is_valid_integer(status).then_some(val)
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        let (val, status) = self.to_u128_with_status(RoundingMode::Zero);
+        Self::is_valid_integer(status).then_some(val)
+    }
+
+    fn to_f32(&self) -> Option<f32> {
+        Some(self.as_f32())
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.as_f64())
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::NumCast for Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Converts the number `n`. Integers are converted with a single rounding,
+    /// and other numbers are converted through f64.
+    fn from<T: num_traits::ToPrimitive>(n: T) -> Option<Self> {
+        let val = FP64::from_f64(n.to_f64()?);
+        if val.is_normal() && val == val.trunc() {
+            if let Some(n) = n.to_i128() {
+                return Some(Self::from_i128(n));
+            }
+            if let Some(n) = n.to_u128() {
+                return Some(Self::from_u128(n));
+            }
+        }
+        Some(val.cast())
+    }
+}
+
+/// Implements the methods that are shared by `FloatCore` and `Float` of
+/// num-traits, which take the number by value, with the inherent methods.
+#[cfg(feature = "num-traits")]
+macro_rules! impl_num_traits_float_methods {
+    () => {
+        fn nan() -> Self {
+            Self::nan(false)
+        }
+
+        fn infinity() -> Self {
+            Self::inf(false)
+        }
+
+        fn neg_infinity() -> Self {
+            Self::inf(true)
+        }
+
+        fn neg_zero() -> Self {
+            Self::zero(true)
+        }
+
+        fn min_value() -> Self {
+            Self::largest(true)
+        }
+
+        fn min_positive_value() -> Self {
+            Self::smallest_normal(false)
+        }
+
+        fn max_value() -> Self {
+            Self::largest(false)
+        }
+
+        fn epsilon() -> Self {
+            Self::epsilon()
+        }
+
+        fn is_nan(self) -> bool {
+            Self::is_nan(&self)
+        }
+
+        fn is_infinite(self) -> bool {
+            self.is_inf()
+        }
+
+        fn is_finite(self) -> bool {
+            Self::is_finite(&self)
+        }
+
+        fn is_normal(self) -> bool {
+            Self::is_normal(&self) && !self.is_subnormal()
+        }
+
+        fn is_subnormal(self) -> bool {
+            Self::is_subnormal(&self)
+        }
+
+        fn classify(self) -> core::num::FpCategory {
+            Self::classify(&self)
+        }
+
+        fn floor(self) -> Self {
+            Self::floor(&self)
+        }
+
+        fn ceil(self) -> Self {
+            Self::ceil(&self)
+        }
+
+        fn round(self) -> Self {
+            Self::round(&self)
+        }
+
+        fn trunc(self) -> Self {
+            Self::trunc(&self)
+        }
+
+        fn fract(self) -> Self {
+            self - Self::trunc(&self)
+        }
+
+        fn abs(self) -> Self {
+            Self::abs(&self)
+        }
+
+        fn signum(self) -> Self {
+            Self::signum(&self)
+        }
+
+        fn is_sign_positive(self) -> bool {
+            !self.get_sign()
+        }
+
+        fn is_sign_negative(self) -> bool {
+            self.get_sign()
+        }
+
+        fn min(self, other: Self) -> Self {
+            Self::min(&self, other)
+        }
+
+        fn max(self, other: Self) -> Self {
+            Self::max(&self, other)
+        }
+
+        fn recip(self) -> Self {
+            Self::recip(&self)
+        }
+
+        fn powi(self, n: i32) -> Self {
+            Self::powi(&self, n as i64)
+        }
+
+        fn to_degrees(self) -> Self {
+            Self::to_degrees(&self)
+        }
+
+        fn to_radians(self) -> Self {
+            Self::to_radians(&self)
+        }
+
+        fn integer_decode(self) -> (u64, i16, i8) {
+            self.integer_decode_u64()
+        }
+    };
+}
+
+#[cfg(feature = "num-traits")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::float::FloatCore for Float<EXPONENT, MANTISSA, PARTS>
+{
+    impl_num_traits_float_methods!();
+}
+
+#[cfg(all(feature = "num-traits", feature = "std"))]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    num_traits::Float for Float<EXPONENT, MANTISSA, PARTS>
+{
+    impl_num_traits_float_methods!();
+
+    /// Computes `self * a + b`, with a single rounding.
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        let rm = RoundingMode::NearestTiesToEven;
+        Self::mul_add_widening(self, a, b, rm)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        Self::powf(&self, n)
+    }
+
+    fn sqrt(self) -> Self {
+        Self::sqrt(&self)
+    }
+
+    fn exp(self) -> Self {
+        Self::exp(&self)
+    }
+
+    fn exp2(self) -> Self {
+        Self::from_u64(2).powf(self)
+    }
+
+    fn ln(self) -> Self {
+        Self::ln(&self)
+    }
+
+    fn log(self, base: Self) -> Self {
+        Self::log(&self, base)
+    }
+
+    fn log2(self) -> Self {
+        Self::log2(&self)
+    }
+
+    fn log10(self) -> Self {
+        Self::log10(&self)
+    }
+
+    fn abs_sub(self, other: Self) -> Self {
+        self.fdim(other)
+    }
+
+    fn cbrt(self) -> Self {
+        Self::cbrt(&self)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        Self::hypot(&self, other)
+    }
+
+    fn sin(self) -> Self {
+        Self::sin(&self)
+    }
+
+    fn cos(self) -> Self {
+        Self::cos(&self)
+    }
+
+    fn tan(self) -> Self {
+        Self::tan(&self)
+    }
+
+    fn asin(self) -> Self {
+        Self::asin(&self)
+    }
+
+    fn acos(self) -> Self {
+        Self::acos(&self)
+    }
+
+    fn atan(self) -> Self {
+        Self::atan(&self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        if self.is_nan() || other.is_nan() {
+            return Self::nan(false);
+        }
+        // Compute the angle in the first quadrant, and reflect it.
+        let pi = Self::pi();
+        let angle = if self.is_zero() {
+            Self::zero(false)
+        } else if self.is_inf() && other.is_inf() {
+            pi.scale(-2, RoundingMode::NearestTiesToEven)
+        } else {
+            (self / other).abs().atan()
+        };
+        let angle = if other.get_sign() { pi - angle } else { angle };
+        angle.copysign(self)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        Self::sin_cos(&self)
+    }
+
+    fn exp_m1(self) -> Self {
+        Self::exp_m1(&self)
+    }
+
+    fn ln_1p(self) -> Self {
+        Self::ln_1p(&self)
+    }
+
+    fn sinh(self) -> Self {
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return num_traits::Float::sinh(x).cast();
+        }
+        if self.abs() > Self::from_u64(MANTISSA as u64) {
+            return half_exp(self.abs()).copysign(self);
+        }
+        // sinh(x) = (expm1(x) - expm1(-x)) / 2, which does not cancel.
+        let diff = self.exp_m1() - self.neg().exp_m1();
+        diff.scale(-1, RoundingMode::NearestTiesToEven)
+    }
+
+    fn cosh(self) -> Self {
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return num_traits::Float::cosh(x).cast();
+        }
+        if self.abs() > Self::from_u64(MANTISSA as u64) {
+            return half_exp(self.abs());
+        }
+        let sum = self.exp() + self.neg().exp();
+        sum.scale(-1, RoundingMode::NearestTiesToEven)
+    }
+
+    fn tanh(self) -> Self {
+        // tanh(x) = -t / (t + 2), where t = expm1(-2|x|), which does not
+        // overflow.
+        let t = self.abs().scale(1, RoundingMode::NearestTiesToEven).neg();
+        let t = t.exp_m1();
+        let res = t.neg() / (t + Self::from_u64(2));
+        res.copysign(self)
+    }
+
+    fn asinh(self) -> Self {
+        Self::asinh(&self)
+    }
+
+    fn acosh(self) -> Self {
+        Self::acosh(&self)
+    }
+
+    fn atanh(self) -> Self {
+        Self::atanh(&self)
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        Self::copysign(&self, sign)
+    }
+}
+
+/// Returns e^x / 2 for large x, where e^-x is below the rounding error of
+/// sinh(x) and cosh(x). It is computed as e^(x/2) * (e^(x/2) / 2), which does
+/// not overflow unless the result does.
+#[cfg(all(feature = "num-traits", feature = "std"))]
+fn half_exp<const E: usize, const M: usize, const P: usize>(
+    x: Float<E, M, P>,
+) -> Float<E, M, P> {
+    let rm = RoundingMode::NearestTiesToEven;
+    let h = x.scale(-1, rm).exp();
+    h * h.scale(-1, rm)
+}
+
+#[cfg(feature = "serde")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// The number of 64-bit words in the serialized encoding.
+    const SERDE_WORDS: usize = (1 + EXPONENT + MANTISSA).div_ceil(64);
+}
+
+#[cfg(feature = "serde")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    serde::Serialize for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+        let bits = self.to_bits();
+        let mut tuple = serializer.serialize_tuple(Self::SERDE_WORDS)?;
+        for word in &bits[..Self::SERDE_WORDS] {
+            tuple.serialize_element(word)?;
+        }
+        tuple.end()
+    }
+}
+
+/// Reads the words of the interchange encoding of Float<E, M, P>.
+#[cfg(feature = "serde")]
+struct EncodingVisitor<const E: usize, const M: usize, const P: usize>;
+
+#[cfg(feature = "serde")]
+impl<'de, const E: usize, const M: usize, const P: usize>
+    serde::de::Visitor<'de> for EncodingVisitor<E, M, P>
+{
+    type Value = Float<E, M, P>;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let words = Float::<E, M, P>::SERDE_WORDS;
+        write!(f, "{} words of a {}-bit float encoding", words, 1 + E + M)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        use serde::de::{Error, Unexpected};
+        let words = Float::<E, M, P>::SERDE_WORDS;
+        let mut parts = [0; P];
+        for (i, part) in parts.iter_mut().take(words).enumerate() {
+            let word = seq.next_element()?;
+            *part = word.ok_or_else(|| Error::invalid_length(i, &self))?;
+        }
+
+        // Reject the encodings that set bits above the top bit.
+        let top = parts[words - 1];
+        let bits = (1 + E + M) % 64;
+        if bits != 0 && top >> bits != 0 {
+            return Err(Error::invalid_value(Unexpected::Unsigned(top), &self));
+        }
+        Ok(Float::from_bits(parts))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    serde::Deserialize<'de> for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(Self::SERDE_WORDS, EncodingVisitor)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Returns a random multiple of 2^-p in [0, 1), where p is the precision.
+    /// If `odd` is set then the lowest bit is set, and the number is an odd
+    /// multiple of 2^-p, in (0, 1).
+    fn random_fraction<R: rand::Rng + ?Sized>(rng: &mut R, odd: bool) -> Self {
+        let bits = MANTISSA + 1;
+        let words = bits.div_ceil(64);
+        let parts: [u64; PARTS] =
+            core::array::from_fn(
+                |i| if i < words { rng.next_u64() } else { 0 },
+            );
+        let mut mantissa = BigInt::from_parts(&parts);
+        mantissa.mask(bits);
+        if odd && mantissa.is_even() {
+            mantissa.flip_bit(0);
+        }
+
+        // The value is mantissa * 2^-p. Formats with a narrow exponent range
+        // round down, and never return 1.
+        let mut a = Self::new(false, -1, mantissa);
+        a.normalize(RoundingMode::Zero, LossFraction::ExactlyZero);
+        a
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    rand::distr::Distribution<Float<EXPONENT, MANTISSA, PARTS>>
+    for rand::distr::StandardUniform
+{
+    fn sample<R: rand::Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Float<EXPONENT, MANTISSA, PARTS> {
+        Float::random_fraction(rng, false)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    rand::distr::Distribution<Float<EXPONENT, MANTISSA, PARTS>>
+    for rand::distr::Open01
+{
+    fn sample<R: rand::Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Float<EXPONENT, MANTISSA, PARTS> {
+        Float::random_fraction(rng, true)
+    }
+}
+
+/// Samples floats uniformly from a range. This is the sampler of Float, for
+/// `rand::distr::Uniform`.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy)]
+pub struct UniformFloat<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+> {
+    low: Float<EXPONENT, MANTISSA, PARTS>,
+    high: Float<EXPONENT, MANTISSA, PARTS>,
+    scale: Float<EXPONENT, MANTISSA, PARTS>,
+    inclusive: bool,
+}
+
+#[cfg(feature = "rand")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    UniformFloat<EXPONENT, MANTISSA, PARTS>
+{
+    /// Creates a sampler for the range from `low` to `high`, which includes
+    /// `high` if `inclusive` is set.
+    fn new_range(
+        low: Float<EXPONENT, MANTISSA, PARTS>,
+        high: Float<EXPONENT, MANTISSA, PARTS>,
+        inclusive: bool,
+    ) -> Result<Self, rand::distr::uniform::Error> {
+        use rand::distr::uniform::Error;
+        if !low.is_finite() || !high.is_finite() {
+            return Err(Error::NonFinite);
+        }
+        if low > high || (low == high && !inclusive) {
+            return Err(Error::EmptyRange);
+        }
+        let mut scale = high - low;
+        if inclusive {
+            // Stretch the range, so that the largest fraction maps to `high`.
+            scale = scale / Float::one(false).next_down();
+        }
+        if !scale.is_finite() {
+            return Err(Error::NonFinite);
+        }
+        Ok(UniformFloat {
+            low,
+            high,
+            scale,
+            inclusive,
+        })
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    rand::distr::uniform::UniformSampler
+    for UniformFloat<EXPONENT, MANTISSA, PARTS>
+{
+    type X = Float<EXPONENT, MANTISSA, PARTS>;
+
+    fn new<B1, B2>(
+        low: B1,
+        high: B2,
+    ) -> Result<Self, rand::distr::uniform::Error>
+    where
+        B1: rand::distr::uniform::SampleBorrow<Self::X> + Sized,
+        B2: rand::distr::uniform::SampleBorrow<Self::X> + Sized,
+    {
+        Self::new_range(*low.borrow(), *high.borrow(), false)
+    }
+
+    fn new_inclusive<B1, B2>(
+        low: B1,
+        high: B2,
+    ) -> Result<Self, rand::distr::uniform::Error>
+    where
+        B1: rand::distr::uniform::SampleBorrow<Self::X> + Sized,
+        B2: rand::distr::uniform::SampleBorrow<Self::X> + Sized,
+    {
+        Self::new_range(*low.borrow(), *high.borrow(), true)
+    }
+
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        // The sum may round up to `high`. Exclusive ranges draw another
+        // number, which terminates because zero maps to `low`.
+        loop {
+            let fraction = Float::random_fraction(rng, false);
+            let val = fraction * self.scale + self.low;
+            if val < self.high {
+                return val;
+            }
+            if self.inclusive {
+                return self.high;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    rand::distr::uniform::SampleUniform for Float<EXPONENT, MANTISSA, PARTS>
+{
+    type Sampler = UniformFloat<EXPONENT, MANTISSA, PARTS>;
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn test_half_interop() {
+    use half::{bf16, f16};
+
+    let x = FP16::from(f16::from_f32(1.5));
+    assert_eq!(x.as_f32(), 1.5);
+    let x: f16 = FP16::from_f64(-0.1).into();
+    assert_eq!(x, f16::from_f64(-0.1));
+    let x = BF16::from(bf16::from_f32(3.0e38));
+    assert_eq!(x.as_f32(), bf16::from_f32(3.0e38).to_f32());
+    let x: bf16 = BF16::from_f64(1e-40).into();
+    assert_eq!(x, bf16::from_f64(1e-40));
+
+    // Every encoding converts back to itself, except for signaling NaNs,
+    // which become quiet.
+    for bits in 0..=u16::MAX {
+        let h = f16::from_bits(bits);
+        let back: f16 = FP16::from(h).into();
+        let quiet = if h.is_nan() { 1 << 9 } else { 0 };
+        assert_eq!(back.to_bits(), bits | quiet);
+        assert_eq!(FP16::from(h).as_f64().to_bits(), h.to_f64().to_bits());
+
+        let h = bf16::from_bits(bits);
+        let back: bf16 = BF16::from(h).into();
+        let quiet = if h.is_nan() { 1 << 6 } else { 0 };
+        assert_eq!(back.to_bits(), bits | quiet);
+    }
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn test_nightly_interop() {
+    use crate::utils::Lfsr;
+    use crate::FP64;
+
+    // Compare the soft implementation to the native types. The sign of NaN
+    // results is not specified, so NaNs only need to match each other.
+    fn same128(a: f128, b: f128) -> bool {
+        (a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits()
+    }
+    fn same16(a: f16, b: f16) -> bool {
+        (a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits()
+    }
+
+    let mut lfsr = Lfsr::new();
+    for _ in 0..2000 {
+        let a = f64::from_bits(lfsr.get64());
+        let b = f64::from_bits(lfsr.get64());
+        if a.is_nan() || b.is_nan() {
+            continue;
+        }
+        let (x, y) = (a as f128, b as f128);
+        let (fx, fy) = (FP128::from_f128(x), FP128::from_f128(y));
+        assert!(same128((fx + fy).as_f128(), x + y));
+        assert!(same128((fx * fy).as_f128(), x * y));
+        assert!(same128((fx / fy).as_f128(), x / y));
+
+        let (x, y) = (a as f16, b as f16);
+        assert!(same16(FP64::from_f64(a).as_f16(), x));
+        let (fx, fy) = (FP16::from_f16(x), FP16::from_f16(y));
+        assert!(same16((fx + fy).as_f16(), x + y));
+        assert!(same16((fx * fy).as_f16(), x * y));
+    }
+
+    // Every f16 converts back to itself.
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+        if !x.is_nan() {
+            assert_eq!(FP16::from_f16(x).as_f16().to_bits(), bits);
+        }
+    }
+}
+
+#[cfg(feature = "num")]
+#[test]
+fn test_num_interop() {
+    use crate::utils::Lfsr;
+    use crate::{FP32, FP64};
+
+    // Every finite f32 value survives the round trip.
+    let rm = RoundingMode::NearestTiesToEven;
+    let mut lfsr = Lfsr::new();
+    for _ in 0..5000 {
+        let x = FP32::from_f32(f32::from_bits(lfsr.get64() as u32));
+        if let Ok(r) = BigRational::try_from(x) {
+            assert_eq!(FP32::from_big_rational(&r, rm), x);
+        } else {
+            assert!(!x.is_finite());
+        }
+    }
+
+    // The ratio of two integers is correctly rounded, like the native
+    // division of two exact values.
+    for _ in 0..5000 {
+        let a = lfsr.get64() >> 11;
+        let b = (lfsr.get64() >> (lfsr.get64() % 53 + 11)) | 1;
+        let r = BigRational::new(a.into(), b.into());
+        let x = FP64::from_big_rational(&r, rm);
+        assert_eq!(x.as_f64(), a as f64 / b as f64);
+        let x = FP64::from_big_rational(&-r, RoundingMode::Zero);
+        let (a, b) = (FP64::from_u64(a), FP64::from_u64(b));
+        let y = FP64::div_with_rm(a, b, RoundingMode::Zero);
+        assert_eq!(x.as_f64(), -y.as_f64());
+    }
+
+    // Integers that don't fit in the mantissa, overflow and underflow.
+    let big = BigUint::from(u128::MAX) * BigUint::from(u128::MAX);
+    let x = FP64::from_biguint(&big, rm);
+    assert_eq!(x.as_f64(), (u128::MAX as f64) * (u128::MAX as f64));
+    let x = FP32::from_biguint(&(BigUint::from(1u8) << 128u32), rm);
+    assert!(x.is_inf());
+    let tiny = BigRational::new(1.into(), NumBigInt::from(1u8) << 1074u32);
+    assert_eq!(
+        FP64::from_big_rational(&tiny, rm).as_f64(),
+        f64::from_bits(1)
+    );
+    let tiny = BigRational::new(1.into(), NumBigInt::from(1u8) << 1076u32);
+    assert!(FP64::from_big_rational(&tiny, rm).is_zero());
+    assert!(FP64::from_big_rational(&tiny, RoundingMode::Positive).is_normal());
+}
+
+#[cfg(all(feature = "num-traits", feature = "std"))]
+#[test]
+fn test_num_traits_interop() {
+    use crate::utils;
+    use crate::{FP128, FP16, FP256, FP32, FP64};
+    use num_traits::float::FloatCore;
+    use num_traits::{
+        Float as _, Num, NumCast, One, Signed, ToPrimitive, Zero,
+    };
+
+    // Generic code computes the same values as f64, up to the rounding errors
+    // of the f64 computation.
+    fn poly<T: num_traits::Float>(x: T) -> T {
+        let c: T = NumCast::from(0.25).unwrap();
+        x.mul_add(x, c).sqrt() + x.abs().ln_1p() + x.sin_cos().0.abs()
+    }
+    for v in [0.1, -2.5, 3.75, 1000.] {
+        let r = poly(FP128::from_f64(v)).as_f64();
+        assert!(utils::is_close(r, poly(v), 4));
+        let r = poly(FP64::from_f64(v)).as_f64();
+        assert!(utils::is_close(r, poly(v), 4));
+    }
+
+    // The functions that are computed from other functions.
+    for v in utils::get_special_test_values() {
+        let x = FP128::from_f64(v);
+        assert!(utils::is_close(x.sinh().as_f64(), v.sinh(), 1));
+        assert!(utils::is_close(x.cosh().as_f64(), v.cosh(), 1));
+        assert!(utils::is_close(x.tanh().as_f64(), v.tanh(), 1));
+        assert!(utils::is_close(x.exp2().as_f64(), v.exp2(), 1));
+        for w in [0.0, -0.0, 1.5, -3.0, f64::INFINITY, -f64::INFINITY] {
+            let r = x.atan2(FP128::from_f64(w)).as_f64();
+            assert!(utils::is_close(r, v.atan2(w), 1), "{} {}", v, w);
+        }
+    }
+    // Arguments where e^|x| overflows, but sinh(x) and cosh(x) do not.
+    for v in [709.9, 710.3, 710.47, -710.47, 710.48, 711.] {
+        let x = FP64::from_f64(v);
+        assert!(utils::is_close(x.sinh().as_f64(), v.sinh(), 1));
+        assert!(utils::is_close(x.cosh().as_f64(), v.cosh(), 1));
+        let x = FP256::from_f64(v);
+        assert!(utils::is_close(x.sinh().as_f64(), v.sinh(), 1));
+        assert!(utils::is_close(x.cosh().as_f64(), v.cosh(), 1));
+    }
+    for v in [89f32, -89.4, 89.5] {
+        let x = FP32::from_f32(v);
+        let r0 = x.sinh().as_f32() as f64;
+        assert!(utils::is_close(r0, v.sinh() as f64, 1));
+        let r0 = x.cosh().as_f32() as f64;
+        assert!(utils::is_close(r0, v.cosh() as f64, 1));
+    }
+    let x = FP64::from_f64(1e-300);
+    assert_eq!(x.sinh().as_f64(), 1e-300);
+    assert_eq!(x.tanh().as_f64(), 1e-300);
+    assert_eq!(FP64::from_f64(0.1).mul_add(x, x).as_f64(), 1.1e-300);
+    let a = FP256::one(false) + FP256::from_f64(2f64.powi(-130));
+    let res = a.mul_add(a, FP256::one(true));
+    let low = FP256::from_f64(2f64.powi(-260));
+    assert_eq!(res, FP256::from_f64(2f64.powi(-129)) + low);
+
+    // The basic traits.
+    let one = <FP64 as One>::one();
+    assert!(<FP64 as Zero>::zero().is_zero() && one.as_f64() == 1.);
+    let x = <FP64 as Num>::from_str_radix("-1.8", 16).unwrap();
+    assert_eq!(x.as_f64(), -1.5);
+    assert!(Signed::is_negative(&x) && !Signed::is_positive(&x));
+    assert_eq!(Signed::abs_sub(&x, &one).as_f64(), 0.);
+    assert_eq!(FloatCore::fract(x).as_f64(), -0.5);
+    assert_eq!(FloatCore::powi(x, 3).as_f64(), -3.375);
+    let min = <FP64 as FloatCore>::min_positive_value();
+    assert_eq!(min, FP64::smallest_normal(false));
+    assert!(!FloatCore::is_normal(FP64::from_f64(1e-310)));
+    assert_eq!(FloatCore::integer_decode(x), (3 << 51, -52, -1));
+    assert_eq!(
+        FloatCore::integer_decode(0.1),
+        FloatCore::integer_decode(0.1f64)
+    );
+    let x = FP128::recip(&FP128::from_u64(3));
+    let (m, e, _) = FloatCore::integer_decode(x);
+    assert_eq!(m as f64 * 2f64.powi(e as i32), 1. / 3.);
+
+    // Conversions between numbers.
+    let x = FP128::from_u128(u128::MAX << 15);
+    assert_eq!(ToPrimitive::to_u128(&x), Some(u128::MAX << 15));
+    assert_eq!(ToPrimitive::to_i64(&x), None);
+    let x = FP128::from_u128(u128::MAX);
+    assert_eq!(ToPrimitive::to_u128(&x), None);
+    let x = FP16::from_f64(-2.75);
+    assert_eq!(ToPrimitive::to_i64(&x), Some(-2));
+    assert_eq!(ToPrimitive::to_u64(&x), None);
+    assert_eq!(x.to_f32(), Some(-2.75));
+    assert_eq!(ToPrimitive::to_i128(&FP16::nan(false)), None);
+    let x: FP128 = NumCast::from(u64::MAX).unwrap();
+    assert_eq!(x, FP128::from_u64(u64::MAX));
+    let x: FP16 = NumCast::from(2049.5).unwrap();
+    assert_eq!(x.as_f64(), 2050.);
+    let x: FP16 = NumCast::from(-0.0).unwrap();
+    assert!(x.is_zero() && x.is_negative());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_interop() {
+    use crate::utils::Lfsr;
+    use crate::{RoundingMode, FP128, FP16};
+
+    // The encodings of known values.
+    let x = FP16::from_f64(1.5);
+    assert_eq!(serde_json::to_string(&x).unwrap(), "[15872]");
+    let x = FP128::from_u64(1);
+    let json = serde_json::to_string(&x).unwrap();
+    assert_eq!(json, "[0,4611404543450677248]");
+    assert_eq!(serde_json::from_str::<FP128>(&json).unwrap(), x);
+    let x: FP16 = serde_json::from_str("[31744]").unwrap();
+    assert!(x.is_inf());
+    let rm: RoundingMode = serde_json::from_str("\"Positive\"").unwrap();
+    assert!(matches!(rm, RoundingMode::Positive));
+
+    // Random numbers, including NaNs with payloads, survive the round trip.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..500 {
+        let x = FP128::from_bits([lfsr.get64(), lfsr.get64(), 0, 0]);
+        let y: FP128 = serde_json::from_value(serde_json::json!(x)).unwrap();
+        assert_eq!(x.to_bits(), y.to_bits());
+        let x = FP16::from_bits([lfsr.get64() & 0xffff]);
+        let y: FP16 = serde_json::from_value(serde_json::json!(x)).unwrap();
+        assert_eq!(x.to_bits(), y.to_bits());
+    }
+
+    // Invalid encodings.
+    assert!(serde_json::from_str::<FP128>("[1]").is_err());
+    assert!(serde_json::from_str::<FP128>("[1,2,3]").is_err());
+    assert!(serde_json::from_str::<FP16>("[65536]").is_err());
+    assert!(serde_json::from_str::<FP16>("1.5").is_err());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_rand_interop() {
+    use crate::utils::Lfsr;
+    use crate::{FP128, FP16, FP64};
+    use rand::distr::uniform::Error;
+    use rand::distr::{Distribution, Open01, StandardUniform, Uniform};
+    use rand::Rng;
+
+    struct LfsrRng(Lfsr);
+    impl rand::RngCore for LfsrRng {
+        fn next_u32(&mut self) -> u32 {
+            self.0.get64() as u32
+        }
+        fn next_u64(&mut self) -> u64 {
+            self.0.get64()
+        }
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            rand::rand_core::impls::fill_bytes_via_next(self, dst)
+        }
+    }
+    let mut rng = LfsrRng(Lfsr::new());
+    let rm = RoundingMode::Zero;
+
+    // The numbers are multiples of 2^-p in [0, 1), and all of the bits are
+    // random.
+    let mut odd = 0;
+    for _ in 0..1000 {
+        let x: FP16 = rng.random();
+        assert!((0..1).contains(&x));
+        assert_eq!(x.scale(11, rm).trunc(), x.scale(11, rm));
+        let x: FP128 = StandardUniform.sample(&mut rng);
+        assert!((0..1).contains(&x));
+        let k = x.scale(113, rm);
+        assert_eq!(k.trunc(), k);
+        odd += k.to_i128(rm) & 1;
+        let x: FP128 = Open01.sample(&mut rng);
+        assert!(x > 0 && x < 1);
+        assert_eq!(x.scale(113, rm).to_i128(rm) & 1, 1);
+    }
+    assert!(odd > 400 && odd < 600);
+
+    // Uniform ranges.
+    let dist = Uniform::new(FP64::from_f64(-2.5), FP64::from_f64(10.)).unwrap();
+    let mut sum = FP64::zero(false);
+    for _ in 0..1000 {
+        let x = dist.sample(&mut rng);
+        assert!(x >= -2.5 && x < 10);
+        sum = sum + x;
+    }
+    assert!(sum > 3500 && sum < 4000);
+    let x = rng.random_range(FP128::from_u64(1)..=FP128::from_u64(2));
+    assert!((1..=2).contains(&x));
+
+    // Ranges of a single ulp, where the sum rounds to the bounds.
+    let one = FP16::one(false);
+    let dist = Uniform::new(one, one.next_up()).unwrap();
+    assert!((&mut rng).sample_iter(dist).take(100).all(|x| x == one));
+    let dist = Uniform::new_inclusive(one, one.next_up()).unwrap();
+    let count = (&mut rng)
+        .sample_iter(dist)
+        .take(100)
+        .filter(|x| *x == one)
+        .count();
+    assert!(count > 20 && count < 80);
+    let dist = Uniform::new_inclusive(one, one).unwrap();
+    assert_eq!(dist.sample(&mut rng), one);
+
+    // Invalid ranges.
+    assert_eq!(Uniform::new(one, one).unwrap_err(), Error::EmptyRange);
+    assert_eq!(Uniform::new(one, -one).unwrap_err(), Error::EmptyRange);
+    let nan = FP16::nan(false);
+    assert_eq!(Uniform::new(nan, one).unwrap_err(), Error::NonFinite);
+    let (min, max) = (FP16::largest(true), FP16::largest(false));
+    assert_eq!(Uniform::new(min, max).unwrap_err(), Error::NonFinite);
+}
+
+#[cfg(feature = "rug")]
+#[test]
+fn test_rug_interop() {
+    use crate::utils::Lfsr;
+    use crate::{FP16, FP64};
+
+    // Every f16 value survives the round trip.
+    let rm = RoundingMode::NearestTiesToEven;
+    for bits in 0..=u16::MAX {
+        let x = FP16::from_bits([bits as u64]);
+        assert!(x.matches_rug(&x.to_rug()));
+        if x.is_finite() {
+            assert_eq!(x.to_rug().to_f64(), x.as_f64());
+        }
+    }
+
+    // Compare the correctly rounded operations to MPFR.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..2000 {
+        let x = FP64::from_f64(f64::from_bits(lfsr.get64()));
+        let y = FP64::from_f64(f64::from_bits(lfsr.get64()));
+        let (rx, ry) = (x.to_rug(), y.to_rug());
+        assert!((x + y).matches_rug(&rug::Float::with_val(53, &rx + &ry)));
+        assert!((x - y).matches_rug(&rug::Float::with_val(53, &rx - &ry)));
+        assert!(x.sqrt().matches_rug(&rx.sqrt()));
+    }
+
+    // Values with a higher precision are rounded once.
+    let pi = rug::Float::with_val(300, rug::float::Constant::Pi);
+    let x = FP64::from_rug(&pi, rm);
+    assert_eq!(x.as_f64(), core::f64::consts::PI);
+    let up = FP64::from_rug(&pi, RoundingMode::Positive);
+    assert_eq!(up.as_f64(), core::f64::consts::PI.next_up());
+    let down = FP64::from_rug(&-pi, RoundingMode::Positive);
+    assert_eq!(down.as_f64(), -core::f64::consts::PI);
+
+    // Overflow and underflow.
+    let tiny = rug::Float::with_val(53, 1) >> 1075;
+    assert!(FP64::from_rug(&tiny, rm).is_zero());
+    let tiny = rug::Float::with_val(53, 3) >> 1076;
+    assert_eq!(FP64::from_rug(&tiny, rm).as_f64(), f64::from_bits(1));
+    let huge = rug::Float::with_val(53, 1) << 1024;
+    assert!(FP64::from_rug(&huge, rm).is_inf());
+    assert!(FP64::from_rug(&huge, RoundingMode::Zero).is_normal());
+}
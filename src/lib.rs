@@ -51,8 +51,12 @@
 //!    let fp = FP16::from_i64(15);
 //!    let m = fp.get_mantissa();
 //!
-//!    // Prints FP[+ E=+3 M=11110000000]
+//!    // Prints FP[+ E=   3 M = 11110000000]
 //!    fp.dump();
+//!
+//!    // Writes the same text to any core::fmt::Write, without std.
+//!    let mut s = String::new();
+//!    fp.write_dump(&mut s).unwrap();
 //!```
 //!
 //! Control the rounding mode for type conversion:
@@ -64,6 +68,7 @@
 //!```
 
 #![no_std]
+#![cfg_attr(feature = "nightly", feature(f16, f128))]
 
 #[cfg(feature = "std")]
 extern crate std;
@@ -81,13 +86,36 @@ macro_rules! new_float_type {
 
 mod arithmetic;
 mod bigint;
+mod cache;
 mod cast;
+pub mod consts;
+mod env;
 mod float;
+mod fp8;
 mod functions;
+mod hfp;
+mod interop;
+pub mod mx;
+pub mod quant;
+mod special;
+mod storage;
 mod string;
+#[cfg(feature = "proptest")]
+pub mod testing;
 mod utils;
+mod vax;
 
 pub use self::bigint::BigInt;
+pub use self::env::FpEnv;
 pub use self::float::Float;
-pub use self::float::RoundingMode;
-pub use self::float::{FP128, FP16, FP256, FP32, FP64};
+pub use self::float::TotalFloat;
+pub use self::float::TryFromFloatError;
+pub use self::float::{ArithError, OpStatus, ParseFloatError, RoundingMode};
+pub use self::float::{BF16, FP128, FP16, FP256, FP32, FP64, FP80, TF32};
+pub use self::fp8::{E4M3, E5M2};
+pub use self::hfp::{HfpExtended, HfpLong, HfpShort};
+#[cfg(feature = "rand")]
+pub use self::interop::UniformFloat;
+pub use self::storage::{Bf16Bits, Fp128Bits, Fp16Bits, Fp256Bits};
+pub use self::storage::{Fp32Bits, Fp64Bits, Fp80Bits};
+pub use self::vax::{VaxD, VaxF, VaxG};
@@ -0,0 +1,643 @@
+//! The low-precision element formats of the OCP Microscaling (MX)
+//! specification: FP6 (E2M3 and E3M2) and FP4 (E2M1). The formats have no
+//! infinities and no NaNs, and the largest exponent is used for normal
+//! numbers. Conversions to these formats saturate: values that overflow, and
+//! infinities, are converted to the largest finite number. NaN can't be
+//! represented, and is converted to zero.
+//!
+//! The numbers are stored as their encoding in the low bits of a byte, and
+//! [`pack_bits`] and [`unpack_bits`] store many of them in a byte array.
+//!```
+//!    use arpfloat::mx::{self, E2M1};
+//!    use arpfloat::{FP32, RoundingMode};
+//!    let rm = RoundingMode::NearestTiesToEven;
+//!    let vals = [0.5, 1.4, -3.2, 100.].map(FP32::from_f64);
+//!    let codes = vals.map(|v| E2M1::from_float(&v, rm).to_bits());
+//!    let mut packed = [0u8; 2];
+//!    mx::pack_bits(&codes, 4, &mut packed);
+//!    let mut unpacked = [0u8; 4];
+//!    mx::unpack_bits(&packed, 4, &mut unpacked);
+//!    let x: FP32 = E2M1::from_bits(unpacked[3]).to_float();
+//!    assert_eq!(x.as_f32(), 6.);
+//!```
+//!
+//! An [`MxBlock`] stores a block of elements that share an 8-bit power-of-two
+//! scale, like the MXFP8, MXFP6 and MXFP4 formats:
+//!```
+//!    use arpfloat::mx::MxFp4;
+//!    use arpfloat::{FP64, RoundingMode};
+//!    let vals: [FP64; 32] = core::array::from_fn(|i| FP64::from_u64(i as u64));
+//!    let block = MxFp4::quantize(&vals, RoundingMode::NearestTiesToEven);
+//!    let res: [FP64; 32] = block.dequantize();
+//!    assert_eq!(res[6].as_f64(), 6.);
+//!```
+
+use super::float::{Float, RoundingMode, FP32};
+use super::fp8::{E4M3, E5M2};
+
+/// Returns the exponent of the number, which is floor(log2(|val|)), for
+/// normal and subnormal numbers.
+fn get_exponent<const E: usize, const M: usize, const P: usize>(
+    val: &Float<E, M, P>,
+) -> i64 {
+    debug_assert!(val.is_normal());
+    let precision = Float::<E, M, P>::get_precision() as i64;
+    let msb = val.get_mantissa().msb_index() as i64;
+    val.get_exp() + msb - precision
+}
+
+/// Converts `val` to the encoding of a format with `exp_bits` exponent bits
+/// and `man_bits` mantissa bits, that has no infinities and no NaNs.
+fn encode<const E: usize, const M: usize, const P: usize>(
+    val: &Float<E, M, P>,
+    rm: RoundingMode,
+    exp_bits: usize,
+    man_bits: usize,
+) -> u8 {
+    let sign = (val.get_sign() as u8) << (exp_bits + man_bits);
+    let bias = (1 << (exp_bits - 1)) - 1;
+    let exp_min = 1 - bias;
+    let exp_max = (1 << exp_bits) - 1 - bias;
+    let max = ((1 << (exp_bits + man_bits)) - 1) as u8;
+    if val.is_nan() || val.is_zero() {
+        return sign;
+    } else if val.is_inf() {
+        return sign | max;
+    }
+
+    // Round the number to an integer 'n', in units of the last mantissa bit
+    // of the exponent 'k'. Numbers below the normal range use the smallest
+    // exponent, and are rounded to the subnormal range.
+    let mut k = get_exponent(val).max(exp_min);
+    let scaled = val.scale(man_bits as i64 - k, rm);
+    let mut n = scaled.to_i64(rm).unsigned_abs();
+    if n == 1 << (man_bits + 1) {
+        k += 1;
+        n >>= 1;
+    }
+    if k > exp_max {
+        return sign | max;
+    }
+
+    // Numbers with an integer bit are normal. This also encodes subnormals
+    // that were rounded up to the normal range.
+    if n >> man_bits == 0 {
+        return sign | n as u8;
+    }
+    let exp = ((k + bias) as u8) << man_bits;
+    sign | exp | (n as u8 & ((1 << man_bits) - 1))
+}
+
+/// Converts the encoding `bits` of a format with `exp_bits` exponent bits and
+/// `man_bits` mantissa bits, that has no infinities and no NaNs, to a Float.
+fn decode<const E: usize, const M: usize, const P: usize>(
+    bits: u8,
+    exp_bits: usize,
+    man_bits: usize,
+) -> Float<E, M, P> {
+    let sign = (bits >> (exp_bits + man_bits)) & 1 == 1;
+    let bias = (1 << (exp_bits - 1)) - 1;
+    let exp = ((bits >> man_bits) & ((1 << exp_bits) - 1)) as i64;
+    let mut n = (bits & ((1 << man_bits) - 1)) as u64;
+    let mut k = 1 - bias;
+    if exp != 0 {
+        n += 1 << man_bits;
+        k = exp - bias;
+    }
+
+    // The values of all of the formats are exact in FP32.
+    let rm = RoundingMode::NearestTiesToEven;
+    let mut x = FP32::from_u64(n).scale(k - man_bits as i64, rm);
+    x.set_sign(sign);
+    x.cast()
+}
+
+/// Defines a struct for a format with no infinities and no NaNs, that holds
+/// the encoding in the low bits of a byte.
+macro_rules! define_mx_format {
+    ($(#[$attr:meta])* $name:ident, $exp_bits:expr, $man_bits:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name(u8);
+
+        impl $name {
+            /// The number of bits in the encoding.
+            pub const BITS: usize = 1 + $exp_bits + $man_bits;
+            /// The largest finite number.
+            pub const MAX: Self = $name((1 << (Self::BITS - 1)) - 1);
+
+            /// Creates a number from its encoding, in the low bits of `bits`.
+            pub fn from_bits(bits: u8) -> Self {
+                debug_assert!(bits >> Self::BITS == 0, "Invalid encoding");
+                $name(bits)
+            }
+
+            /// Returns the encoding of the number, in the low bits.
+            pub fn to_bits(&self) -> u8 {
+                self.0
+            }
+
+            /// Converts `val` to this format, with the rounding mode `rm`.
+            /// Values that overflow, and infinities, are converted to the
+            /// largest finite number, and NaN is converted to zero.
+            pub fn from_float<const E: usize, const M: usize, const P: usize>(
+                val: &Float<E, M, P>,
+                rm: RoundingMode,
+            ) -> Self {
+                $name(encode(val, rm, $exp_bits, $man_bits))
+            }
+
+            /// Converts the number to a Float. The conversion is exact, unless
+            /// the target format is narrower than this format.
+            pub fn to_float<const E: usize, const M: usize, const P: usize>(
+                &self,
+            ) -> Float<E, M, P> {
+                decode(self.0, $exp_bits, $man_bits)
+            }
+        }
+
+        impl MxElement for $name {
+            const BITS: usize = $name::BITS;
+            const EMAX: i64 = (1 << ($exp_bits - 1)) as i64;
+
+            fn quantize<const E: usize, const M: usize, const P: usize>(
+                val: &Float<E, M, P>,
+                rm: RoundingMode,
+            ) -> Self {
+                Self::from_float(val, rm)
+            }
+
+            fn dequantize<const E: usize, const M: usize, const P: usize>(
+                &self,
+            ) -> Float<E, M, P> {
+                self.to_float()
+            }
+
+            fn from_code(code: u8) -> Self {
+                Self::from_bits(code)
+            }
+
+            fn code(&self) -> u8 {
+                self.to_bits()
+            }
+        }
+    };
+}
+
+/// The element formats of an [`MxBlock`].
+pub trait MxElement: Copy {
+    /// The number of bits in the encoding.
+    const BITS: usize;
+    /// The exponent of the largest normal number.
+    const EMAX: i64;
+
+    /// Converts `val` to the element format, with the rounding mode `rm`.
+    /// Values that overflow saturate to the largest finite number.
+    fn quantize<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self;
+
+    /// Converts the element to a Float.
+    fn dequantize<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P>;
+
+    /// Creates an element from its encoding.
+    fn from_code(code: u8) -> Self;
+
+    /// Returns the encoding of the element.
+    fn code(&self) -> u8;
+}
+
+define_mx_format!(
+    /// The FP6 format with 2 exponent bits and 3 mantissa bits. The largest
+    /// number is 7.5.
+    E2M3,
+    2,
+    3
+);
+
+define_mx_format!(
+    /// The FP6 format with 3 exponent bits and 2 mantissa bits. The largest
+    /// number is 28.
+    E3M2,
+    3,
+    2
+);
+
+define_mx_format!(
+    /// The FP4 format with 2 exponent bits and 1 mantissa bit. The largest
+    /// number is 6.
+    E2M1,
+    2,
+    1
+);
+
+impl MxElement for E4M3 {
+    const BITS: usize = 8;
+    const EMAX: i64 = 8;
+
+    fn quantize<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self {
+        Self::from_float(val, rm, true)
+    }
+
+    fn dequantize<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        self.to_float()
+    }
+
+    fn from_code(code: u8) -> Self {
+        Self::from_bits(code)
+    }
+
+    fn code(&self) -> u8 {
+        self.to_bits()
+    }
+}
+
+impl MxElement for E5M2 {
+    const BITS: usize = 8;
+    const EMAX: i64 = 15;
+
+    fn quantize<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self {
+        Self::from_float(val, rm, true)
+    }
+
+    fn dequantize<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        self.to_float()
+    }
+
+    fn from_code(code: u8) -> Self {
+        Self::from_bits(code)
+    }
+
+    fn code(&self) -> u8 {
+        self.to_bits()
+    }
+}
+
+/// The encoding of the shared scale that marks a block as NaN.
+const SCALE_NAN: u8 = 0xff;
+/// The bias of the shared scale, which is an E8M0 number: 2^(scale - 127).
+const SCALE_BIAS: i64 = 127;
+
+/// A block of N elements of the format T, that share a power-of-two scale,
+/// as defined by the OCP Microscaling (MX) specification. The scale is an
+/// 8-bit exponent, and the value of each element is 2^(scale - 127) times
+/// the value of its encoding.
+#[derive(Debug, Clone, Copy)]
+pub struct MxBlock<T: MxElement, const N: usize> {
+    scale: u8,
+    elements: [T; N],
+}
+
+/// MXFP8 block with E4M3 elements.
+pub type MxFp8E4M3 = MxBlock<E4M3, 32>;
+/// MXFP8 block with E5M2 elements.
+pub type MxFp8E5M2 = MxBlock<E5M2, 32>;
+/// MXFP6 block with E2M3 elements.
+pub type MxFp6E2M3 = MxBlock<E2M3, 32>;
+/// MXFP6 block with E3M2 elements.
+pub type MxFp6E3M2 = MxBlock<E3M2, 32>;
+/// MXFP4 block with E2M1 elements.
+pub type MxFp4 = MxBlock<E2M1, 32>;
+
+impl<T: MxElement, const N: usize> MxBlock<T, N> {
+    /// Creates a block from the encoding of the scale and of the elements.
+    pub fn new(scale: u8, elements: [T; N]) -> Self {
+        MxBlock { scale, elements }
+    }
+
+    /// Returns the encoding of the shared scale.
+    pub fn get_scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Returns the elements of the block.
+    pub fn get_elements(&self) -> &[T; N] {
+        &self.elements
+    }
+
+    /// Returns true if the block is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.scale == SCALE_NAN
+    }
+
+    /// Converts the values to a block. The shared scale is selected so that
+    /// the largest value is in the binade of the largest element, like in
+    /// Section 6.3 of the MX specification. The elements are rounded with the
+    /// rounding mode `rm`, and saturate. If one of the values is NaN or an
+    /// infinity then the block is NaN.
+    pub fn quantize<const E: usize, const M: usize, const P: usize>(
+        values: &[Float<E, M, P>; N],
+        rm: RoundingMode,
+    ) -> Self {
+        let zero = T::from_code(0);
+        if values.iter().any(|v| v.is_nan() || v.is_inf()) {
+            return MxBlock::new(SCALE_NAN, [zero; N]);
+        }
+
+        // Blocks of zeros get the smallest scale.
+        let max = values
+            .iter()
+            .filter(|v| v.is_normal())
+            .map(get_exponent)
+            .max();
+        let exp = match max {
+            Some(max) => (max - T::EMAX).clamp(-SCALE_BIAS, SCALE_BIAS),
+            None => -SCALE_BIAS,
+        };
+
+        let elements = values.map(|v| T::quantize(&v.scale(-exp, rm), rm));
+        MxBlock::new((exp + SCALE_BIAS) as u8, elements)
+    }
+
+    /// Converts the block to values of the format Float<E, M, P>.
+    pub fn dequantize<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> [Float<E, M, P>; N] {
+        if self.is_nan() {
+            return [Float::nan(false); N];
+        }
+        let exp = self.scale as i64 - SCALE_BIAS;
+        let rm = RoundingMode::NearestTiesToEven;
+        self.elements
+            .map(|e| e.dequantize::<E, M, P>().scale(exp, rm))
+    }
+
+    /// Packs the elements into `out`, with [`pack_bits`].
+    pub fn pack_elements(&self, out: &mut [u8]) {
+        pack_bits(&self.elements.map(|e| e.code()), T::BITS, out);
+    }
+
+    /// Creates a block from the encoding of the scale, and from the elements
+    /// that [`MxBlock::pack_elements`] packed.
+    pub fn from_packed(scale: u8, bytes: &[u8]) -> Self {
+        let mut codes = [0; N];
+        unpack_bits(bytes, T::BITS, &mut codes);
+        MxBlock::new(scale, codes.map(T::from_code))
+    }
+}
+
+/// Packs the low `width` bits of each code in `codes` into `out`, as a stream
+/// of bits that starts at the lowest bit of the first byte. Two FP4 numbers
+/// are stored in each byte, and four FP6 numbers in three bytes.
+pub fn pack_bits(codes: &[u8], width: usize, out: &mut [u8]) {
+    assert!(width > 0 && width <= 8, "Invalid width");
+    assert!(out.len() * 8 >= codes.len() * width, "Output is too small");
+    out.fill(0);
+    for (i, code) in codes.iter().enumerate() {
+        let code = (*code as u16) & ((1 << width) - 1);
+        let bit = i * width;
+        let word = code << (bit % 8);
+        out[bit / 8] |= word as u8;
+        if bit % 8 + width > 8 {
+            out[bit / 8 + 1] |= (word >> 8) as u8;
+        }
+    }
+}
+
+/// Unpacks codes of `width` bits from the stream of bits in `bytes`, that
+/// [`pack_bits`] created, and stores them in `out`.
+pub fn unpack_bits(bytes: &[u8], width: usize, out: &mut [u8]) {
+    assert!(width > 0 && width <= 8, "Invalid width");
+    assert!(bytes.len() * 8 >= out.len() * width, "Input is too small");
+    for (i, code) in out.iter_mut().enumerate() {
+        let bit = i * width;
+        let mut word = bytes[bit / 8] as u16;
+        if bit % 8 + width > 8 {
+            word |= (bytes[bit / 8 + 1] as u16) << 8;
+        }
+        *code = ((word >> (bit % 8)) & ((1 << width) - 1)) as u8;
+    }
+}
+
+#[test]
+fn test_mx_decode() {
+    // Known values of the encodings.
+    let e2m3 = |bits: u8| E2M3::from_bits(bits).to_float::<8, 23, 1>();
+    let e3m2 = |bits: u8| E3M2::from_bits(bits).to_float::<8, 23, 1>();
+    let e2m1 = |bits: u8| E2M1::from_bits(bits).to_float::<8, 23, 1>();
+    assert_eq!(e2m3(0x1f).as_f32(), 7.5);
+    assert_eq!(e2m3(0x08).as_f32(), 1.);
+    assert_eq!(e2m3(0x01).as_f32(), 0.125);
+    assert_eq!(e2m3(0x3f).as_f32(), -7.5);
+    assert_eq!(e3m2(0x1f).as_f32(), 28.);
+    assert_eq!(e3m2(0x0c).as_f32(), 1.);
+    assert_eq!(e3m2(0x01).as_f32(), 0.0625);
+    assert_eq!(e2m1(0x7).as_f32(), 6.);
+    assert_eq!(e2m1(0x2).as_f32(), 1.);
+    assert_eq!(e2m1(0x1).as_f32(), 0.5);
+    assert_eq!(e2m1(0xd).as_f32(), -3.);
+    assert!(e2m1(0x8).is_zero() && e2m1(0x8).is_negative());
+    assert_eq!(E2M3::MAX.to_bits(), 0x1f);
+    assert_eq!(E2M1::MAX.to_bits(), 0x7);
+
+    // Every encoding converts back to itself.
+    let rm = RoundingMode::NearestTiesToEven;
+    for bits in 0..64 {
+        let x: FP32 = E2M3::from_bits(bits).to_float();
+        assert_eq!(E2M3::from_float(&x, rm).to_bits(), bits);
+        let x: FP32 = E3M2::from_bits(bits).to_float();
+        assert_eq!(E3M2::from_float(&x, rm).to_bits(), bits);
+    }
+    for bits in 0..16 {
+        let x: FP32 = E2M1::from_bits(bits).to_float();
+        assert_eq!(E2M1::from_float(&x, rm).to_bits(), bits);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_mx_rounding() {
+    use super::utils::Lfsr;
+    use std::vec::Vec;
+    use RoundingMode::*;
+
+    // Find the nearest encodings by searching all of the values. Numbers
+    // that overflow saturate.
+    let round = |table: &Vec<(f32, u8)>, f: f32, rm: RoundingMode| {
+        let below = table.iter().filter(|v| v.0 <= f);
+        let above = table.iter().filter(|v| v.0 >= f);
+        let lo = below.max_by(|a, b| a.0.total_cmp(&b.0));
+        let hi = above.min_by(|a, b| a.0.total_cmp(&b.0));
+        let (lo, hi) = match (lo, hi) {
+            (Some(lo), Some(hi)) => (lo, hi),
+            (Some(lo), None) => return lo.1,
+            (None, Some(hi)) => return hi.1,
+            _ => panic!(),
+        };
+        match rm {
+            Negative => lo.1,
+            Positive => hi.1,
+            Zero => (if f > 0. { lo } else { hi }).1,
+            _ => {
+                let (d0, d1) = (f - lo.0, hi.0 - f);
+                let even = if lo.1 & 1 == 0 { lo.1 } else { hi.1 };
+                if d0 < d1 {
+                    lo.1
+                } else if d1 < d0 {
+                    hi.1
+                } else {
+                    even
+                }
+            }
+        }
+    };
+    let e2m3: Vec<(f32, u8)> = (0..64)
+        .map(|b| (E2M3::from_bits(b).to_float::<8, 23, 1>().as_f32(), b))
+        .collect();
+    let e3m2: Vec<(f32, u8)> = (0..64)
+        .map(|b| (E3M2::from_bits(b).to_float::<8, 23, 1>().as_f32(), b))
+        .collect();
+    let e2m1: Vec<(f32, u8)> = (0..16)
+        .map(|b| (E2M1::from_bits(b).to_float::<8, 23, 1>().as_f32(), b))
+        .collect();
+
+    let mut lfsr = Lfsr::new();
+    for i in 0..3000 {
+        let scale = [1. / 64., 1. / 4., 1., 8.][i % 4];
+        let sign = if i % 2 == 0 { 1. } else { -1. };
+        let f = (lfsr.get() % 4096) as f32 / 1024. * scale * sign;
+        if f == 0. {
+            continue;
+        }
+        let x = FP32::from_f32(f);
+        for rm in [NearestTiesToEven, Zero, Positive, Negative] {
+            let r = E2M3::from_float(&x, rm).to_bits();
+            assert_eq!(r, round(&e2m3, f, rm));
+            let r = E3M2::from_float(&x, rm).to_bits();
+            assert_eq!(r, round(&e3m2, f, rm));
+            let r = E2M1::from_float(&x, rm).to_bits();
+            assert_eq!(r, round(&e2m1, f, rm));
+        }
+    }
+
+    // Infinities saturate, and NaN is converted to zero.
+    let rm = NearestTiesToEven;
+    assert_eq!(E2M1::from_float(&FP32::inf(true), rm).to_bits(), 0xf);
+    assert_eq!(E3M2::from_float(&FP32::inf(false), rm).to_bits(), 0x1f);
+    assert_eq!(E2M3::from_float(&FP32::nan(false), rm).to_bits(), 0);
+}
+
+#[test]
+fn test_pack_bits() {
+    let codes = [0x1, 0x2, 0x3, 0x4, 0x5];
+    let mut packed = [0xffu8; 3];
+    pack_bits(&codes, 4, &mut packed);
+    assert_eq!(packed, [0x21, 0x43, 0x05]);
+
+    // Four FP6 numbers fit in three bytes.
+    let codes = [0x3f, 0x00, 0x15, 0x2a];
+    let mut packed = [0u8; 3];
+    pack_bits(&codes, 6, &mut packed);
+    assert_eq!(packed, [0x3f, 0x50, 0xa9]);
+    let mut unpacked = [0u8; 4];
+    unpack_bits(&packed, 6, &mut unpacked);
+    assert_eq!(unpacked, codes);
+
+    // All of the widths.
+    let codes: [u8; 29] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+    for width in 1..=8 {
+        let mask = ((1u16 << width) - 1) as u8;
+        let mut packed = [0u8; 29];
+        pack_bits(&codes, width, &mut packed);
+        let mut unpacked = [0u8; 29];
+        unpack_bits(&packed, width, &mut unpacked);
+        for (a, b) in codes.iter().zip(unpacked.iter()) {
+            assert_eq!(a & mask, *b);
+        }
+    }
+}
+
+#[test]
+fn test_mx_block() {
+    use crate::FP64;
+    let rm = RoundingMode::NearestTiesToEven;
+    let vals: [FP64; 32] = core::array::from_fn(|i| FP64::from_u64(i as u64));
+
+    // The largest value is 31, and its exponent is 4. The largest exponent of
+    // E2M1 is 2, so the scale is 2^2, and 31/4 saturates to 6.
+    let block = MxFp4::quantize(&vals, rm);
+    assert_eq!(block.get_scale(), 127 + 2);
+    let res: [FP64; 32] = block.dequantize();
+    assert_eq!(res[31].as_f64(), 24.);
+    assert_eq!(res[5].as_f64(), 4.);
+    assert_eq!(res[1].as_f64(), 0.);
+    assert_eq!(block.get_elements()[31].to_bits(), 0x7);
+
+    // The elements of E4M3 have more precision. The scale is 2^-4, and 31*16
+    // is above 448, so the largest value saturates, like in the MX spec.
+    let block = MxFp8E4M3::quantize(&vals, rm);
+    assert_eq!(block.get_scale(), 127 - 4);
+    let res: [FP64; 32] = block.dequantize();
+    for (i, r) in res.iter().enumerate().take(17) {
+        assert_eq!(r.as_f64(), i as f64);
+    }
+    assert_eq!(res[27].as_f64(), 28.);
+    assert_eq!(res[31].as_f64(), 28.);
+
+    // The scale is clamped, and the elements round to subnormals.
+    let tiny = FP64::from_f64(1e-60);
+    let vals = [tiny, tiny.neg(), FP64::zero(false), tiny.scale(-4, rm)];
+    let block = MxBlock::<E3M2, 4>::quantize(&vals, rm);
+    assert_eq!(block.get_scale(), 0);
+    let res: [FP64; 4] = block.dequantize();
+    assert!(res.iter().all(|x| x.is_zero()));
+    let block = MxBlock::<E5M2, 4>::quantize(&vals, rm);
+    assert_eq!(block.get_scale(), 0);
+
+    // Blocks of zeros, and NaN.
+    let zeros = [FP64::zero(false); 32];
+    let block = MxFp6E2M3::quantize(&zeros, rm);
+    assert_eq!(block.get_scale(), 0);
+    assert!(block.dequantize::<11, 52, 2>().iter().all(|x| x.is_zero()));
+    let mut vals = zeros;
+    vals[7] = FP64::inf(true);
+    let block = MxFp6E3M2::quantize(&vals, rm);
+    assert!(block.is_nan());
+    assert!(block.dequantize::<11, 52, 2>().iter().all(|x| x.is_nan()));
+}
+
+#[test]
+fn test_mx_block_accuracy() {
+    use super::utils::Lfsr;
+    use crate::FP64;
+    let rm = RoundingMode::NearestTiesToEven;
+    let mut lfsr = Lfsr::new();
+    for _ in 0..20 {
+        let vals: [FP64; 32] = core::array::from_fn(|_| {
+            let v = (lfsr.get() % 2000) as f64 - 1000.;
+            FP64::from_f64(v / 64.)
+        });
+        let max = vals.iter().map(|v| v.as_f64().abs()).fold(0., f64::max);
+
+        // Each element has the precision of its format, relative to the
+        // largest value of the block.
+        let block = MxFp8E5M2::quantize(&vals, rm);
+        let res: [FP64; 32] = block.dequantize();
+        for (v, r) in vals.iter().zip(res.iter()) {
+            let err = (v.as_f64() - r.as_f64()).abs();
+            assert!(err <= v.as_f64().abs() / 8. + max / 65536.);
+        }
+
+        // Packing and unpacking keeps the block.
+        let block = MxFp6E3M2::quantize(&vals, rm);
+        let mut packed = [0u8; 24];
+        block.pack_elements(&mut packed);
+        let copy = MxFp6E3M2::from_packed(block.get_scale(), &packed);
+        let res: [FP64; 32] = block.dequantize();
+        let res_copy: [FP64; 32] = copy.dequantize();
+        assert_eq!(res, res_copy);
+    }
+}
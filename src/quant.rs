@@ -0,0 +1,257 @@
+//! Conversion of buffers of numbers to and from narrow formats, such as FP16,
+//! BF16, FP8 and the MX element formats. The numbers can be divided by a
+//! per-tensor scale before they are rounded to the narrow format, and are
+//! multiplied by the scale when they are converted back.
+//!```
+//!    use arpfloat::quant;
+//!    use arpfloat::{RoundingMode, E4M3};
+//!    let rm = RoundingMode::NearestTiesToEven;
+//!    let vals = [0.5, -1000., 3000.];
+//!    let mut codes = [E4M3::from_bits(0); 3];
+//!    quant::quantize_f32(&vals, Some(8.), rm, &mut codes);
+//!    let mut res = [0f32; 3];
+//!    quant::dequantize_f32(&codes, Some(8.), &mut res);
+//!    assert_eq!(res, [0.5, -1024., 3072.]);
+//!```
+
+use super::float::{Float, RoundingMode, FP128, FP32};
+use super::mx::MxElement;
+
+/// The narrow formats that buffers are converted to.
+pub trait Element: Copy {
+    /// Converts `val` to the format, with the rounding mode `rm`.
+    fn quantize<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self;
+
+    /// Converts the number to a Float.
+    fn dequantize<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P>;
+}
+
+/// The Float formats follow the IEEE754 rules, and overflow to infinity.
+impl<const E: usize, const M: usize, const P: usize> Element
+    for Float<E, M, P>
+{
+    fn quantize<const E2: usize, const M2: usize, const P2: usize>(
+        val: &Float<E2, M2, P2>,
+        rm: RoundingMode,
+    ) -> Self {
+        val.cast_with_rm(rm)
+    }
+
+    fn dequantize<const E2: usize, const M2: usize, const P2: usize>(
+        &self,
+    ) -> Float<E2, M2, P2> {
+        self.cast()
+    }
+}
+
+/// The FP8, FP6 and FP4 formats saturate, and convert NaN like
+/// [`MxElement::quantize`].
+impl<T: MxElement> Element for T {
+    fn quantize<const E: usize, const M: usize, const P: usize>(
+        val: &Float<E, M, P>,
+        rm: RoundingMode,
+    ) -> Self {
+        MxElement::quantize(val, rm)
+    }
+
+    fn dequantize<const E: usize, const M: usize, const P: usize>(
+        &self,
+    ) -> Float<E, M, P> {
+        MxElement::dequantize(self)
+    }
+}
+
+/// Divides `val` by `scale`, and converts the quotient to the format T, with
+/// the rounding mode `rm`.
+fn quantize_one<T: Element, const E: usize, const M: usize, const P: usize>(
+    val: &Float<E, M, P>,
+    scale: Option<FP128>,
+    rm: RoundingMode,
+) -> T {
+    match scale {
+        None => T::quantize(val, rm),
+        Some(scale) => {
+            let rm_div = RoundingMode::NearestTiesToEven;
+            let q = FP128::div_with_rm(val.cast(), scale, rm_div);
+            T::quantize(&q, rm)
+        }
+    }
+}
+
+/// Converts `val` to Float<E, M, P>, and multiplies it by `scale`.
+fn dequantize_one<
+    T: Element,
+    const E: usize,
+    const M: usize,
+    const P: usize,
+>(
+    val: &T,
+    scale: Option<Float<E, M, P>>,
+) -> Float<E, M, P> {
+    let x = val.dequantize();
+    match scale {
+        None => x,
+        Some(scale) => {
+            let rm = RoundingMode::NearestTiesToEven;
+            Float::mul_with_rm(x, scale, rm)
+        }
+    }
+}
+
+/// Converts the numbers in `input` to the format T, and stores them in `out`.
+/// If `scale` is given then each number is divided by the scale before it is
+/// rounded with the rounding mode `rm`. The quotient is computed in FP128, so
+/// the result is correctly rounded for inputs up to the precision of FP64.
+pub fn quantize<T: Element, const E: usize, const M: usize, const P: usize>(
+    input: &[Float<E, M, P>],
+    scale: Option<&Float<E, M, P>>,
+    rm: RoundingMode,
+    out: &mut [T],
+) {
+    assert_eq!(input.len(), out.len(), "Input and output lengths differ");
+    let scale = scale.map(|s| s.cast());
+    for (x, o) in input.iter().zip(out.iter_mut()) {
+        *o = quantize_one(x, scale, rm);
+    }
+}
+
+/// Converts the numbers in `input` to Float<E, M, P>, and stores them in `out`.
+/// If `scale` is given then each number is multiplied by the scale, and the
+/// product is rounded to nearest, ties to even.
+pub fn dequantize<
+    T: Element,
+    const E: usize,
+    const M: usize,
+    const P: usize,
+>(
+    input: &[T],
+    scale: Option<&Float<E, M, P>>,
+    out: &mut [Float<E, M, P>],
+) {
+    assert_eq!(input.len(), out.len(), "Input and output lengths differ");
+    for (x, o) in input.iter().zip(out.iter_mut()) {
+        *o = dequantize_one(x, scale.copied());
+    }
+}
+
+/// Converts the f32 numbers in `input` to the format T, like [`quantize`].
+pub fn quantize_f32<T: Element>(
+    input: &[f32],
+    scale: Option<f32>,
+    rm: RoundingMode,
+    out: &mut [T],
+) {
+    assert_eq!(input.len(), out.len(), "Input and output lengths differ");
+    let scale = scale.map(FP128::from_f32);
+    for (x, o) in input.iter().zip(out.iter_mut()) {
+        *o = quantize_one(&FP32::from_f32(*x), scale, rm);
+    }
+}
+
+/// Converts the numbers in `input` to f32, like [`dequantize`].
+pub fn dequantize_f32<T: Element>(
+    input: &[T],
+    scale: Option<f32>,
+    out: &mut [f32],
+) {
+    assert_eq!(input.len(), out.len(), "Input and output lengths differ");
+    let scale = scale.map(FP32::from_f32);
+    for (x, o) in input.iter().zip(out.iter_mut()) {
+        *o = dequantize_one(x, scale).as_f32();
+    }
+}
+
+#[test]
+fn test_quantize_formats() {
+    use super::float::{BF16, FP16, FP64};
+    use super::fp8::E5M2;
+    use super::mx::E2M1;
+    let rm = RoundingMode::NearestTiesToEven;
+    let vals = [1.0, -2.5, 1e-9, 70000., 0.1, f32::INFINITY, f32::NAN];
+
+    let mut res = [FP16::zero(false); 7];
+    quantize_f32(&vals, None, rm, &mut res);
+    for (x, y) in vals.iter().zip(res.iter()) {
+        let expected: FP16 = FP32::from_f32(*x).cast();
+        assert!(y.is_nan() && x.is_nan() || *y == expected);
+    }
+    assert!(res[3].is_inf());
+
+    let mut res = [BF16::zero(false); 7];
+    quantize_f32(&vals, None, RoundingMode::Zero, &mut res);
+    assert_eq!(res[4].as_f64(), 0.099609375);
+
+    // The FP8 and FP4 formats saturate when they overflow.
+    let mut res = [E5M2::from_bits(0); 7];
+    quantize_f32(&vals, None, rm, &mut res);
+    let mut back = [0f32; 7];
+    dequantize_f32(&res, None, &mut back);
+    assert_eq!(&back[..5], &[1.0, -2.5, 0., 57344., 0.09375]);
+    assert!(back[5].is_infinite());
+    assert!(back[6].is_nan());
+
+    let vals = vals.map(|x| FP64::from_f64(x as f64));
+    let mut res = [E2M1::from_bits(0); 7];
+    quantize(&vals, None, rm, &mut res);
+    let mut back = [FP64::zero(false); 7];
+    dequantize(&res, None, &mut back);
+    let back = back.map(|x| x.as_f64());
+    assert_eq!(back, [1.0, -2., 0., 6., 0., 6., 0.]);
+}
+
+#[test]
+fn test_quantize_with_scale() {
+    use super::float::{FP16, FP64};
+    use super::fp8::E4M3;
+    use super::utils::Lfsr;
+    let rm = RoundingMode::NearestTiesToEven;
+
+    // Power-of-two scales are exact.
+    let vals = [1.0, -3.0, 1e6, 1e-6];
+    let mut res = [FP16::zero(false); 4];
+    quantize_f32(&vals, Some(1024.), rm, &mut res);
+    let mut back = [0f32; 4];
+    dequantize_f32(&res, Some(1024.), &mut back);
+    assert_eq!(back, [1.0, -3.0, 999936., 0.]);
+
+    // The quotient is rounded once, to the narrow format.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..500 {
+        let x = f32::from_bits(lfsr.get64() as u32 & 0x47ff_ffff);
+        let s = f32::from_bits(lfsr.get64() as u32 & 0x3fff_ffff) + 1.;
+        for rm in [RoundingMode::NearestTiesToEven, RoundingMode::Positive] {
+            let mut res = [FP16::zero(false)];
+            quantize_f32(&[x], Some(s), rm, &mut res);
+            let a = FP64::from_f32(x);
+            let b = FP64::from_f32(s);
+            let q: FP16 = FP64::div_with_rm(a, b, rm).cast_with_rm(rm);
+            assert_eq!(res[0], q);
+        }
+    }
+
+    // The generic functions match the f32 functions.
+    let vals = [0.3, -17.25, 400.];
+    let scale = FP32::from_f32(0.7);
+    let mut res = [E4M3::from_bits(0); 3];
+    quantize(&vals.map(FP32::from_f32), Some(&scale), rm, &mut res);
+    let mut expected = [E4M3::from_bits(0); 3];
+    quantize_f32(&vals, Some(0.7), rm, &mut expected);
+    assert_eq!(res.map(|x| x.to_bits()), expected.map(|x| x.to_bits()));
+    let mut back = [FP32::zero(false); 3];
+    dequantize(&res, Some(&scale), &mut back);
+    let mut expected = [0f32; 3];
+    dequantize_f32(&res, Some(0.7), &mut expected);
+    assert_eq!(back.map(|x| x.as_f32()), expected);
+}
+
+#[test]
+#[should_panic]
+fn test_quantize_length_panics() {
+    let mut res = [FP32::zero(false); 2];
+    quantize_f32(&[1., 2., 3.], None, RoundingMode::Zero, &mut res);
+}
@@ -0,0 +1,904 @@
+//! Special functions: the gamma function and its logarithm, the Bessel
+//! functions of integer order, the error function and its inverse, and the
+//! normal distribution. The series and the approximations of these functions
+//! lose bits to cancellation, so they are evaluated in a wider format when
+//! one is available, and rounded to the format of the argument.
+
+use super::float::{Float, RoundingMode};
+use super::functions::WideFloat;
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Returns the parameter 'a' of the Spouge approximation, that is needed
+    /// for the precision of this format. The relative error of the
+    /// approximation is bounded by (2*pi)^-(a + 1/2), which is about 2.65
+    /// bits per increment of 'a'.
+    fn spouge_parameter() -> u64 {
+        (MANTISSA as u64 + 1) * 3 / 8 + 3
+    }
+
+    /// Computes ln(gamma(z + 1)) for z > -1/2, using the Spouge approximation
+    /// with the parameter 'a':
+    /// gamma(z + 1) = (z + a)^(z + 1/2) * e^-(z + a) * (c0 + sum(ck / (z + k)))
+    /// The coefficients alternate in sign and cancel, so this needs to be
+    /// evaluated with more precision than the result.
+    fn ln_gamma_spouge(z: Self, a: u64) -> Self {
+        let one = Self::one(false);
+        let a_f = Self::from_u64(a);
+
+        // c0 = sqrt(2 * pi).
+        let mut sum = Self::pi().scale(1, RoundingMode::Zero).sqrt();
+
+        // ck = (-1)^(k-1) / (k-1)! * (a-k)^(k-1/2) * e^(a-k)
+        let e_inv = one.neg().exp();
+        let mut e_pow = (a_f - one).exp();
+        let mut fact = one;
+        for k in 1..a {
+            let t = a_f - Self::from_u64(k);
+            let mut ck = t.powi(k as i64) / t.sqrt() * e_pow / fact;
+            if k % 2 == 0 {
+                ck = ck.neg();
+            }
+            sum = sum + ck / (z + Self::from_u64(k));
+            e_pow = e_pow * e_inv;
+            fact = fact * Self::from_u64(k);
+        }
+
+        let half = one.scale(-1, RoundingMode::Zero);
+        let za = z + a_f;
+        (z + half) * za.ln() - za + sum.ln()
+    }
+
+    /// Computes ln(gamma(x)) for x >= 1/2, with the Spouge parameter `a`.
+    fn ln_gamma_positive(x: Self, a: u64) -> Self {
+        Self::ln_gamma_spouge(x - Self::one(false), a)
+    }
+
+    /// Computes gamma(x) for a finite x that is not a pole, with the Spouge
+    /// parameter `a`, which is chosen for the precision of the result, and
+    /// not of this format, which can be wider.
+    fn gamma_finite(x: Self, a: u64) -> Self {
+        let one = Self::one(false);
+        let half = one.scale(-1, RoundingMode::Zero);
+        if x >= half {
+            return Self::ln_gamma_positive(x, a).exp();
+        }
+        let g = Self::ln_gamma_positive(one - x, a).exp();
+        Self::pi() / (Self::sin_pi(x) * g)
+    }
+
+    /// Computes ln|gamma(x)| for a finite x that is not a pole, with the
+    /// Spouge parameter `a`, like `gamma_finite`.
+    fn ln_gamma_finite(x: Self, a: u64) -> Self {
+        let one = Self::one(false);
+        let half = one.scale(-1, RoundingMode::Zero);
+        if x >= half {
+            return Self::ln_gamma_positive(x, a);
+        }
+        // ln|gamma(x)| = ln(pi / |sin(pi * x)|) - ln(gamma(1 - x)).
+        let s = Self::sin_pi(x).abs();
+        (Self::pi() / s).ln() - Self::ln_gamma_positive(one - x, a)
+    }
+
+    /// Computes sin(pi * x), by reducing x to the range [-1/2, 1/2] first,
+    /// to avoid rounding pi * x for large values of x.
+    fn sin_pi(x: Self) -> Self {
+        let n = x.round();
+        let r = (Self::pi() * (x - n)).sin();
+        if n.is_odd_integer() {
+            r.neg()
+        } else {
+            r
+        }
+    }
+
+    /// Returns the gamma function of the number. Values below 1/2 are
+    /// computed using the reflection formula:
+    /// gamma(x) = pi / (sin(pi * x) * gamma(1 - x)).
+    pub fn gamma(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_zero() {
+            return Self::inf(self.is_negative());
+        }
+        if self.is_inf() {
+            return if self.is_negative() {
+                Self::nan(false)
+            } else {
+                *self
+            };
+        }
+        // The poles of the gamma function.
+        if self.is_negative() && self.is_integer() {
+            return Self::nan(false);
+        }
+
+        let a = Self::spouge_parameter();
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return WideFloat::gamma_finite(x, a).cast();
+        }
+        Self::gamma_finite(*self, a)
+    }
+
+    /// Returns the natural logarithm of the absolute value of the gamma
+    /// function of the number.
+    pub fn ln_gamma(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_inf() || self.is_zero() {
+            return Self::inf(false);
+        }
+        // ln(gamma(x)) is +inf at the poles, and exactly zero at 1 and 2.
+        if self.is_negative() && self.is_integer() {
+            return Self::inf(false);
+        }
+        let one = Self::one(false);
+        if *self == one || *self == Self::from_u64(2) {
+            return Self::zero(false);
+        }
+
+        let a = Self::spouge_parameter();
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return WideFloat::ln_gamma_finite(x, a).cast();
+        }
+        Self::ln_gamma_finite(*self, a)
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Computes the power series of the Bessel function of the first kind:
+    /// J_n(x) = sum((-1)^k * (x/2)^(2k+n) / (k! * (k+n)!)).
+    /// The terms grow before they converge, so this is only accurate for
+    /// small x (or with extra precision).
+    fn bessel_j_series(x: Self, n: u64) -> Self {
+        let half_x = x.scale(-1, RoundingMode::Zero);
+        let q = half_x.sqr().neg();
+        // The first term is (x/2)^n / n!, and n! is 1 for n <= 1.
+        debug_assert!(n <= 1, "Only order 0 and 1 are supported");
+        let mut term = half_x.powi(n as i64);
+        let mut sum = term;
+        let mut k = 1;
+        loop {
+            term = term * q / Self::from_u64(k * (k + n));
+            let prev = sum;
+            sum = sum + term;
+            if sum == prev {
+                return sum;
+            }
+            k += 1;
+        }
+    }
+
+    /// Computes the power series of the Bessel function of the second kind,
+    /// using the value `j` of J_n(x):
+    /// Y0(x) = 2/pi * ((ln(x/2) + gamma) * J0(x) +
+    ///                 sum((-1)^(k+1) * H_k * (x^2/4)^k / (k!)^2))
+    /// Y1(x) = 2/pi * ln(x/2) * J1(x) - 2/(pi * x) -
+    ///         1/pi * sum((-1)^k * (psi(k+1) + psi(k+2)) *
+    ///                    (x/2)^(2k+1) / (k! * (k+1)!))
+    /// where psi(k+1) = H_k - gamma and H_k is the k-th harmonic number.
+    fn bessel_y_series(x: Self, n: u64, j: Self) -> Self {
+        let one = Self::one(false);
+        let pi = Self::pi();
+        let euler = Self::euler_gamma();
+        let half_x = x.scale(-1, RoundingMode::Zero);
+        let q = half_x.sqr().neg();
+        let log_term = half_x.ln();
+
+        if n == 0 {
+            let mut term = one;
+            let mut harmonic = Self::zero(false);
+            let mut sum = Self::zero(false);
+            let mut k = 1;
+            loop {
+                let k_f = Self::from_u64(k);
+                term = term * q / k_f.sqr();
+                harmonic = harmonic + one / k_f;
+                let prev = sum;
+                sum = sum - term * harmonic;
+                if sum == prev {
+                    break;
+                }
+                k += 1;
+            }
+            let r = (log_term + euler) * j + sum;
+            return r.scale(1, RoundingMode::Zero) / pi;
+        }
+
+        debug_assert!(n == 1, "Only order 0 and 1 are supported");
+        let mut term = half_x;
+        let mut harmonic_next = one; // H_(k+1)
+        let mut sum = term * (one - euler.scale(1, RoundingMode::Zero));
+        let mut k = 1;
+        loop {
+            let k_f = Self::from_u64(k);
+            term = term * q / Self::from_u64(k * (k + 1));
+            let harmonic = harmonic_next;
+            harmonic_next = harmonic_next + one / (k_f + one);
+            let psi =
+                harmonic + harmonic_next - euler.scale(1, RoundingMode::Zero);
+            let prev = sum;
+            sum = sum + term * psi;
+            if sum == prev {
+                break;
+            }
+            k += 1;
+        }
+        let two = Self::from_u64(2);
+        (two * log_term * j - two / x - sum) / pi
+    }
+
+    /// Computes the Hankel asymptotic expansion of the Bessel functions of
+    /// order n for large x. Returns the pair (J_n(x), Y_n(x)).
+    /// J_n(x) = sqrt(2/(pi*x)) * (P * cos(chi) - Q * sin(chi))
+    /// Y_n(x) = sqrt(2/(pi*x)) * (P * sin(chi) + Q * cos(chi))
+    /// where chi = x - (n/2 + 1/4) * pi. The series diverges, so the sum stops
+    /// at the smallest term, which is about e^(-2x).
+    fn bessel_asymptotic(x: Self, n: u64) -> (Self, Self) {
+        let mu = Self::from_u64(4 * n * n);
+        let eight_x = x.scale(3, RoundingMode::Zero);
+        let mut p = Self::one(false);
+        let mut q = Self::zero(false);
+        let mut term = Self::one(false);
+        let mut k = 1;
+        loop {
+            // a_k / x^k = a_(k-1) / x^(k-1) * (mu - (2k-1)^2) / (8 * k * x).
+            let odd = Self::from_u64((2 * k - 1) * (2 * k - 1));
+            let next = term * (mu - odd) / (eight_x * Self::from_u64(k));
+            if next.abs() >= term.abs() || next.is_zero() {
+                break;
+            }
+            term = next;
+            // The signs alternate in pairs: +P, +Q, -P, -Q, ...
+            let t = if k % 4 >= 2 { term.neg() } else { term };
+            let prev = (p, q);
+            if k % 2 == 0 {
+                p = p + t;
+            } else {
+                q = q + t;
+            }
+            if (p, q) == prev {
+                break;
+            }
+            k += 1;
+        }
+
+        let pi = Self::pi();
+        let phase =
+            Self::from_u64(2 * n + 1) * pi.scale(-2, RoundingMode::Zero);
+        let chi = x - phase;
+        let (s, c) = (chi.sin(), chi.cos());
+        let amp = (Self::from_u64(2) / (pi * x)).sqrt();
+        (amp * (p * c - q * s), amp * (p * s + q * c))
+    }
+
+    /// Computes the Bessel function of order `n` (0 or 1), of the first kind
+    /// or the `second_kind`, for positive normal x.
+    fn bessel_positive(
+        x: Self,
+        n: u64,
+        second_kind: bool,
+        limit: Self,
+    ) -> Self {
+        if x >= limit {
+            let (j, y) = Self::bessel_asymptotic(x, n);
+            return if second_kind { y } else { j };
+        }
+        let j = Self::bessel_j_series(x, n);
+        if second_kind {
+            Self::bessel_y_series(x, n, j)
+        } else {
+            j
+        }
+    }
+
+    /// Computes the Bessel function of order `n` (0 or 1), of the first kind
+    /// or the `second_kind`. The power series is used for small values, and
+    /// the asymptotic expansion is used for large values. The series cancels,
+    /// so it is evaluated with extra precision.
+    fn bessel(&self, n: u64, second_kind: bool) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if second_kind {
+            if self.is_zero() {
+                return Self::inf(true);
+            }
+            if self.is_negative() {
+                return Self::nan(false);
+            }
+        }
+        if self.is_inf() {
+            return Self::zero(false);
+        }
+        if self.is_zero() {
+            return if n == 0 { Self::one(false) } else { *self };
+        }
+
+        // J0 is even and J1 is odd.
+        let x = self.abs();
+        let odd = n == 1 && self.is_negative();
+
+        // The asymptotic expansion is accurate to about 2.9 bits per unit of
+        // x, and the series loses about 1.44 bits per unit of x.
+        let limit = (MANTISSA as u64 + 1) * 2 / 5 + 8;
+        let r = if Self::has_wide_float() {
+            let x: WideFloat = x.cast();
+            let limit = WideFloat::from_u64(limit);
+            WideFloat::bessel_positive(x, n, second_kind, limit).cast()
+        } else {
+            let limit = Self::from_u64(limit);
+            Self::bessel_positive(x, n, second_kind, limit)
+        };
+        if odd {
+            r.neg()
+        } else {
+            r
+        }
+    }
+
+    /// Returns the Bessel function of the first kind of order zero.
+    pub fn j0(&self) -> Self {
+        self.bessel(0, false)
+    }
+
+    /// Returns the Bessel function of the first kind of order one.
+    pub fn j1(&self) -> Self {
+        self.bessel(1, false)
+    }
+
+    /// Returns the Bessel function of the second kind of order zero.
+    pub fn y0(&self) -> Self {
+        self.bessel(0, true)
+    }
+
+    /// Returns the Bessel function of the second kind of order one.
+    pub fn y1(&self) -> Self {
+        self.bessel(1, true)
+    }
+}
+
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
+    Float<EXPONENT, MANTISSA, PARTS>
+{
+    /// Computes erf(x) = 2/sqrt(pi) * e^(-x^2) * sum(2^n x^(2n+1) / (2n+1)!!).
+    /// Unlike the Taylor series, all of the terms are positive and don't
+    /// cancel.
+    fn erf_series(x: Self) -> Self {
+        let two_x2 = x.sqr().scale(1, RoundingMode::Zero);
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 1;
+        loop {
+            term = term * two_x2 / Self::from_u64(2 * n + 1);
+            let prev = sum;
+            sum = sum + term;
+            if sum == prev {
+                break;
+            }
+            n += 1;
+        }
+        let factor = Self::from_u64(2) / Self::pi().sqrt();
+        factor * x.sqr().neg().exp() * sum
+    }
+
+    /// Computes erfc(x) for positive x with the continued fraction:
+    /// erfc(x) = e^(-x^2) / sqrt(pi) / (x + (1/2)/(x + 1/(x + (3/2)/(x + ...))))
+    /// The fraction is evaluated with the modified Lentz method, and
+    /// converges quickly for large x.
+    fn erfc_continued_fraction(x: Self) -> Self {
+        let one = Self::one(false);
+        let eps = one.scale(-(MANTISSA as i64), RoundingMode::Zero);
+        let mut f = x;
+        let mut c = x;
+        let mut d = Self::zero(false);
+        let mut j = 1;
+        loop {
+            let a = Self::from_u64(j).scale(-1, RoundingMode::Zero);
+            d = one / (x + a * d);
+            c = x + a / c;
+            let delta = c * d;
+            f = f * delta;
+            if (delta - one).abs() <= eps {
+                break;
+            }
+            j += 1;
+        }
+        x.sqr().neg().exp() / (Self::pi().sqrt() * f)
+    }
+
+    /// Computes the pair (erf(x), erfc(x)) for positive x. Below `limit`, erfc
+    /// is computed as 1 - erf(x), which loses about 1.44 * x^2 bits. Above it,
+    /// the continued fraction is used.
+    fn erf_erfc_positive(x: Self, limit: Self) -> (Self, Self) {
+        let one = Self::one(false);
+        if x < limit {
+            let r = Self::erf_series(x);
+            (r, one - r)
+        } else {
+            let r = Self::erfc_continued_fraction(x);
+            (one - r, r)
+        }
+    }
+
+    /// Computes the pair (erf(x), erfc(x)) for positive finite x, in a wider
+    /// format if one is available.
+    fn erf_erfc(x: Self) -> (Self, Self) {
+        if Self::has_wide_float() {
+            // Allow 1 - erf(x) to cancel all of the extra bits of the wide
+            // format, except for a few guard bits.
+            let extra_bits = 236 - MANTISSA as u64 - 8;
+            let limit = WideFloat::from_u64(extra_bits * 2 / 3).sqrt();
+            let x: WideFloat = x.cast();
+            let (r, rc) = WideFloat::erf_erfc_positive(x, limit);
+            return (r.cast(), rc.cast());
+        }
+        let limit = Self::from_u64(2);
+        Self::erf_erfc_positive(x, limit)
+    }
+
+    /// Returns the error function of the number.
+    pub fn erf(&self) -> Self {
+        if self.is_nan() || self.is_zero() {
+            return *self;
+        }
+        if self.is_inf() {
+            return Self::one(self.is_negative());
+        }
+        let r = Self::erf_erfc(self.abs()).0;
+        if self.is_negative() {
+            r.neg()
+        } else {
+            r
+        }
+    }
+
+    /// Returns the complementary error function of the number, 1 - erf(x),
+    /// which is accurate for large x.
+    pub fn erfc(&self) -> Self {
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_zero() {
+            return Self::one(false);
+        }
+        if self.is_inf() {
+            return if self.is_negative() {
+                Self::from_u64(2)
+            } else {
+                Self::zero(false)
+            };
+        }
+        if self.is_negative() {
+            // erfc(-x) = 1 + erf(x), which does not cancel.
+            return Self::one(false) + Self::erf_erfc(self.abs()).0;
+        }
+        Self::erf_erfc(*self).1
+    }
+
+    /// Computes x such that erf(x) = y, or erfc(x) = y if `complement` is set,
+    /// for y in the range (0, 1], using Newton's method:
+    /// x = x + (y - erf(x)) / (2/sqrt(pi) * e^(-x^2)).
+    /// The initial guess is the approximation by Sergei Winitzki.
+    fn erf_inv_newton(y: Self, complement: bool) -> Self {
+        let one = Self::one(false);
+        let two = Self::from_u64(2);
+        let pi = Self::pi();
+        let a = Self::from_u64(147) / Self::from_u64(1000);
+        // Compute ln(1 - erf(x)^2) without cancelling the low bits of y.
+        let l = if complement {
+            (y * (two - y)).ln()
+        } else {
+            ((one - y) * (one + y)).ln()
+        };
+        let t = two / (pi * a) + l.scale(-1, RoundingMode::Zero);
+        let mut x = ((t.sqr() - l / a).sqrt() - t).sqrt();
+
+        let factor = two / pi.sqrt();
+        let eps = one.scale(-(MANTISSA as i64) + 4, RoundingMode::Zero);
+        for _ in 0..100 {
+            let (r, rc) = Self::erf_erfc(x);
+            let residual = if complement { rc - y } else { y - r };
+            let dx = residual / (factor * x.sqr().neg().exp());
+            x = x + dx;
+            if dx.abs() <= x.abs() * eps {
+                break;
+            }
+        }
+        x
+    }
+
+    /// Solves erf(x) = y, or erfc(x) = y if `complement` is set, for y in the
+    /// range (0, 1], in a wider format if one is available.
+    fn erf_inv_positive(y: Self, complement: bool) -> Self {
+        if Self::has_wide_float() {
+            let y: WideFloat = y.cast();
+            return WideFloat::erf_inv_newton(y, complement).cast();
+        }
+        Self::erf_inv_newton(y, complement)
+    }
+
+    /// Returns the inverse complementary error function for z in (0, 2).
+    fn erfc_inv(z: Self) -> Self {
+        let one = Self::one(false);
+        let half = one.scale(-1, RoundingMode::Zero);
+        if z > one {
+            // erfc(-x) = 2 - erfc(x). The subtraction is exact.
+            return Self::erfc_inv(Self::from_u64(2) - z).neg();
+        }
+        if z == one {
+            return Self::zero(false);
+        }
+        if z > half {
+            // erfc(x) = 1 - erf(x). The subtraction is exact.
+            return Self::erf_inv_positive(one - z, false);
+        }
+        Self::erf_inv_positive(z, true)
+    }
+
+    /// Returns the inverse error function of the number, which is in the
+    /// range [-1, 1].
+    pub fn erf_inv(&self) -> Self {
+        let one = Self::one(false);
+        if self.is_nan() || self.is_zero() {
+            return *self;
+        }
+        let y = self.abs();
+        if y > one {
+            return Self::nan(false);
+        }
+        if y == one {
+            return Self::inf(self.is_negative());
+        }
+        // Values close to one are solved with erfc, and the subtraction is
+        // exact for y >= 1/2.
+        let r = if y < one.scale(-1, RoundingMode::Zero) {
+            Self::erf_inv_positive(y, false)
+        } else {
+            Self::erfc_inv(one - y)
+        };
+        if self.is_negative() {
+            r.neg()
+        } else {
+            r
+        }
+    }
+
+    /// Returns the cumulative distribution function of the standard normal
+    /// distribution: norm_cdf(x) = erfc(-x / sqrt(2)) / 2.
+    pub fn norm_cdf(&self) -> Self {
+        // The result is sensitive to the rounding of x / sqrt(2).
+        if Self::has_wide_float() {
+            let x: WideFloat = self.cast();
+            return x.norm_cdf().cast();
+        }
+        let x = self.neg() / Self::from_u64(2).sqrt();
+        x.erfc().scale(-1, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Returns the quantile function (the inverse of the cumulative
+    /// distribution function) of the standard normal distribution, for the
+    /// probability p in the range [0, 1]:
+    /// norm_quantile(p) = -sqrt(2) * erfc_inv(2p).
+    pub fn norm_quantile(&self) -> Self {
+        let one = Self::one(false);
+        if self.is_nan() {
+            return *self;
+        }
+        if self.is_negative() || *self > one {
+            return Self::nan(false);
+        }
+        if self.is_zero() {
+            return Self::inf(true);
+        }
+        if *self == one {
+            return Self::inf(false);
+        }
+        if Self::has_wide_float() {
+            let p: WideFloat = self.cast();
+            return p.norm_quantile().cast();
+        }
+        let z = self.scale(1, RoundingMode::NearestTiesToEven);
+        Self::erfc_inv(z).neg() * Self::from_u64(2).sqrt()
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_gamma() {
+    use super::utils;
+    use super::{FP128, FP16, FP32, FP64};
+
+    // Factorials are exact (and so is the f64 product up to 22!).
+    let mut fact = 1.;
+    for i in 1..24 {
+        assert_eq!(FP64::from_u64(i).gamma().as_f64(), fact);
+        fact *= i as f64;
+    }
+    assert_eq!(FP16::from_u64(5).gamma().as_f32(), 24.);
+
+    // Values from mpmath.
+    fn check(x: f64, res: f64) {
+        let r0 = FP64::from_f64(x).gamma().as_f64();
+        assert!(utils::is_close(r0, res, 1));
+    }
+    check(0.5, 1.772453850905516);
+    check(4.3, 8.855343360454034);
+    check(-2.5, -0.9453087204829419);
+    check(170.5, 5.56209241456e+305);
+    check(1e-5, 99999.42279422555);
+    check(-0.1, -10.686287021193193);
+    check(30.25, 2.0628053137753467e+31);
+    check(0.999, 1.0005782056293586);
+    check(-170.3, -1.1449279983878122e-307);
+
+    // Check gamma(x + 1) = x * gamma(x), and gamma(1/2)^2 = pi, at FP128.
+    let x = FP128::from_u64(1) / FP128::from_u64(3);
+    let one = FP128::one(false);
+    let err = ((x + one).gamma() - x * x.gamma()).abs();
+    assert!(err < x.gamma().scale(-109, RoundingMode::Zero));
+    let half = one.scale(-1, RoundingMode::Zero);
+    let err = (half.gamma().sqr() - FP128::pi()).abs();
+    assert!(err < FP128::pi().scale(-109, RoundingMode::Zero));
+
+    // The approximation is chosen for the precision of the format, and not
+    // of the wider format of the evaluation.
+    for i in 0..20 {
+        let x = FP32::from_f64(i as f64 * 1.13 - 9.1);
+        let expected = FP64::from_f64(x.as_f64());
+        let (r, e) = (x.gamma().as_f32(), expected.gamma().as_f64() as f32);
+        assert!((r - e).abs() <= e.abs() * f32::EPSILON, "{} {}", r, e);
+        let (r, e) = (x.ln_gamma().as_f32(), expected.ln_gamma().as_f64());
+        let tolerance = e.abs().max(1.) as f32 * f32::EPSILON;
+        assert!((r - e as f32).abs() <= tolerance, "{} {}", r, e);
+    }
+
+    // Test overflow, underflow and the special values.
+    assert!(FP64::from_u64(172).gamma().is_inf());
+    assert!(FP64::from_f64(-190.5).gamma().is_zero());
+    assert!(FP64::from_f64(0.).gamma().is_inf());
+    assert!(FP64::from_f64(-0.).gamma().is_negative());
+    assert!(FP64::from_f64(-3.).gamma().is_nan());
+    assert!(FP64::inf(true).gamma().is_nan());
+    assert!(FP64::inf(false).gamma().is_inf());
+    assert!(FP64::nan(false).gamma().is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_ln_gamma() {
+    use super::utils;
+    use super::FP64;
+
+    // Values from mpmath.
+    fn check(x: f64, res: f64) {
+        let r0 = FP64::from_f64(x).ln_gamma().as_f64();
+        assert!(utils::is_close(r0, res, 1));
+    }
+    check(10.5, 13.940625219403763);
+    check(1e10, 220258509288.81058);
+    check(-2.5, -0.056243716497674054);
+    check(0.001, 6.907178885383853);
+    check(1.5, -0.12078223763524522);
+    check(1e300, 6.897755278982137e+302);
+    check(2.000001, 4.2278465762452923e-07);
+    check(-100.7, -365.61225576180533);
+    check(3., std::f64::consts::LN_2);
+
+    // Test the special values.
+    assert!(FP64::from_f64(1.).ln_gamma().is_zero());
+    assert!(FP64::from_f64(2.).ln_gamma().is_zero());
+    assert!(FP64::from_f64(-4.).ln_gamma().is_inf());
+    assert!(FP64::from_f64(-0.).ln_gamma().is_inf());
+    assert!(FP64::inf(true).ln_gamma().is_inf());
+    assert!(FP64::nan(false).ln_gamma().is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bessel() {
+    use super::utils;
+    use super::{FP128, FP64};
+
+    // Values from mpmath. The second and fourth values are close to the
+    // zeros of J0 and J1.
+    let xs = [
+        1e-5,
+        0.5,
+        2.404825557695773,
+        3.8317059702075125,
+        7.5,
+        28.9,
+        29.5,
+        100.25,
+        1234.5,
+    ];
+    let j0 = [
+        0.999999999975,
+        0.9384698072408129,
+        -6.10876525973673e-17,
+        -0.402759395702553,
+        0.2663396578803784,
+        -0.14641582398874464,
+        -0.1331478582983982,
+        0.038427302481501335,
+        -0.013550379618035721,
+    ];
+    let j1 = [
+        4.9999999999375e-06,
+        0.2422684576748739,
+        0.5191474972894667,
+        -6.149807356994906e-17,
+        0.1352484275797055,
+        0.02170926042203162,
+        -0.0643043780991924,
+        -0.06962028467960972,
+        0.0182175083373925,
+    ];
+    let y0 = [
+        -7.40316028370197,
+        -0.44451873350670656,
+        0.509924383448479,
+        0.0513976730994109,
+        0.11731328614820863,
+        0.024238030060577686,
+        -0.06203938540000604,
+        -0.06981106912601158,
+        0.018222995047412552,
+    ];
+    let y1 = [
+        -63661.97727536548,
+        -1.471472392670243,
+        0.1027466824382596,
+        0.41251739515882574,
+        -0.25912851048611624,
+        0.14685691432381437,
+        0.13211573506102567,
+        -0.03877595658074313,
+        0.013557761447180334,
+    ];
+    for i in 0..xs.len() {
+        let x = FP64::from_f64(xs[i]);
+        assert!(utils::is_close(x.j0().as_f64(), j0[i], 1));
+        assert!(utils::is_close(x.j1().as_f64(), j1[i], 1));
+        assert!(utils::is_close(x.y0().as_f64(), y0[i], 1));
+        assert!(utils::is_close(x.y1().as_f64(), y1[i], 1));
+        assert!(utils::is_close(x.neg().j0().as_f64(), j0[i], 1));
+        assert!(utils::is_close(x.neg().j1().as_f64(), -j1[i], 1));
+    }
+
+    // Check the Wronskian: J1(x)Y0(x) - J0(x)Y1(x) = 2/(pi*x), at FP128.
+    for v in [0.75, 20., 90.] {
+        let x = FP128::from_f64(v);
+        let w = x.j1() * x.y0() - x.j0() * x.y1();
+        let r = FP128::from_u64(2) / (FP128::pi() * x);
+        assert!((w - r).abs() < r.scale(-100, RoundingMode::Zero));
+    }
+
+    // Test the special values.
+    let zero = FP64::zero(false);
+    assert_eq!(zero.j0().as_f64(), 1.);
+    assert!(zero.j1().is_zero());
+    assert!(zero.y0().is_inf() && zero.y0().is_negative());
+    assert!(zero.y1().is_inf() && zero.y1().is_negative());
+    assert!(FP64::from_f64(-1.).y0().is_nan());
+    assert!(FP64::inf(true).j0().is_zero());
+    assert!(FP64::inf(false).y1().is_zero());
+    assert!(FP64::nan(false).j1().is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_erf() {
+    use super::{FP128, FP64};
+    use crate::utils;
+
+    // Values from mpmath.
+    fn check(x: f64, erf: f64, erfc: f64) {
+        let x = FP64::from_f64(x);
+        assert!(utils::is_close(x.erf().as_f64(), erf, 1));
+        assert!(utils::is_close(x.erfc().as_f64(), erfc, 1));
+    }
+    check(0.1, 0.1124629160182849, 0.887537083981715);
+    check(0.5, 0.5204998778130465, 0.4795001221869535);
+    check(1.0, 0.8427007929497149, 0.15729920705028513);
+    check(2.5, 0.999593047982555, 0.0004069520174449589);
+    check(5.0, 0.9999999999984626, 1.537459794428035e-12);
+    check(10.0, 1.0, 2.088487583762545e-45);
+    check(26.5, 1.0, 2.2109076642637343e-307);
+    check(-0.75, -0.7111556336535151, 1.7111556336535152);
+    check(1e-10, 1.1283791670955126e-10, 0.999999999887162);
+
+    // Check erf(x) + erfc(x) = 1 at FP128.
+    let one = FP128::one(false);
+    for v in [0.3, 1.7, 4.2, 9.] {
+        let x = FP128::from_f64(v);
+        let err = (x.erf() + x.erfc() - one).abs();
+        assert!(err <= one.scale(-110, RoundingMode::Zero));
+    }
+
+    // Test the special values.
+    assert_eq!(FP64::inf(false).erf().as_f64(), 1.);
+    assert_eq!(FP64::inf(true).erf().as_f64(), -1.);
+    assert_eq!(FP64::inf(true).erfc().as_f64(), 2.);
+    assert!(FP64::inf(false).erfc().is_zero());
+    assert!(FP64::zero(true).erf().is_negative());
+    assert_eq!(FP64::zero(true).erfc().as_f64(), 1.);
+    assert!(FP64::from_f64(30.).erfc().is_zero());
+    assert!(FP64::nan(false).erf().is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_erf_inv() {
+    use super::FP64;
+    use crate::utils;
+
+    // Values from mpmath.
+    fn check(y: f64, res: f64) {
+        let r0 = FP64::from_f64(y).erf_inv().as_f64();
+        assert!(utils::is_close(r0, res, 1));
+    }
+    check(0.1, 0.08885599049425769);
+    check(0.5, 0.4769362762044699);
+    check(0.9, 1.1630871536766743);
+    check(0.999999, 3.458910737275499);
+    check(-0.3, -0.2724627147267543);
+    check(1e-10, 8.862269254527581e-11);
+
+    // Round trip. Larger values are ill-conditioned, because the derivative
+    // of erf is small.
+    for i in 1..4 {
+        let x = FP64::from_f64(i as f64 / 4.);
+        let r0 = x.erf().erf_inv().as_f64();
+        assert!(utils::is_close(r0, x.as_f64(), 4));
+    }
+
+    assert!(FP64::from_f64(1.).erf_inv().is_inf());
+    assert!(FP64::from_f64(-1.).erf_inv().is_negative());
+    assert!(FP64::from_f64(1.5).erf_inv().is_nan());
+    assert!(FP64::zero(true).erf_inv().is_negative());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_norm() {
+    use super::FP64;
+    use crate::utils;
+
+    // Values from mpmath.
+    fn check_cdf(x: f64, res: f64) {
+        let r0 = FP64::from_f64(x).norm_cdf().as_f64();
+        assert!(utils::is_close(r0, res, 1));
+    }
+    check_cdf(-40., 0.);
+    check_cdf(-5., 2.866515718791939e-07);
+    check_cdf(-1., 0.15865525393145705);
+    check_cdf(0.3, 0.6179114221889527);
+    check_cdf(2., 0.9772498680518208);
+
+    fn check_quantile(p: f64, res: f64) {
+        let r0 = FP64::from_f64(p).norm_quantile().as_f64();
+        assert!(utils::is_close(r0, res, 1));
+    }
+    check_quantile(1e-300, -37.0470962993612);
+    check_quantile(1e-10, -6.361340902404057);
+    check_quantile(0.025, -1.9599639845400543);
+    check_quantile(0.3, -0.5244005127080408);
+    check_quantile(0.975, 1.9599639845400538);
+    check_quantile(0.9999999, 5.199337582290661);
+
+    assert!(FP64::from_f64(0.5).norm_quantile().is_zero());
+    assert!(FP64::from_f64(0.).norm_quantile().is_inf());
+    assert!(FP64::from_f64(1.).norm_quantile().is_inf());
+    assert!(FP64::from_f64(1.1).norm_quantile().is_nan());
+    assert_eq!(FP64::inf(false).norm_cdf().as_f64(), 1.);
+    assert!(FP64::inf(true).norm_cdf().is_zero());
+}
@@ -0,0 +1,226 @@
+//! Packed storage forms of the predefined formats. Each type holds the
+//! interchange encoding of a format, and has the size of the encoding, so
+//! arrays of the types can be memory-mapped, or passed to foreign code that
+//! expects arrays of native floats. The formats of up to 64 bits are stored as
+//! integers in the native byte order, like the native float types, and the
+//! wider formats are stored as little-endian byte arrays, with an alignment of
+//! one byte. FP80 is stored in the 10 bytes of the x87 encoding.
+//!
+//! The conversions to and from Float are exact, except that signaling NaNs are
+//! loaded as quiet NaNs. The `bytemuck` feature derives `Pod` and `Zeroable`,
+//! and the `zerocopy` feature derives `FromBytes`, `IntoBytes`, `Immutable`
+//! and `KnownLayout` for the types.
+//!```
+//!    use arpfloat::{Fp16Bits, FP16};
+//!    let x = Fp16Bits::from_float(&FP16::from_f64(1.5));
+//!    assert_eq!(x.to_bits(), 0x3e00);
+//!    assert_eq!(x.to_float().as_f64(), 1.5);
+//!```
+
+use super::float::{BF16, FP128, FP16, FP256, FP32, FP64, FP80};
+
+macro_rules! define_storage_format {
+    ($(#[$attr:meta])* $name:ident, $ty:ty, $float:ty, $to:expr, $from:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[cfg_attr(
+            feature = "bytemuck",
+            derive(bytemuck::Pod, bytemuck::Zeroable)
+        )]
+        #[cfg_attr(
+            feature = "zerocopy",
+            derive(
+                zerocopy::FromBytes,
+                zerocopy::IntoBytes,
+                zerocopy::Immutable,
+                zerocopy::KnownLayout
+            )
+        )]
+        pub struct $name($ty);
+
+        impl $name {
+            /// Creates a number from its encoding.
+            pub fn from_bits(bits: $ty) -> Self {
+                $name(bits)
+            }
+
+            /// Returns the encoding of the number.
+            pub fn to_bits(&self) -> $ty {
+                self.0
+            }
+
+            /// Converts `val` to its storage form.
+            pub fn from_float(val: &$float) -> Self {
+                let to_bits: fn(&$float) -> $ty = $to;
+                $name(to_bits(val))
+            }
+
+            /// Loads the stored number.
+            pub fn to_float(&self) -> $float {
+                let from_bits: fn($ty) -> $float = $from;
+                from_bits(self.0)
+            }
+        }
+
+        impl From<$float> for $name {
+            fn from(val: $float) -> Self {
+                Self::from_float(&val)
+            }
+        }
+
+        impl From<$name> for $float {
+            fn from(val: $name) -> Self {
+                val.to_float()
+            }
+        }
+    };
+}
+
+define_storage_format!(
+    /// The storage form of FP16, with the layout of `f16`.
+    Fp16Bits,
+    u16,
+    FP16,
+    |x| x.to_bits()[0] as u16,
+    |bits| FP16::from_bits([bits as u64])
+);
+
+define_storage_format!(
+    /// The storage form of BF16, which is the upper half of an FP32 encoding.
+    Bf16Bits,
+    u16,
+    BF16,
+    |x| x.to_bits()[0] as u16,
+    |bits| BF16::from_bits([bits as u64])
+);
+
+define_storage_format!(
+    /// The storage form of FP32, with the layout of `f32`.
+    Fp32Bits,
+    u32,
+    FP32,
+    |x| x.to_bits()[0] as u32,
+    |bits| FP32::from_bits([bits as u64])
+);
+
+define_storage_format!(
+    /// The storage form of FP64, with the layout of `f64`.
+    Fp64Bits,
+    u64,
+    FP64,
+    |x| x.to_bits()[0],
+    |bits| FP64::from_bits([bits, 0])
+);
+
+define_storage_format!(
+    /// The storage form of FP80, in the 10 bytes of the x87 encoding, with
+    /// the explicit integer bit. See `as_fp80_bits`.
+    Fp80Bits,
+    [u8; 10],
+    FP80,
+    |x| core::array::from_fn(|i| (x.as_fp80_bits() >> (i * 8)) as u8),
+    |bytes| {
+        let bits = bytes.iter().rev().fold(0, |acc, b| acc << 8 | *b as u128);
+        FP80::from_fp80_bits(bits)
+    }
+);
+
+define_storage_format!(
+    /// The storage form of FP128, in little-endian byte order.
+    Fp128Bits,
+    [u8; 16],
+    FP128,
+    |x| x.to_le_bytes(),
+    FP128::from_le_bytes
+);
+
+define_storage_format!(
+    /// The storage form of FP256, in little-endian byte order.
+    Fp256Bits,
+    [u8; 32],
+    FP256,
+    |x| x.to_le_bytes(),
+    FP256::from_le_bytes
+);
+
+#[test]
+fn test_storage_round_trip() {
+    use super::utils::Lfsr;
+    use core::mem::{align_of, size_of};
+
+    assert_eq!(size_of::<Fp16Bits>(), 2);
+    assert_eq!(size_of::<Bf16Bits>(), 2);
+    assert_eq!(size_of::<Fp32Bits>(), 4);
+    assert_eq!(size_of::<Fp64Bits>(), 8);
+    assert_eq!(size_of::<Fp80Bits>(), 10);
+    assert_eq!(size_of::<[Fp128Bits; 3]>(), 48);
+    assert_eq!(size_of::<Fp256Bits>(), 32);
+    assert_eq!(align_of::<Fp128Bits>(), 1);
+
+    // Known encodings.
+    let x = FP64::from_f64(-2.5);
+    assert_eq!(
+        Fp32Bits::from_float(&x.cast()).to_bits(),
+        (-2.5f32).to_bits()
+    );
+    assert_eq!(Fp64Bits::from(x).to_bits(), (-2.5f64).to_bits());
+    assert_eq!(Bf16Bits::from_float(&x.cast()).to_bits(), 0xc020);
+    let one = Fp80Bits::from_float(&FP80::from_u64(1));
+    assert_eq!(one.to_bits(), [0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x3f]);
+    let one = Fp128Bits::from_float(&FP128::from_u64(1));
+    assert_eq!(&one.to_bits()[14..], &[0xff, 0x3f]);
+    assert_eq!(FP128::from(one), FP128::from_u64(1));
+
+    // Every encoding survives the round trip, including the NaN payloads.
+    // Signaling NaNs are loaded as quiet NaNs.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..1000 {
+        let bits = lfsr.get64();
+        let x = Fp16Bits::from_bits(bits as u16);
+        let quiet = if x.to_float().is_nan() { 0x200 } else { 0 };
+        let y = Fp16Bits::from_float(&x.to_float());
+        assert_eq!(y.to_bits(), x.to_bits() | quiet);
+        let x = Fp32Bits::from_bits(bits as u32);
+        let quiet = if x.to_float().is_nan() { 1 << 22 } else { 0 };
+        assert_eq!(Fp32Bits::from(x.to_float()).to_bits(), bits as u32 | quiet);
+        let x = Fp64Bits::from_bits(bits);
+        let quiet = if x.to_float().is_nan() { 1 << 51 } else { 0 };
+        assert_eq!(Fp64Bits::from(x.to_float()).to_bits(), bits | quiet);
+        let bits = bits as u128 | (lfsr.get64() as u128) << 64;
+        let x = Fp128Bits::from_bits(bits.to_le_bytes());
+        let quiet = if x.to_float().is_nan() { 1 << 111 } else { 0 };
+        let y = u128::from_le_bytes(Fp128Bits::from(x.to_float()).to_bits());
+        assert_eq!(y, bits | quiet);
+        let x = FP80::from_f64(f64::from_bits(bits as u64));
+        let y = Fp80Bits::from_float(&x).to_float();
+        assert!(x == y || x.is_nan() && y.is_nan());
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_storage_bytemuck() {
+    let raw: [u16; 2] = [0x3c00, 0xc000];
+    let vals: &[Fp16Bits] = bytemuck::cast_slice(&raw);
+    assert_eq!(vals[0].to_float().as_f64(), 1.0);
+    assert_eq!(vals[1].to_float().as_f64(), -2.0);
+    let vals = [Fp128Bits::from_float(&FP128::from_u64(3)); 2];
+    let bytes: &[u8] = bytemuck::cast_slice(&vals);
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(
+        FP128::from_le_bytes::<16>(bytes[16..].try_into().unwrap()),
+        3
+    );
+}
+
+#[cfg(feature = "zerocopy")]
+#[test]
+fn test_storage_zerocopy() {
+    use zerocopy::{FromBytes, IntoBytes};
+    let raw = [1.0f32, -2.0];
+    let vals = <[Fp32Bits]>::ref_from_bytes(raw.as_bytes()).unwrap();
+    assert_eq!(vals[0].to_float().as_f32(), 1.0);
+    assert_eq!(vals[1].to_float().as_f32(), -2.0);
+    assert_eq!(vals.as_bytes(), raw.as_bytes());
+}
@@ -1,19 +1,30 @@
 extern crate alloc;
 
-use super::bigint::BigInt;
-use super::float::Float;
+use super::bigint::{BigInt, DynBigInt, LossFraction};
+use super::float::{Float, ParseFloatError, RoundingMode};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use core::fmt::Display;
 use core::cmp::Ordering;
+use core::fmt::{Binary, Display, LowerExp, LowerHex, UpperExp, UpperHex};
+use core::str::FromStr;
 
 #[cfg(test)]
 #[cfg(feature = "std")]
-use std::{println, format};
+use std::{format, println};
 
+// Use a growable bigint for the decimal conversions, because the powers of
+// ten in the range of the wide types don't fit in a fixed-size integer.
+type BigNum = DynBigInt;
 
-// Use a bigint for the decimal conversions.
-type BigNum = BigInt<50>;
+// The fixed-size integer that `parse_bytes` uses on the stack. It holds the
+// longest significands, and the powers of ten of the formats up to FP64.
+type StackNum = BigInt<48>;
+
+/// The number of significant digits that the parser keeps. The digits after
+/// them only affect the rounding, like a sticky bit. This is more than the 767
+/// significant digits of the longest FP64 number, so parsing is correctly
+/// rounded for FP64 and for the narrower types.
+const MAX_PARSED_DIGITS: usize = 800;
 
 impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
     Float<EXPONENT, MANTISSA, PARTS>
@@ -27,7 +38,7 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         // See Ryu: Fast Float-to-String Conversion -- Ulf Adams.
         // https://youtu.be/kw-U6smcLzk?t=681
         let mut exp = self.get_exp() - MANTISSA as i64;
-        let mut mantissa: BigNum = self.get_mantissa().cast();
+        let mut mantissa = BigNum::from_bigint(&self.get_mantissa());
 
         match exp.cmp(&0) {
             Ordering::Less => {
@@ -38,10 +49,8 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
                 // And the left hand side is how we represent our binary number
                 // 1.mmmm * 2^-e, and the right-hand-side is how we represent
                 // our decimal number: nnnnnnn * 10^-e.
-                let five = BigInt::from_u64(5);
-                let e5 = five.powi((-exp) as u64);
-                let overflow = mantissa.inplace_mul(e5);
-                debug_assert!(!overflow);
+                let e5 = BigNum::from_u64(5).powi((-exp) as u64);
+                mantissa.inplace_mul(&e5);
                 exp = -exp;
             }
             Ordering::Equal | Ordering::Greater => {
@@ -66,6 +75,377 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         2 + (MANTISSA * 59) / 196
     }
 
+    /// Converts the decimal number `significand * 10^exp10` to the nearest
+    /// float, with ties rounded to even.
+    pub fn from_decimal(significand: i128, exp10: i64) -> Self {
+        let rm = RoundingMode::NearestTiesToEven;
+        Self::from_decimal_with_rm(significand, exp10, rm)
+    }
+
+    /// Converts the decimal number `significand * 10^exp10` to the nearest
+    /// float, rounded with the rounding mode `rm`. The number is scaled
+    /// exactly with big integers, and is rounded only once. Numbers outside
+    /// of the range of the type become infinity or zero.
+    pub fn from_decimal_with_rm(
+        significand: i128,
+        exp10: i64,
+        rm: RoundingMode,
+    ) -> Self {
+        let m = BigNum::from_u128(significand.unsigned_abs());
+        Self::from_big_radix(significand < 0, m, 10, exp10, rm)
+    }
+
+    /// Converts the number `m * radix^exp`, with the sign `sign`, to a float,
+    /// rounded with the rounding mode `rm`. Numbers that need integers larger
+    /// than `B` are converted with a BigNum.
+    fn from_big_radix<B: DigitInt>(
+        sign: bool,
+        m: B,
+        radix: u64,
+        exp: i64,
+        rm: RoundingMode,
+    ) -> Self {
+        if m.is_zero() {
+            return Self::zero(sign);
+        }
+
+        // Handle numbers that are far outside of the range of the type, using
+        // the bounds 2^(l*e) <= radix^e for e >= 0, and radix^e <= 2^(l*e) for
+        // e < 0, where l is a lower bound of log2(radix). Numbers that are too
+        // small are replaced by a number that is less than half of the
+        // smallest subnormal, and round the same way.
+        let (min_exp, max_exp) = Self::get_exp_bounds();
+        let log2_radix = log2_fixed(radix);
+        let bits = m.msb_index() as i64;
+        let log2_pow = (exp.unsigned_abs() as i128 * log2_radix as i128) >> 16;
+        let log2_pow = if exp < 0 { -log2_pow } else { log2_pow };
+        if exp > 0 && (bits - 1) as i128 + log2_pow > max_exp as i128 {
+            let mut a = Self::new(sign, max_exp + 1, BigInt::one_hot(MANTISSA));
+            a.normalize(rm, LossFraction::ExactlyZero);
+            return a;
+        }
+        let min_bits = (min_exp - MANTISSA as i64 - 2) as i128;
+        if exp < 0 && bits as i128 + log2_pow < min_bits {
+            let mut a = Self::new(sign, min_exp - 2, BigInt::one());
+            a.normalize(rm, LossFraction::ExactlyZero);
+            return a;
+        }
+
+        // Split the radix into odd * 2^twos, and only scale by the powers of
+        // the odd part. For decimal numbers this uses 10^e = 5^e * 2^e.
+        let twos = radix.trailing_zeros() as i64;
+        let odd = radix >> twos;
+
+        // Check that the power, and the scaled number, fit in B.
+        let log2_odd = log2_fixed(odd) as i128 + 1;
+        let pow_bits = ((exp.unsigned_abs() as i128 * log2_odd) >> 16) + 1;
+        let bits = bits as i128;
+        let num_bits = if exp >= 0 {
+            bits + pow_bits
+        } else {
+            bits.max(pow_bits + MANTISSA as i128 + 3)
+        };
+        if num_bits >= B::BITS as i128 {
+            return Self::from_big_radix(sign, m.to_big_num(), radix, exp, rm);
+        }
+
+        let pow = B::from_u64(odd).powi(exp.unsigned_abs());
+        let exp2 = exp * twos;
+        let mut num = m;
+
+        if exp >= 0 {
+            // Keep a few bits more than the precision, and round with the
+            // bits that are shifted out.
+            num.inplace_mul(&pow);
+            let shift = num.msb_index().saturating_sub(MANTISSA + 3);
+            let loss = num.get_loss_kind_for_bit(shift);
+            num.shift_right(shift);
+            let exp = MANTISSA as i64 + exp2 + shift as i64;
+            let mut a = Self::new(sign, exp, num.to_bigint());
+            a.normalize(rm, loss);
+            return a;
+        }
+
+        // Divide by the power of the odd part, with a few bits more than the
+        // precision, and fold the remainder into a sticky bit. Long
+        // significands can leave more bits in the quotient, which are folded
+        // into the sticky bit too.
+        let shift =
+            (pow.msb_index() + MANTISSA + 3).saturating_sub(num.msb_index());
+        num.shift_left(shift);
+        let rem = num.inplace_div(&pow);
+        let extra = num.msb_index().saturating_sub(MANTISSA + 4);
+        let sticky = !rem.is_zero() || num.trailing_zeros() < extra;
+        num.shift_right(extra);
+        num.shift_left(1);
+        if sticky {
+            num.flip_bit(0);
+        }
+        let exp = MANTISSA as i64 + exp2 - shift as i64 + extra as i64 - 1;
+        let mut a = Self::new(sign, exp, num.to_bigint());
+        a.normalize(rm, LossFraction::ExactlyZero);
+        a
+    }
+
+    /// Parses the decimal number in `s`, and rounds it with the rounding mode
+    /// `rm`. The number has an optional sign, digits with an optional decimal
+    /// point, and an optional exponent, like "-1.5e-7". The strings "inf",
+    /// "infinity", "nan" and "snan" are accepted in any case, and the NaNs
+    /// can have a payload in decimal or in hex, like "nan(0x123)". The number
+    /// is rounded once, like [`Float::from_decimal_with_rm`].
+    ///```
+    ///    use arpfloat::{FP64, RoundingMode};
+    ///    let x = FP64::from_str_with_rm("0.1", RoundingMode::Zero).unwrap();
+    ///    assert!(x.as_f64() < 0.1);
+    ///```
+    pub fn from_str_with_rm(
+        s: &str,
+        rm: RoundingMode,
+    ) -> Result<Self, ParseFloatError> {
+        Self::from_str_radix(s, 10, rm)
+    }
+
+    /// Parses the number in `s`, in the base `radix`, between 2 and 36, and
+    /// rounds it with the rounding mode `rm`. The digits above 9 are the
+    /// letters, in any case. The number has an optional sign, digits with an
+    /// optional radix point, and an optional exponent, which is the power of
+    /// the radix, in decimal. The exponent follows '@', or 'e' in bases up to
+    /// 10, like the output of [`Float::to_string_radix`]. The strings "@inf@",
+    /// "@nan@" and "@snan@" are accepted in any case, and "inf", "infinity",
+    /// "nan" and "snan" in bases up to 16. The NaNs can have a payload, like
+    /// [`Float::from_str_with_rm`]. All NaNs are quiet, so signaling NaNs
+    /// become quiet NaNs with the same payload. The number is rounded once,
+    /// and the digits after the first 2660 bits are folded into a sticky
+    /// digit, like the digits after the first 800 decimal digits.
+    ///```
+    ///    use arpfloat::{FP32, RoundingMode};
+    ///    let rm = RoundingMode::NearestTiesToEven;
+    ///    let x = FP32::from_str_radix("-1.1e5", 2, rm).unwrap();
+    ///    assert_eq!(x.as_f32(), -48.0);
+    ///    let x = FP32::from_str_radix("z.8@1", 36, rm).unwrap();
+    ///    assert_eq!(x.as_f32(), 1268.0);
+    ///```
+    pub fn from_str_radix(
+        s: &str,
+        radix: u32,
+        rm: RoundingMode,
+    ) -> Result<Self, ParseFloatError> {
+        Self::parse_radix::<BigNum>(s, radix, rm)
+    }
+
+    /// Parses the number in `s`, like [`Float::from_str_radix`], and collects
+    /// the digits in the integer type `B`.
+    fn parse_radix<B: DigitInt>(
+        s: &str,
+        radix: u32,
+        rm: RoundingMode,
+    ) -> Result<Self, ParseFloatError> {
+        assert!((2..=36).contains(&radix), "Invalid radix");
+        let input = s;
+        let (sign, s) = split_sign(s);
+        let is_word = |w: &str| s.eq_ignore_ascii_case(w);
+        let words = radix <= 16;
+        let inf = is_word("inf") || is_word("infinity");
+        if is_word("@inf@") || (words && inf) {
+            return Ok(Self::inf(sign));
+        }
+        if let Some(payload) = split_nan(s, words) {
+            let payload: B = parse_nan_payload(input, payload, MANTISSA - 1)?;
+            return Ok(Self::nan_with_payload(sign, payload.to_bigint()));
+        }
+
+        let markers: &[char] = if radix <= 10 {
+            &['e', 'E', '@']
+        } else {
+            &['@']
+        };
+        let (digits, exponent) = match s.find(markers) {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        // Keep the digits of about 2660 bits, like the 800 decimal digits.
+        let log2_radix = log2_fixed(radix as u64);
+        let max_bits = (MAX_PARSED_DIGITS as i64 * log2_fixed(10)) >> 16;
+        let max_digits = ((max_bits << 16) / log2_radix) as usize;
+
+        // Collect the significant digits in chunks that fit in a u64. Leading
+        // zeros are skipped, and the digits after the first max_digits digits
+        // are folded into a sticky digit.
+        let radix = radix as u64;
+        let chunk_max_len = u64::MAX.ilog(radix);
+        let mut m = B::zero();
+        let mut chunk = 0u64;
+        let mut chunk_len = 0;
+        let mut num_digits = 0;
+        let mut exp: i64 = 0;
+        let mut seen_digit = false;
+        let mut seen_dot = false;
+        let mut sticky = false;
+        for (i, c) in digits.char_indices() {
+            if c == '.' && !seen_dot {
+                seen_dot = true;
+                continue;
+            }
+            let d = match c.to_digit(radix as u32) {
+                Some(d) => d as u64,
+                None => return Err(invalid_digit(input, digits, i)),
+            };
+            seen_digit = true;
+            if num_digits == 0 && d == 0 {
+                exp -= seen_dot as i64;
+            } else if num_digits < max_digits {
+                chunk = chunk * radix + d;
+                chunk_len += 1;
+                num_digits += 1;
+                exp -= seen_dot as i64;
+                if chunk_len == chunk_max_len {
+                    push_digits(&mut m, radix, chunk, chunk_len);
+                    (chunk, chunk_len) = (0, 0);
+                }
+            } else {
+                sticky |= d != 0;
+                exp += !seen_dot as i64;
+            }
+        }
+        if !seen_digit {
+            return Err(ParseFloatError::EmptyMantissa);
+        }
+        push_digits(&mut m, radix, chunk, chunk_len);
+        if sticky {
+            push_digits(&mut m, radix, 1, 1);
+            exp -= 1;
+        }
+
+        if let Some(exponent) = exponent {
+            exp += parse_exponent(input, exponent)?;
+        }
+        Ok(Self::from_big_radix(sign, m, radix, exp, rm))
+    }
+
+    /// Parses the decimal number in the bytes `bytes`, like [`FromStr`], and
+    /// rounds it to nearest, with ties to even. The digits are collected in a
+    /// fixed-size integer on the stack, so the parser does not allocate
+    /// memory in the formats up to FP64, and numbers can be parsed from input
+    /// buffers in firmware without a heap. Numbers in the wider formats that
+    /// don't fit in the stack integer are converted on the heap. Bytes that
+    /// are not ASCII are rejected.
+    ///```
+    ///    use arpfloat::FP32;
+    ///    let x = FP32::parse_bytes(b"-2.5e-1").unwrap();
+    ///    assert_eq!(x.as_f32(), -0.25);
+    ///    assert!(FP32::parse_bytes(b"1.5\xff").is_err());
+    ///```
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, ParseFloatError> {
+        if let Some(position) = bytes.iter().position(|c| !c.is_ascii()) {
+            return Err(ParseFloatError::InvalidDigit { position });
+        }
+        let s = core::str::from_utf8(bytes).unwrap();
+        Self::parse_radix::<StackNum>(s, 10, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Parses the hexadecimal number in `s`, in the syntax of C99, like
+    /// "-0x1.fffffep127", and rounds it to nearest, with ties to even. The
+    /// binary exponent after 'p' is optional. Numbers that fit in the type
+    /// are converted exactly.
+    ///```
+    ///    use arpfloat::FP32;
+    ///    let x = FP32::from_hex_str("0x1.8p+3").unwrap();
+    ///    assert_eq!(x.as_f32(), 12.0);
+    ///```
+    pub fn from_hex_str(s: &str) -> Result<Self, ParseFloatError> {
+        Self::from_hex_str_with_rm(s, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Parses the hexadecimal number in `s`, like [`Float::from_hex_str`],
+    /// and rounds it with the rounding mode `rm`.
+    pub fn from_hex_str_with_rm(
+        s: &str,
+        rm: RoundingMode,
+    ) -> Result<Self, ParseFloatError> {
+        let input = s;
+        let (sign, s) = split_sign(s);
+        let s = match s.get(..2) {
+            Some("0x") | Some("0X") => &s[2..],
+            _ => return Err(invalid_digit(input, s, 0)),
+        };
+        let (digits, exponent) = match s.find(['p', 'P']) {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        // Collect the significant digits, and fold the digits that are below
+        // the precision of the type into a sticky bit.
+        let max_digits = MANTISSA / 4 + 4;
+        let mut m = BigNum::zero();
+        let mut num_digits = 0;
+        let mut exp2: i64 = 0;
+        let mut seen_digit = false;
+        let mut seen_dot = false;
+        let mut sticky = false;
+        for (i, c) in digits.char_indices() {
+            if c == '.' && !seen_dot {
+                seen_dot = true;
+                continue;
+            }
+            let d = match c.to_digit(16) {
+                Some(d) => d as u64,
+                None => return Err(invalid_digit(input, digits, i)),
+            };
+            seen_digit = true;
+            if num_digits == 0 && d == 0 {
+                exp2 -= 4 * seen_dot as i64;
+            } else if num_digits < max_digits {
+                m.shift_left(4);
+                m.inplace_add(&BigNum::from_u64(d));
+                num_digits += 1;
+                exp2 -= 4 * seen_dot as i64;
+            } else {
+                sticky |= d != 0;
+                exp2 += 4 * !seen_dot as i64;
+            }
+        }
+        if !seen_digit {
+            return Err(ParseFloatError::EmptyMantissa);
+        }
+        if let Some(exponent) = exponent {
+            exp2 += parse_exponent(input, exponent)?;
+        }
+        if m.is_zero() {
+            return Ok(Self::zero(sign));
+        }
+        if sticky {
+            m.shift_left(1);
+            m.flip_bit(0);
+            exp2 -= 1;
+        }
+
+        // Handle numbers that are far outside of the range of the type, like
+        // from_decimal.
+        let (min_exp, max_exp) = Self::get_exp_bounds();
+        let msb_exp = exp2 + m.msb_index() as i64 - 1;
+        if msb_exp > max_exp {
+            let mut a = Self::new(sign, max_exp + 1, BigInt::one_hot(MANTISSA));
+            a.normalize(rm, LossFraction::ExactlyZero);
+            return Ok(a);
+        }
+        if msb_exp < min_exp - MANTISSA as i64 - 2 {
+            let mut a = Self::new(sign, min_exp - 2, BigInt::one());
+            a.normalize(rm, LossFraction::ExactlyZero);
+            return Ok(a);
+        }
+
+        // Keep a few bits more than the precision, and round with the bits
+        // that are shifted out.
+        let shift = m.msb_index().saturating_sub(MANTISSA + 3);
+        let loss = m.get_loss_kind_for_bit(shift);
+        m.shift_right(shift);
+        let exp = MANTISSA as i64 + exp2 + shift as i64;
+        let mut a = Self::new(sign, exp, m.to_bigint());
+        a.normalize(rm, loss);
+        Ok(a)
+    }
+
     /// Reduce a number in the representation mmmmm * e^10, to fewer bits in
     /// 'm', based on the max possible digits in the mantissa.
     fn reduce_printed_integer_length(integer: &mut BigNum, exp: &mut i64) {
@@ -83,9 +463,9 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
             digits_to_remove = *exp;
         }
         *exp -= digits_to_remove;
-        let ten = BigInt::from_u64(10);
+        let ten = BigNum::from_u64(10);
         let divisor = ten.powi(digits_to_remove as u64);
-        integer.inplace_div(divisor);
+        integer.inplace_div(&divisor);
     }
 
     fn convert_normal_to_string(&self) -> String {
@@ -97,7 +477,7 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
 
         let chars = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
         while !integer.is_zero() {
-            let rem = integer.inplace_div(ten);
+            let rem = integer.inplace_div(&ten);
             let ch = chars[rem.as_u64() as usize];
             buff.insert(0, ch);
         }
@@ -114,31 +494,761 @@ impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>
         String::from_iter(buff)
     }
 
-    /// Convert the number to a string. This is a simple implementation
-    /// that does not take into account rounding during the round-trip of
-    /// parsing-printing of the value, or scientific notation, and the minimal
-    /// representation of numbers. For all of that that check out the paper:
-    /// "How to Print Floating-Point Numbers Accurately" by Steele and White.
+    /// Convert the magnitude of the number to a string. This is a simple
+    /// implementation that does not take into account rounding during the
+    /// round-trip of parsing-printing of the value, or scientific notation,
+    /// and the minimal representation of numbers. For all of that that check
+    /// out the paper: "How to Print Floating-Point Numbers Accurately" by
+    /// Steele and White.
     fn convert_to_string(&self) -> String {
-        let result = if self.get_sign() { "-" } else { "" };
-        let mut result: String = result.to_string();
-
-        let body: String = match self.get_category() {
+        match self.get_category() {
             super::float::Category::Infinity => "Inf".to_string(),
-            super::float::Category::NaN => "NaN".to_string(),
+            super::float::Category::NaN => {
+                // Print the payload, so that the NaN is parsed back exactly.
+                let payload = self.get_nan_payload().unwrap();
+                let payload = BigNum::from_bigint(&payload);
+                if payload.is_zero() {
+                    "NaN".to_string()
+                } else {
+                    let digits = radix_digits(payload, 16);
+                    "NaN(0x".to_string() + &digits + ")"
+                }
+            }
             super::float::Category::Normal => self.convert_normal_to_string(),
             super::float::Category::Zero => "0.0".to_string(),
+        }
+    }
+
+    /// Returns the magnitude of the number divided by radix^exp, rounded to an
+    /// integer, with ties to even. The number must be normal.
+    fn get_scaled_integer(&self, radix: u64, exp: i64) -> BigNum {
+        debug_assert!(self.is_normal());
+        let mut num = BigNum::from_bigint(&self.get_mantissa());
+
+        // Split the radix to an odd number and a power of two, and move the
+        // power of two to the binary exponent. For example: 10 = 5 * 2.
+        let twos = radix.trailing_zeros() as i64;
+        let odd = BigNum::from_u64(radix >> twos);
+        let exp2 = self.get_exp() - MANTISSA as i64 - exp * twos;
+
+        let pow = odd.powi(exp.unsigned_abs());
+        if exp <= 0 {
+            // The denominator is a power of two, so divide with a shift, and
+            // round with the bits that are shifted out.
+            num.inplace_mul(&pow);
+            if exp2 >= 0 {
+                num.shift_left(exp2 as usize);
+                return num;
+            }
+            let loss = num.get_loss_kind_for_bit(-exp2 as usize);
+            num.shift_right(-exp2 as usize);
+            let round_up = match loss {
+                LossFraction::MoreThanHalf => true,
+                LossFraction::ExactlyHalf => num.is_odd(),
+                _ => false,
+            };
+            if round_up {
+                num.inplace_add(&BigNum::one());
+            }
+            return num;
+        }
+
+        // The quotient has about as many bits as the digits that are printed,
+        // so the long division is short.
+        let mut den = pow;
+        if exp2 >= 0 {
+            num.shift_left(exp2 as usize);
+        } else {
+            den.shift_left(-exp2 as usize);
+        }
+        let mut rem = num.inplace_div(&den);
+        rem.shift_left(1);
+        let round_up = match rem.cmp(&den) {
+            Ordering::Greater => true,
+            Ordering::Equal => num.is_odd(),
+            Ordering::Less => false,
+        };
+        if round_up {
+            num.inplace_add(&BigNum::one());
+        }
+        num
+    }
+
+    /// Returns an upper bound of the number of significant digits of the
+    /// exact value of the number in the base `radix`, or None if the digits
+    /// don't terminate. The number must be normal.
+    fn get_max_significant_digits(&self, radix: u64) -> Option<usize> {
+        let bits = self.get_mantissa().msb_index() as i64;
+        let exp2 = self.get_exp() - MANTISSA as i64;
+        let log2_radix = log2_fixed(radix);
+        // The digits of m * 2^e, and of m * (radix/2)^-e for negative
+        // exponents, which only terminate for even radixes.
+        let digits = if exp2 >= 0 {
+            ((bits + exp2) << 16) / log2_radix
+        } else if radix & 1 == 0 {
+            ((bits << 16) - exp2 * (log2_radix - (1 << 16))) / log2_radix
+        } else {
+            return None;
+        };
+        Some(digits as usize + 2)
+    }
+
+    /// Returns the magnitude of the number, rounded to `digits` significant
+    /// digits in the base `radix` with ties to even, as the integer `d` with
+    /// `digits` digits, and the exponent `k`, where the value is
+    /// d * radix^(k-digits+1). The number must be normal, and `digits` must
+    /// not be zero.
+    fn get_significant_digits(
+        &self,
+        radix: u64,
+        digits: usize,
+    ) -> (BigNum, i64) {
+        let radix_big = BigNum::from_u64(radix);
+        let low = radix_big.powi(digits as u64 - 1);
+        let high = radix_big.powi(digits as u64);
+
+        // Estimate the exponent from the binary exponent, and correct the
+        // estimate.
+        let bits = self.get_mantissa().msb_index() as i64;
+        let log2 = self.get_exp() - MANTISSA as i64 + bits - 1;
+        let mut k = (log2 << 16).div_euclid(log2_fixed(radix));
+        loop {
+            let d = self.get_scaled_integer(radix, k - digits as i64 + 1);
+            if d >= high {
+                k += 1;
+            } else if d < low {
+                k -= 1;
+            } else {
+                return (d, k);
+            }
+        }
+    }
+
+    /// Converts the magnitude of the number to a string, with `precision`
+    /// digits after the decimal point, rounded to nearest with ties to even.
+    fn convert_to_fixed_string(&self, precision: usize) -> String {
+        if self.is_inf() || self.is_nan() {
+            return self.convert_to_string();
+        }
+
+        // The digits after the bits of the binary fraction are zeros.
+        let frac_bits = (MANTISSA as i64 - self.get_exp()).max(0) as usize;
+        let exact = if self.is_zero() {
+            0
+        } else {
+            precision.min(frac_bits)
+        };
+        let mut digits = if self.is_zero() {
+            "0".to_string()
+        } else {
+            radix_digits(self.get_scaled_integer(10, -(exact as i64)), 10)
+        };
+        while digits.len() <= exact {
+            digits.insert(0, '0');
+        }
+        if precision > 0 {
+            digits.insert(digits.len() - exact, '.');
+        }
+        digits.push_str(&"0".repeat(precision - exact));
+        digits
+    }
+
+    /// Converts the number to a string in fixed-point notation, with
+    /// `decimal_places` digits after the decimal point. The exact value of
+    /// the number is rounded to nearest, with ties to even, like "{:.N}".
+    ///```
+    ///    use arpfloat::FP128;
+    ///    let x = FP128::from_f64(-2.5);
+    ///    assert_eq!(x.to_string_fixed(3), "-2.500");
+    ///    assert_eq!(x.to_string_fixed(0), "-2");
+    ///```
+    pub fn to_string_fixed(&self, decimal_places: usize) -> String {
+        let mut result = String::new();
+        if self.get_sign() {
+            result.push('-');
+        }
+        result.push_str(&self.convert_to_fixed_string(decimal_places));
+        result
+    }
+
+    /// Converts the magnitude of the number to a string in scientific
+    /// notation in the base `radix`, with the exponent marker `e`. The number
+    /// is rounded to `precision` digits after the radix point, with ties to
+    /// even. If there is no precision then the number is printed with the
+    /// fewest decimal digits that are parsed back to the same number.
+    fn convert_to_exp_string(
+        &self,
+        radix: u64,
+        precision: Option<usize>,
+        e: char,
+    ) -> String {
+        if self.is_inf() || self.is_nan() {
+            return self.convert_to_string();
+        }
+        let (mut digits, exp, num_digits) = if self.is_zero() {
+            ("0".to_string(), 0, precision.unwrap_or(0) + 1)
+        } else if let Some(precision) = precision {
+            // The digits after the exact value are zeros.
+            let max_digits = self.get_max_significant_digits(radix);
+            let exact = max_digits.unwrap_or(usize::MAX).min(precision + 1);
+            let (d, k) = self.get_significant_digits(radix, exact);
+            (radix_digits(d, radix), k, precision + 1)
+        } else {
+            debug_assert_eq!(radix, 10);
+            let rm = RoundingMode::NearestTiesToEven;
+            let abs = if self.get_sign() { self.neg() } else { *self };
+            let mut n = 1;
+            loop {
+                let (d, k) = self.get_significant_digits(10, n);
+                let exp10 = k - n as i64 + 1;
+                let x = Self::from_big_radix(false, d.clone(), 10, exp10, rm);
+                if x == abs {
+                    break (radix_digits(d, 10), k, n);
+                }
+                n += 1;
+            }
         };
+        digits.push_str(&"0".repeat(num_digits - digits.len()));
+        if num_digits > 1 {
+            digits.insert(1, '.');
+        }
+        digits.push(e);
+        digits.push_str(&exp.to_string());
+        digits
+    }
 
+    /// Converts the number to a string in the base `radix`, between 2 and 36,
+    /// with `digits` significant digits, rounded to nearest with ties to
+    /// even. The number is printed in scientific notation, and the exponent is
+    /// the power of the radix, in decimal. The exponent follows 'e' in bases
+    /// up to 10, and '@' in larger bases, where 'e' is a digit.
+    ///```
+    ///    use arpfloat::FP32;
+    ///    let x = FP32::from_f64(-12.);
+    ///    assert_eq!(x.to_string_radix(2, 5), "-1.1000e3");
+    ///    assert_eq!(x.to_string_radix(16, 2), "-c.0@0");
+    ///```
+    pub fn to_string_radix(&self, radix: u32, digits: usize) -> String {
+        assert!((2..=36).contains(&radix), "Invalid radix");
+        assert!(digits > 0, "Invalid number of digits");
+        let e = if radix <= 10 { 'e' } else { '@' };
+        let mut result = String::new();
+        if self.get_sign() {
+            result.push('-');
+        }
+        let body =
+            self.convert_to_exp_string(radix as u64, Some(digits - 1), e);
         result.push_str(&body);
         result
     }
+
+    /// Returns the first `n` significant decimal digits of the magnitude of
+    /// the number, rounded to nearest with ties to even, and the decimal
+    /// exponent of the first digit. The digits of the fraction are generated
+    /// in chunks of up to 19 digits, by multiplying the binary fraction by
+    /// powers of ten. The number must be normal.
+    fn get_leading_digits(&self, n: usize) -> (String, i64) {
+        debug_assert!(self.is_normal());
+        let exp2 = self.get_exp() - MANTISSA as i64;
+        let frac_bits = (-exp2).max(0) as usize;
+
+        // Split the number to the integer part and the binary fraction.
+        let mut int = BigNum::from_bigint(&self.get_mantissa());
+        let mut frac = BigNum::zero();
+        if exp2 >= 0 {
+            int.shift_left(exp2 as usize);
+        } else {
+            frac = int.clone();
+            frac.mask(frac_bits);
+            int.shift_right(frac_bits);
+        }
+        let mut digits = String::new();
+        if !int.is_zero() {
+            digits = radix_digits(int, 10);
+        }
+        let mut k = digits.len() as i64 - 1;
+
+        // Multiply the fraction by powers of ten, and move the integer part
+        // to the digits. Leading zeros are skipped in whole chunks. Generate
+        // at least one more digit than needed, for the rounding.
+        while digits.len() <= n && !frac.is_zero() {
+            let chunk_len = if digits.is_empty() {
+                19
+            } else {
+                (n + 1 - digits.len()).min(19)
+            };
+            frac.inplace_mul(&BigNum::from_u64(10u64.pow(chunk_len as u32)));
+            let mut chunk = frac.clone();
+            chunk.shift_right(frac_bits);
+            frac.mask(frac_bits);
+            if digits.is_empty() {
+                if !chunk.is_zero() {
+                    digits = radix_digits(chunk, 10);
+                }
+                k -= (chunk_len - digits.len()) as i64;
+                continue;
+            }
+            let chunk = radix_digits(chunk, 10);
+            digits.push_str(&"0".repeat(chunk_len - chunk.len()));
+            digits.push_str(&chunk);
+        }
+
+        // Round with the digits after the first n digits, and the rest of
+        // the fraction as a sticky bit.
+        let round_up = match digits.as_bytes().get(n) {
+            Some(b'6'..=b'9') => true,
+            Some(b'5') => {
+                let rest = &digits.as_bytes()[n + 1..];
+                let sticky = rest.iter().any(|c| *c != b'0') || !frac.is_zero();
+                sticky || (digits.as_bytes()[n - 1] - b'0') % 2 == 1
+            }
+            _ => false,
+        };
+        digits.truncate(n);
+        if round_up {
+            let mut bytes = digits.into_bytes();
+            let mut carry = true;
+            for c in bytes.iter_mut().rev() {
+                if *c == b'9' {
+                    *c = b'0';
+                } else {
+                    *c += 1;
+                    carry = false;
+                    break;
+                }
+            }
+            if carry {
+                bytes[0] = b'1';
+                k += 1;
+            }
+            digits = String::from_utf8(bytes).unwrap();
+        }
+        digits.push_str(&"0".repeat(n - digits.len()));
+        (digits, k)
+    }
+
+    /// Converts the number to a decimal string with `n` correct significant
+    /// digits, rounded to nearest with ties to even. Numbers with a decimal
+    /// exponent from -5 to n - 1 are printed in positional notation, and the
+    /// others are printed in scientific notation, like the "%.Ng" format of
+    /// C, but the trailing zeros are kept. The digits are the digits of the
+    /// exact value of the number, so printing more digits than the precision
+    /// of the type shows the binary rounding error.
+    ///```
+    ///    use arpfloat::{FP128, FP64};
+    ///    let x = FP128::pi();
+    ///    let pi = "3.14159265358979323846264338328";
+    ///    assert_eq!(x.to_string_digits(30), pi);
+    ///    let x = FP64::from_f64(-1.5e-7);
+    ///    assert_eq!(x.to_string_digits(3), "-1.50e-7");
+    ///    let x = FP64::from_f64(0.1);
+    ///    assert_eq!(x.to_string_digits(20), "0.10000000000000000555");
+    ///```
+    pub fn to_string_digits(&self, n: usize) -> String {
+        assert!(n > 0, "Invalid number of digits");
+        let mut result = String::new();
+        if self.get_sign() {
+            result.push('-');
+        }
+        if self.is_inf() || self.is_nan() {
+            result.push_str(&self.convert_to_string());
+            return result;
+        }
+        let (mut digits, k) = if self.is_zero() {
+            ("0".repeat(n), 0)
+        } else {
+            self.get_leading_digits(n)
+        };
+        if (-5..n as i64).contains(&k) {
+            if k < 0 {
+                digits.insert_str(0, &"0".repeat(-k as usize));
+                digits.insert(1, '.');
+            } else if (k as usize) + 1 < n {
+                digits.insert(k as usize + 1, '.');
+            }
+        } else {
+            if n > 1 {
+                digits.insert(1, '.');
+            }
+            digits.push('e');
+            digits.push_str(&k.to_string());
+        }
+        result.push_str(&digits);
+        result
+    }
+
+    /// Returns the IEEE754 interchange encoding of the number, in digits of
+    /// `digit_bits` bits, with the leading zeros. See [`Float::to_bits`].
+    fn get_encoding_digits(&self, digit_bits: usize) -> String {
+        let parts = self.to_bits();
+        let bits = 1 + EXPONENT + MANTISSA;
+        let num_digits = bits.div_ceil(digit_bits);
+        let mut digits = String::new();
+        for i in (0..num_digits).rev() {
+            let mut digit = 0;
+            for j in (i * digit_bits..(i + 1) * digit_bits).rev() {
+                let bit = parts.get(j / 64).map_or(0, |p| (p >> (j % 64)) & 1);
+                digit = digit << 1 | bit;
+            }
+            digits.push(char::from_digit(digit as u32, 16).unwrap());
+        }
+        digits
+    }
+
+    /// Returns the bits of the IEEE754 interchange encoding of the number,
+    /// with the sign, the exponent and the mantissa separated by '|'.
+    ///```
+    ///    use arpfloat::FP32;
+    ///    let x = FP32::from_f64(-92.5);
+    ///    let s = "1|10000101|01110010000000000000000";
+    ///    assert_eq!(x.to_field_string(), s);
+    ///```
+    pub fn to_field_string(&self) -> String {
+        let mut digits = self.get_encoding_digits(1);
+        digits.insert(1 + EXPONENT, '|');
+        digits.insert(1, '|');
+        digits
+    }
+}
+
+/// Returns the digits of `n` in the base `radix`, in lower case.
+fn radix_digits(mut n: BigNum, radix: u64) -> String {
+    // Divide by the largest power of the radix that fits in a u64, and
+    // convert the remainders.
+    let mut chunk_len = 1;
+    while radix.checked_pow(chunk_len + 1).is_some() {
+        chunk_len += 1;
+    }
+    let chunk = BigNum::from_u64(radix.pow(chunk_len));
+    let mut digits = Vec::new();
+    while !n.is_zero() {
+        let mut rem = n.inplace_div(&chunk).as_u64();
+        for _ in 0..chunk_len {
+            let digit = char::from_digit((rem % radix) as u32, radix as u32);
+            digits.push(digit.unwrap());
+            rem /= radix;
+        }
+    }
+    while digits.len() > 1 && digits[digits.len() - 1] == '0' {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    digits.iter().rev().collect()
+}
+
+/// Returns log2(radix), as a fixed-point number with 16 fractional bits.
+fn log2_fixed(radix: u64) -> i64 {
+    debug_assert!(radix > 0);
+    // Compute the integer part, and then the fractional bits, one at a time,
+    // by squaring the normalized number in [1, 2).
+    let int = 63 - radix.leading_zeros() as i64;
+    let mut y = ((radix as u128) << 32) >> int;
+    let mut res = int << 16;
+    for i in (0..16).rev() {
+        y = (y * y) >> 32;
+        if y >= 2 << 32 {
+            y >>= 1;
+            res |= 1 << i;
+        }
+    }
+    res
+}
+
+/// Splits the optional sign from the number in `s`. Returns true if the
+/// number is negative.
+fn split_sign(s: &str) -> (bool, &str) {
+    match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    }
+}
+
+/// Returns the payload of the NaN in `s`, which is empty if there is none, or
+/// None if `s` is not a NaN. The NaNs are "@nan@" and "@snan@", and "nan" and
+/// "snan" if `words` is set, in any case, with an optional payload in
+/// parentheses, like "nan(0x123)".
+fn split_nan(s: &str, words: bool) -> Option<&str> {
+    let (name, payload) = match s.find('(') {
+        Some(idx) => (&s[..idx], s[idx + 1..].strip_suffix(')')?),
+        None => (s, ""),
+    };
+    let is_word = |w: &str| name.eq_ignore_ascii_case(w);
+    let at_words = is_word("@nan@") || is_word("@snan@");
+    if at_words || (words && (is_word("nan") || is_word("snan"))) {
+        return Some(payload);
+    }
+    None
+}
+
+/// Parses the NaN payload in `s`, in decimal, or in hex after "0x", and keeps
+/// its lowest `bits` bits. The payload of signaling NaNs is kept, and the NaN
+/// becomes quiet. The string `s` is a part of `input`.
+fn parse_nan_payload<B: DigitInt>(
+    input: &str,
+    s: &str,
+    bits: usize,
+) -> Result<B, ParseFloatError> {
+    let (radix, digits) = match s.get(..2) {
+        Some("0x") | Some("0X") => (16, &s[2..]),
+        _ => (10, s),
+    };
+    if digits.is_empty() && !s.is_empty() {
+        return Err(invalid_digit(input, s, s.len()));
+    }
+    let mut payload = B::zero();
+    for (i, c) in digits.char_indices() {
+        let d = match c.to_digit(radix) {
+            Some(d) => d as u64,
+            None => return Err(invalid_digit(input, digits, i)),
+        };
+        push_digits(&mut payload, radix as u64, d, 1);
+        payload.mask(bits);
+    }
+    Ok(payload)
+}
+
+/// Parses the decimal exponent in `s`, with an optional sign. Exponents that
+/// are too large to matter are clamped. The string `s` is a part of `input`.
+fn parse_exponent(input: &str, s: &str) -> Result<i64, ParseFloatError> {
+    let (sign, s) = split_sign(s);
+    if s.is_empty() {
+        return Err(ParseFloatError::EmptyExponent);
+    }
+    let mut e: i64 = 0;
+    for (i, c) in s.bytes().enumerate() {
+        if !c.is_ascii_digit() {
+            return Err(invalid_digit(input, s, i));
+        }
+        e = (e * 10 + (c - b'0') as i64).min(1 << 48);
+    }
+    Ok(if sign { -e } else { e })
+}
+
+/// Returns the error of the invalid character at the offset `idx` in `part`,
+/// which is a part of the string `input`.
+fn invalid_digit(input: &str, part: &str, idx: usize) -> ParseFloatError {
+    let position = part.as_ptr() as usize - input.as_ptr() as usize + idx;
+    ParseFloatError::InvalidDigit { position }
+}
+
+/// Appends the `len` digits of `chunk`, in the base `radix`, to the digits of
+/// `m`.
+fn push_digits<B: DigitInt>(m: &mut B, radix: u64, chunk: u64, len: u32) {
+    m.inplace_mul(&B::from_u64(radix.pow(len)));
+    m.inplace_add(&B::from_u64(chunk));
+}
+
+/// The integer operations of the conversion of parsed digits to floats. They
+/// are implemented by BigNum, and by fixed-size integers, which don't
+/// allocate memory.
+trait DigitInt: Sized {
+    /// The number of bits that the integer can hold.
+    const BITS: usize;
+    fn zero() -> Self;
+    fn from_u64(val: u64) -> Self;
+    fn is_zero(&self) -> bool;
+    fn msb_index(&self) -> usize;
+    fn trailing_zeros(&self) -> usize;
+    fn flip_bit(&mut self, bit_num: usize);
+    fn mask(&mut self, bits: usize);
+    fn get_loss_kind_for_bit(&self, bit: usize) -> LossFraction;
+    fn shift_left(&mut self, bits: usize);
+    fn shift_right(&mut self, bits: usize);
+    fn inplace_add(&mut self, rhs: &Self);
+    fn inplace_mul(&mut self, rhs: &Self);
+    fn inplace_div(&mut self, divisor: &Self) -> Self;
+    fn powi(&self, exp: u64) -> Self;
+    fn to_bigint<const P: usize>(&self) -> BigInt<P>;
+    fn to_big_num(&self) -> BigNum;
+}
+
+impl DigitInt for BigNum {
+    const BITS: usize = usize::MAX;
+    fn zero() -> Self {
+        BigNum::zero()
+    }
+    fn from_u64(val: u64) -> Self {
+        BigNum::from_u64(val)
+    }
+    fn is_zero(&self) -> bool {
+        BigNum::is_zero(self)
+    }
+    fn msb_index(&self) -> usize {
+        BigNum::msb_index(self)
+    }
+    fn trailing_zeros(&self) -> usize {
+        BigNum::trailing_zeros(self)
+    }
+    fn flip_bit(&mut self, bit_num: usize) {
+        BigNum::flip_bit(self, bit_num)
+    }
+    fn mask(&mut self, bits: usize) {
+        BigNum::mask(self, bits)
+    }
+    fn get_loss_kind_for_bit(&self, bit: usize) -> LossFraction {
+        BigNum::get_loss_kind_for_bit(self, bit)
+    }
+    fn shift_left(&mut self, bits: usize) {
+        BigNum::shift_left(self, bits)
+    }
+    fn shift_right(&mut self, bits: usize) {
+        BigNum::shift_right(self, bits)
+    }
+    fn inplace_add(&mut self, rhs: &Self) {
+        BigNum::inplace_add(self, rhs)
+    }
+    fn inplace_mul(&mut self, rhs: &Self) {
+        BigNum::inplace_mul(self, rhs)
+    }
+    fn inplace_div(&mut self, divisor: &Self) -> Self {
+        BigNum::inplace_div(self, divisor)
+    }
+    fn powi(&self, exp: u64) -> Self {
+        BigNum::powi(self, exp)
+    }
+    fn to_bigint<const P: usize>(&self) -> BigInt<P> {
+        BigNum::to_bigint(self)
+    }
+    fn to_big_num(&self) -> BigNum {
+        self.clone()
+    }
+}
+
+/// The callers check that the numbers fit, so the operations don't overflow.
+impl<const PARTS: usize> DigitInt for BigInt<PARTS> {
+    const BITS: usize = PARTS * 64;
+    fn zero() -> Self {
+        BigInt::zero()
+    }
+    fn from_u64(val: u64) -> Self {
+        BigInt::from_u64(val)
+    }
+    fn is_zero(&self) -> bool {
+        BigInt::is_zero(self)
+    }
+    fn msb_index(&self) -> usize {
+        BigInt::msb_index(self)
+    }
+    fn trailing_zeros(&self) -> usize {
+        BigInt::trailing_zeros(self)
+    }
+    fn flip_bit(&mut self, bit_num: usize) {
+        BigInt::flip_bit(self, bit_num)
+    }
+    fn mask(&mut self, bits: usize) {
+        BigInt::mask(self, bits)
+    }
+    fn get_loss_kind_for_bit(&self, bit: usize) -> LossFraction {
+        BigInt::get_loss_kind_for_bit(self, bit)
+    }
+    fn shift_left(&mut self, bits: usize) {
+        BigInt::shift_left(self, bits)
+    }
+    fn shift_right(&mut self, bits: usize) {
+        BigInt::shift_right(self, bits)
+    }
+    fn inplace_add(&mut self, rhs: &Self) {
+        let overflow = BigInt::inplace_add(self, rhs);
+        debug_assert!(!overflow);
+    }
+    fn inplace_mul(&mut self, rhs: &Self) {
+        let overflow = BigInt::inplace_mul(self, *rhs);
+        debug_assert!(!overflow);
+    }
+    fn inplace_div(&mut self, divisor: &Self) -> Self {
+        BigInt::inplace_div(self, *divisor)
+    }
+    fn powi(&self, exp: u64) -> Self {
+        BigInt::powi(self, exp)
+    }
+    fn to_bigint<const P: usize>(&self) -> BigInt<P> {
+        self.cast()
+    }
+    fn to_big_num(&self) -> BigNum {
+        BigNum::from_bigint(self)
+    }
+}
+
+/// Parses decimal numbers, with correct rounding to the nearest float, and
+/// ties rounded to even. See [`Float::from_str_with_rm`].
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> FromStr
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    type Err = ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_rm(s, RoundingMode::NearestTiesToEven)
+    }
 }
+
+/// Prints the number. The precision of the format, like in "{:.3}", selects
+/// the number of digits after the decimal point, and the number is rounded to
+/// nearest, with ties to even. The sign flag and the width are supported.
+/// NaNs with a payload are printed like "NaN(0x123)".
 impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Display
     for Float<EXPONENT, MANTISSA, PARTS>
 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.convert_to_string())
+        let body = match f.precision() {
+            Some(precision) => self.convert_to_fixed_string(precision),
+            None => self.convert_to_string(),
+        };
+        f.pad_integral(!self.get_sign(), "", &body)
+    }
+}
+
+/// Prints the number in scientific notation, like "1.5e-7". Without a
+/// precision the number is printed with the fewest digits that are parsed
+/// back to the same number.
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> LowerExp
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let body = self.convert_to_exp_string(10, f.precision(), 'e');
+        f.pad_integral(!self.get_sign(), "", &body)
+    }
+}
+
+/// Prints the number in scientific notation, like "1.5E-7". See LowerExp.
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> UpperExp
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let body = self.convert_to_exp_string(10, f.precision(), 'E');
+        f.pad_integral(!self.get_sign(), "", &body)
+    }
+}
+
+/// Prints the bits of the IEEE754 interchange encoding of the number, with the
+/// leading zeros, like "{:b}" of f32::to_bits. The '#' flag adds "0b".
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> Binary
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad_integral(true, "0b", &self.get_encoding_digits(1))
+    }
+}
+
+/// Prints the IEEE754 interchange encoding of the number in hex, with the
+/// leading zeros. The '#' flag adds "0x".
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> LowerHex
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad_integral(true, "0x", &self.get_encoding_digits(4))
+    }
+}
+
+/// Prints the encoding in upper case hex. See LowerHex.
+impl<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize> UpperHex
+    for Float<EXPONENT, MANTISSA, PARTS>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let digits = self.get_encoding_digits(4).to_ascii_uppercase();
+        f.pad_integral(true, "0x", &digits)
     }
 }
 
@@ -169,6 +1279,289 @@ fn test_convert_to_string() {
     assert_eq!("1995.1994999999999", to_str_w_fp64(1995.1995));
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_format_traits() {
+    use crate::utils::Lfsr;
+    use crate::{FP16, FP64};
+
+    let x = FP64::from_f64(1234.5678);
+    assert_eq!(format!("{:.2}", x), "1234.57");
+    assert_eq!(format!("{:.0}", x), "1235");
+    assert_eq!(format!("{:+.1}", x), "+1234.6");
+    assert_eq!(format!("{:>10.1}|{:<9.1}|", x, x), "    1234.6|1234.6   |");
+    assert_eq!(format!("{:010.1}", x.neg()), "-0001234.6");
+    assert_eq!(format!("{:e}", x), "1.2345678e3");
+    assert_eq!(format!("{:.3E}", x.neg()), "-1.235E3");
+    assert_eq!(format!("{:+}", FP16::from_f64(4.5)), "+4.5");
+    assert_eq!(format!("{:.3}", FP64::zero(true)), "-0.000");
+    assert_eq!(
+        format!("{:e} {:.2e}", FP64::zero(false), FP64::zero(false)),
+        "0e0 0.00e0"
+    );
+    assert_eq!(
+        format!("{:.2} {:e}", FP64::inf(true), FP64::nan(false)),
+        "-Inf NaN"
+    );
+    assert_eq!(format!("{:.1}", FP64::from_f64(0.25)), "0.2");
+    assert_eq!(format!("{:.0}", FP64::from_f64(2.5)), "2");
+    assert_eq!(format!("{:e}", FP16::from_f64(0.1)), "1e-1");
+    assert_eq!(format!("{:.5}", FP16::from_f64(0.1)), "0.09998");
+    assert_eq!(format!("{:.2e}", FP64::from_f64(9.999)), "1.00e1");
+
+    // Compare to the formatting of doubles.
+    let mut lfsr = Lfsr::new();
+    for i in 0..100 {
+        let v = f64::from_bits(lfsr.get64());
+        if !v.is_finite() {
+            continue;
+        }
+        let x = FP64::from_f64(v);
+        assert_eq!(format!("{:e}", x), format!("{:e}", v));
+        let p = i % 20;
+        assert_eq!(format!("{:.*e}", p, x), format!("{:.*e}", p, v));
+        let v = v / 2f64.powi((v.abs().log2() as i32).clamp(-60, 60));
+        let x = FP64::from_f64(v);
+        assert_eq!(format!("{:.*}", p, x), format!("{:.*}", p, v));
+    }
+    let tiny = FP64::from_f64(f64::from_bits(1));
+    assert_eq!(
+        format!("{:.1074}", tiny),
+        format!("{:.1074}", f64::from_bits(1))
+    );
+    assert_eq!(
+        format!("{:.800e}", tiny),
+        format!("{:.800e}", f64::from_bits(1))
+    );
+    assert_eq!(
+        format!("{:.3}", FP64::largest(false)),
+        format!("{:.3}", f64::MAX)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_print_wide_range() {
+    use crate::{FP128, FP256};
+
+    // The extremes of FP128.
+    let largest = FP128::largest(false);
+    let smallest = FP128::smallest(true);
+    let s = format!("{:e}", largest);
+    assert_eq!(s, "1.189731495357231765085759326628007e4932");
+    assert_eq!(s.parse::<FP128>().unwrap(), largest);
+    assert_eq!(format!("{:e}", smallest), "-6e-4966");
+    assert_eq!(format!("{:.3e}", largest), "1.190e4932");
+    assert_eq!(format!("{:.3E}", smallest), "-6.475E-4966");
+    assert_eq!(format!("{:.2}", smallest), "-0.00");
+
+    // Decimal powers far from one.
+    let x = FP128::from_decimal(1, 3900);
+    assert_eq!(format!("{:e}", x), "1e3900");
+    let s = format!("{:.40e}", x);
+    assert_eq!(s, "9.9999999999999999999999999999999999701166e3899");
+    assert_eq!(s.parse::<FP128>().unwrap(), x);
+    let x = FP128::from_decimal(-1, -3900);
+    assert_eq!(format!("{:E}", x), "-1E-3900");
+    assert_eq!(format!("{:.2}", x), "-0.00");
+    assert_eq!(format!("{:.3900}", x), format!("-0.{}1", "0".repeat(3899)));
+
+    // The extremes of FP256.
+    let largest = FP256::largest(false);
+    let smallest = FP256::smallest(false);
+    assert_eq!(format!("{:.3e}", largest), "1.611e78913");
+    assert_eq!(format!("{:.3e}", smallest), "2.248e-78984");
+    assert_eq!(format!("{:e}", smallest), "2e-78984");
+    assert_eq!(format!("{:.2}", smallest), "0.00");
+    let x = FP256::from_decimal(1, -78000);
+    assert_eq!(format!("{:e}", x), "1e-78000");
+    assert_eq!(format!("{:e}", FP256::from_decimal(-3, 78000)), "-3e78000");
+}
+
+#[test]
+fn test_to_string_fixed() {
+    use crate::{FP128, FP16, FP64};
+    let rm = RoundingMode::NearestTiesToEven;
+
+    let x = FP128::from_rational(1, 3, rm);
+    let expected = "0.33333333333333333333333333333333331728391713010637";
+    assert_eq!(x.to_string_fixed(50), expected);
+    assert_eq!(x.neg().to_string_fixed(3), "-0.333");
+    let x = FP16::from_f64(65504.);
+    assert_eq!(x.to_string_fixed(2), "65504.00");
+    assert_eq!(FP64::from_f64(0.125).to_string_fixed(2), "0.12");
+    assert_eq!(FP64::from_f64(0.375).to_string_fixed(2), "0.38");
+    assert_eq!(FP64::from_f64(999.96).to_string_fixed(1), "1000.0");
+    assert_eq!(FP64::zero(true).to_string_fixed(1), "-0.0");
+    assert_eq!(FP64::inf(false).to_string_fixed(1), "Inf");
+
+    // The extremes of FP128, which are exact integers or fractions.
+    let largest = FP128::largest(false);
+    let mut max = BigNum::from_bigint(&largest.get_mantissa());
+    max.shift_left((largest.get_exp() - 112) as usize);
+    let expected = radix_digits(max, 10) + ".00";
+    assert_eq!(largest.to_string_fixed(2), expected);
+    assert_eq!(expected.len(), 4936);
+    let x = FP128::from_decimal(1, 3900).neg().to_string_fixed(1);
+    assert!(x.starts_with("-999999999999999999999999999999999997011655415"));
+    assert_eq!(x.len(), 3903);
+    let x = FP128::smallest(false).to_string_fixed(4975);
+    let expected = "0.".to_string() + &"0".repeat(4965) + "6475175119";
+    assert_eq!(x, expected);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_string_radix() {
+    use crate::{FP16, FP64};
+    let x = FP64::from_f64(-12.);
+    assert_eq!(x.to_string_radix(2, 5), "-1.1000e3");
+    assert_eq!(x.to_string_radix(16, 2), "-c.0@0");
+    assert_eq!(x.to_string_radix(36, 1), "-c@0");
+    assert_eq!(x.to_string_radix(3, 4), "-1.100e2");
+    assert_eq!(x.to_string_radix(10, 1), "-1e1");
+
+    let x = FP64::from_f64(0.1);
+    assert_eq!(x.to_string_radix(16, 14), "1.999999999999a@-1");
+    assert_eq!(x.to_string_radix(2, 3), "1.10e-4");
+    assert_eq!(x.to_string_radix(3, 6), "2.20022e-3");
+    assert_eq!(x.to_string_radix(8, 2), "6.3e-2");
+    let x = FP16::from_f64(65504.);
+    assert_eq!(x.to_string_radix(2, 12), "1.11111111110e15");
+    assert_eq!(x.to_string_radix(36, 4), "1.ejk@3");
+    assert_eq!(x.to_string_radix(36, 2), "1.f@3");
+
+    // Ties are rounded to even.
+    assert_eq!(FP64::from_f64(2.5).to_string_radix(10, 1), "2e0");
+    assert_eq!(FP64::from_f64(0.75).to_string_radix(2, 2), "1.1e-1");
+    assert_eq!(FP64::from_f64(0.75).to_string_radix(2, 1), "1e0");
+
+    assert_eq!(FP64::zero(true).to_string_radix(16, 3), "-0.00@0");
+    assert_eq!(FP64::nan(false).to_string_radix(16, 3), "NaN");
+    assert_eq!(FP64::inf(true).to_string_radix(8, 3), "-Inf");
+
+    // Decimal output matches the LowerExp formatting.
+    for v in [1e300, 3.5e-310, 1. / 3., 123456.789] {
+        let x = FP64::from_f64(v);
+        assert_eq!(x.to_string_radix(10, 12), format!("{:.11e}", x));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_string_digits() {
+    use crate::utils::Lfsr;
+    use crate::{FP128, FP16, FP256, FP64};
+    let x = FP64::from_f64(123.456);
+    assert_eq!(x.to_string_digits(1), "1e2");
+    assert_eq!(x.to_string_digits(3), "123");
+    assert_eq!(x.to_string_digits(5), "123.46");
+    assert_eq!(x.neg().to_string_digits(8), "-123.45600");
+    assert_eq!(FP64::from_f64(9.9996).to_string_digits(4), "10.00");
+    assert_eq!(FP64::from_f64(999.7).to_string_digits(3), "1.00e3");
+    assert_eq!(FP64::from_f64(0.000125).to_string_digits(2), "0.00013");
+    assert_eq!(FP64::from_f64(1.5e-6).to_string_digits(2), "1.5e-6");
+    assert_eq!(FP64::from_f64(2.5).to_string_digits(1), "2");
+    assert_eq!(FP64::from_f64(1e300).to_string_digits(3), "1.00e300");
+    assert_eq!(FP16::from_f64(65504.).to_string_digits(6), "65504.0");
+    assert_eq!(FP64::zero(true).to_string_digits(3), "-0.00");
+    assert_eq!(FP64::inf(false).to_string_digits(3), "Inf");
+    let x = FP64::from_f64(f64::from_bits(1));
+    assert_eq!(x.to_string_digits(4), "4.941e-324");
+
+    // Printing more digits than the precision shows the exact value.
+    let x = FP64::from_f64(0.1);
+    let s = "0.1000000000000000055511151231257827021181583404541015625";
+    assert_eq!(x.to_string_digits(55), s);
+    assert_eq!(x.to_string_digits(60), s.to_string() + "00000");
+    let pi = "3.141592653589793238462643383279502884197169399375105820975";
+    assert_eq!(FP256::pi().to_string_digits(58), pi);
+
+    // Compare to the exact formatting of the standard library.
+    let mut lfsr = Lfsr::new();
+    for i in 0..200 {
+        let v = f64::from_bits(lfsr.get64());
+        if !v.is_finite() || v == 0. {
+            continue;
+        }
+        let n = 1 + i % 60;
+        let s = FP64::from_f64(v).to_string_digits(n);
+        let (digits, exp) = s.split_once('e').unwrap_or((&s, "0"));
+        let expected = format!("{:.*e}", n - 1, v);
+        let (expected, expected_exp) = expected.split_once('e').unwrap();
+        let k: i64 = expected_exp.parse().unwrap();
+        let digits: String = digits.chars().filter(|c| *c != '.').collect();
+        let digits = digits.trim_start_matches(['-', '0']);
+        let expected: String = expected.chars().filter(|c| *c != '.').collect();
+        let expected = expected.trim_start_matches('-');
+        assert_eq!(digits, expected, "{}", s);
+        if exp != "0" {
+            assert_eq!(exp.parse::<i64>().unwrap(), k, "{}", s);
+        }
+    }
+
+    // Enough digits to parse back the numbers of a wide type.
+    type FP1500 = crate::new_float_type!(20, 1500);
+    let x = FP1500::pi() / FP1500::from_u64(7);
+    let s = x.to_string_digits(460);
+    assert_eq!(s.parse::<FP1500>().unwrap(), x);
+    assert!(s.starts_with("0.448798950512827605494663340468500412"));
+    type FP4096 = crate::new_float_type!(20, 4096);
+    let rm = RoundingMode::NearestTiesToEven;
+    let x = FP4096::from_rational(1, 3, rm);
+    assert_eq!(
+        x.to_string_digits(1000),
+        "0.".to_string() + &"3".repeat(1000)
+    );
+
+    // The extremes of the exponent range.
+    let x = FP128::from_hex_str("0x1p-12000").unwrap();
+    assert_eq!(x.to_string_digits(3), "4.37e-3613");
+    assert_eq!(FP128::largest(true).to_string_digits(5), "-1.1897e4932");
+    let s = FP128::smallest(false).to_string_digits(40);
+    assert_eq!(s, "6.475175119438025110924438958227646552500e-4966");
+    assert_eq!(FP256::largest(false).to_string_digits(5), "1.6113e78913");
+    assert_eq!(FP256::smallest(false).to_string_digits(5), "2.2480e-78984");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bit_formatting() {
+    use crate::utils::Lfsr;
+    use crate::{FP128, FP16, FP32, FP64};
+    let x = FP32::from_f64(-92.5);
+    assert_eq!(x.to_field_string(), "1|10000101|01110010000000000000000");
+    assert_eq!(format!("{:x}", x), "c2b90000");
+    assert_eq!(format!("{:#X}", x), "0xC2B90000");
+    let s = format!("{:b}", FP16::from_f64(1.0));
+    assert_eq!(s, "0011110000000000");
+    assert_eq!(format!("{:#b}", FP16::from_f64(-0.0)), "0b1000000000000000");
+    assert_eq!(FP16::inf(false).to_field_string(), "0|11111|0000000000");
+    assert_eq!(FP16::nan(false).to_field_string(), "0|11111|1000000000");
+    assert_eq!(format!("{:>8x}", FP16::from_f64(2.0)), "    4000");
+
+    // Formats that don't fill whole hex digits have leading zeros.
+    let x = FP128::from_u64(1);
+    assert_eq!(format!("{:x}", x), "3fff0000000000000000000000000000");
+    let x = crate::Float::<8, 10, 1>::from_f64(-1.0);
+    assert_eq!(format!("{:x}", x), "5fc00");
+    assert_eq!(format!("{:b}", x).len(), 19);
+
+    // The encodings match the native numbers.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..200 {
+        let v = f64::from_bits(lfsr.get64());
+        if v.is_nan() {
+            continue;
+        }
+        let x = FP64::from_f64(v);
+        assert_eq!(format!("{:b}", x), format!("{:064b}", v.to_bits()));
+        assert_eq!(format!("{:x}", x), format!("{:016x}", v.to_bits()));
+        let field = x.to_field_string().replace('|', "");
+        assert_eq!(field, format!("{:b}", x));
+    }
+}
+
 #[test]
 fn test_fuzz_printing() {
     use crate::utils;
@@ -204,8 +1597,6 @@ fn test_print_sqrt() {
 #[test]
 #[cfg(feature = "std")]
 fn test_readme_example() {
-    use crate::new_float_type;
-
     // Create a new type: 15 bits exponent, 112 significand.
     type FP128 = new_float_type!(15, 112);
 
@@ -226,6 +1617,518 @@ fn test_readme_example() {
     fp.dump();
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_from_decimal() {
+    use crate::utils::Lfsr;
+    use crate::{FP128, FP16, FP256, FP64};
+    use RoundingMode::*;
+
+    assert_eq!(FP64::from_decimal(1, -1).as_f64(), 0.1);
+    assert_eq!(FP64::from_decimal(-123456, -5).as_f64(), -1.23456);
+    assert_eq!(FP64::from_decimal(17, 300).as_f64(), 17e300);
+    assert_eq!(FP64::from_decimal(i128::MAX, -380).as_f64(), 1.7014118e-342);
+    let x = FP128::from_decimal(1, 1000);
+    assert_eq!(x, FP128::from_decimal(10, 999));
+
+    // Compare to the correctly rounded parser of the standard library.
+    let mut lfsr = Lfsr::new();
+    for i in 0..2000 {
+        let m = (lfsr.get64() >> (i % 64)) as i128;
+        let e = (lfsr.get64() % 700) as i64 - 360;
+        let expected: f64 = format!("{}e{}", m, e).parse().unwrap();
+        let x = FP64::from_decimal(m, e);
+        assert_eq!(x.as_f64().to_bits(), expected.to_bits());
+    }
+
+    // The directed rounding modes match the exact ratio of two integers.
+    for i in 0..1000 {
+        let m = (lfsr.get64() >> (i % 63 + 1)) as i128;
+        let e = (lfsr.get64() % 39) as i64 - 19;
+        let (num, den) = if e >= 0 {
+            (m * 10i128.pow(e as u32), 1)
+        } else {
+            (m, 10i128.pow(-e as u32))
+        };
+        for rm in [Zero, Positive, Negative, NearestTiesToAway] {
+            let expected = FP64::from_rational(num, den, rm);
+            assert_eq!(FP64::from_decimal_with_rm(m, e, rm), expected);
+            let expected = FP16::from_rational(-num, den, rm);
+            assert_eq!(FP16::from_decimal_with_rm(-m, e, rm), expected);
+        }
+    }
+
+    // Overflow and underflow.
+    assert!(FP16::from_decimal(1, 5).is_inf());
+    assert!(FP16::from_decimal_with_rm(1, 5, Zero).is_normal());
+    assert!(FP64::from_decimal(-1, 400).is_inf());
+    assert!(FP64::from_decimal(1, -400).is_zero());
+    let x = FP64::from_decimal_with_rm(1, -400, Positive);
+    assert_eq!(x.as_f64(), f64::from_bits(1));
+    let x = FP64::from_decimal_with_rm(-1, -1000, Negative);
+    assert_eq!(x.as_f64(), -f64::from_bits(1));
+
+    // The whole range of the wide types.
+    let x = FP128::from_decimal(1, -3000);
+    assert!(x.is_normal());
+    assert_eq!(x, FP128::from_decimal(1000, -3003));
+    assert_eq!(x, FP256::from_decimal(1, -3000).cast());
+    let x = FP128::from_decimal(-7, 4000);
+    assert_eq!(x, FP256::from_decimal(-7, 4000).cast());
+    let x = FP128::from_decimal_with_rm(1, 4933, Zero);
+    assert_eq!(x, FP128::largest(false));
+    assert!(FP128::from_decimal(1, 4933).is_inf());
+    let x = FP128::from_decimal_with_rm(1, -4966, Positive);
+    assert_eq!(x, FP128::smallest(false));
+    assert!(FP128::from_decimal(1, -4966).is_zero());
+    assert!(FP256::from_decimal(i128::MAX, 78000).is_normal());
+    assert!(FP256::from_decimal(i128::MIN, -78000).is_normal());
+    assert!(FP256::from_decimal(1, -80000).is_zero());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_from_str() {
+    use crate::utils::Lfsr;
+    use crate::{FP16, FP32, FP64};
+    use std::string::String;
+    use RoundingMode::*;
+
+    let parse = |s: &str| s.parse::<FP64>().unwrap().as_f64();
+    assert_eq!(parse("3.14159e-10"), 3.14159e-10);
+    assert_eq!(parse("-0.000123"), -0.000123);
+    assert_eq!(parse("+12."), 12.);
+    assert_eq!(parse(".5E+2"), 50.);
+    assert_eq!(parse("007"), 7.);
+    assert_eq!(parse("1e0000000000000000000000000000000001"), 10.);
+    assert!(parse("-0.0").is_sign_negative());
+    assert!(parse("-InFinity").is_infinite());
+    assert!(parse("inf") > 0.);
+    assert!(parse("NaN").is_nan());
+    assert_eq!(parse("1e99999999999999999999"), f64::INFINITY);
+    assert_eq!(parse("-1e-99999999999999999999"), -0.);
+    for s in ["", "-", ".", "e5", "1e", "1e+", "1.2.3", "--1", " 1", "1f"] {
+        assert!(s.parse::<FP64>().is_err(), "{}", s);
+    }
+
+    // Subnormals, and halfway cases with many digits.
+    assert_eq!(parse("4.9406564584124654e-324"), f64::from_bits(1));
+    assert_eq!(parse("2.4703282292062327e-324"), 0.);
+    assert_eq!(parse("2.4703282292062328e-324"), f64::from_bits(1));
+    assert_eq!(parse("9007199254740993"), 9007199254740992.);
+    let s = String::from("9007199254740993") + &"0".repeat(900) + "1e-901";
+    assert_eq!(parse(&s), 9007199254740994.);
+    let mut pow5 = BigNum::from_u64(5).powi(1075);
+    let mut half = String::new();
+    while !pow5.is_zero() {
+        let digit = pow5.inplace_div(&BigNum::from_u64(10)).as_u64();
+        half.insert(0, char::from(b'0' + digit as u8));
+    }
+    assert_eq!(parse(&(half.clone() + "e-1075")), 0.);
+    assert_eq!(parse(&(half + "1e-1076")), f64::from_bits(1));
+
+    // Compare to the correctly rounded parser of the standard library.
+    let mut lfsr = Lfsr::new();
+    for i in 0..2000 {
+        let m = lfsr.get64() >> (i % 64);
+        let e = (lfsr.get64() % 700) as i64 - 360;
+        let digits = m.to_string();
+        let dot = (lfsr.get64() as usize) % (digits.len() + 1);
+        let s = format!("{}.{}e{}", &digits[..dot], &digits[dot..], e);
+        let expected: f64 = s.parse().unwrap();
+        assert_eq!(parse(&s).to_bits(), expected.to_bits(), "{}", s);
+        let expected: f32 = s.parse().unwrap();
+        let x: FP32 = s.parse().unwrap();
+        assert_eq!(x.as_f32().to_bits(), expected.to_bits(), "{}", s);
+    }
+
+    // The rounding modes match from_decimal.
+    for i in 0..500 {
+        let m = (lfsr.get64() >> (i % 64)) as i128;
+        let e = (lfsr.get64() % 20) as i64 - 14;
+        let s = format!("-{}e{}", m, e);
+        for rm in [Zero, Positive, Negative, NearestTiesToAway] {
+            let x = FP16::from_str_with_rm(&s, rm).unwrap();
+            assert_eq!(x, FP16::from_decimal_with_rm(-m, e, rm));
+        }
+    }
+
+    // Printed numbers are parsed back to the same number.
+    for v in [1.5, -1e-310, 123456.789, 0.1, f64::MAX] {
+        let x = FP64::from_f64(v);
+        assert_eq!(parse(&x.to_string()), v);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parse_wide_range() {
+    use crate::{FP128, FP256};
+
+    // The exact decimal values of powers of two are parsed to the powers.
+    let pow2 = |e: i64| {
+        if e >= 0 {
+            let mut pow = BigNum::one();
+            pow.shift_left(e as usize);
+            radix_digits(pow, 10)
+        } else {
+            // 2^e = 5^-e * 10^e.
+            let pow = BigNum::from_u64(5).powi(e.unsigned_abs());
+            format!("{}e{}", radix_digits(pow, 10), e)
+        }
+    };
+    for e in [2000, 6643, 16383, -2000, -6644, -16382, -16494] {
+        let x: FP128 = pow2(e).parse().unwrap();
+        assert_eq!(x, FP128::from_hex_str(&format!("0x1p{}", e)).unwrap());
+    }
+    for e in [70000, 262143, -70000] {
+        let x: FP256 = pow2(e).parse().unwrap();
+        assert_eq!(x, FP256::from_hex_str(&format!("0x1p{}", e)).unwrap());
+    }
+
+    // Decimal powers in the whole range of FP128.
+    for e in [2000, 4000, 4932, -2000, -4000, -4931, -4965] {
+        let x: FP128 = format!("1e{}", e).parse().unwrap();
+        assert_eq!(x, FP128::from_decimal(1, e));
+        let wide: FP256 = format!("1e{}", e).parse().unwrap();
+        assert_eq!(x, wide.cast());
+        assert!(x.is_finite() && !x.is_zero());
+    }
+    let largest = "1.18973149535723176508575932662800702e4932";
+    assert_eq!(largest.parse::<FP128>().unwrap(), FP128::largest(false));
+    let smallest = "-6.4751751194380251109244389582276466e-4966";
+    assert_eq!(smallest.parse::<FP128>().unwrap(), FP128::smallest(true));
+
+    // Only the numbers outside of the range are rounded to infinity or zero.
+    assert!("1e4933".parse::<FP128>().unwrap().is_inf());
+    assert!("1e-4966".parse::<FP128>().unwrap().is_zero());
+    assert!("-1e100000".parse::<FP256>().unwrap().is_inf());
+    assert!("1e-100000".parse::<FP256>().unwrap().is_zero());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_from_hex_str() {
+    use crate::utils::Lfsr;
+    use crate::{FP128, FP16, FP32, FP64};
+    use RoundingMode::*;
+
+    let parse = |s: &str| FP64::from_hex_str(s).unwrap().as_f64();
+    assert_eq!(parse("0x1.8p+3"), 12.);
+    assert_eq!(parse("-0X1P-2"), -0.25);
+    assert_eq!(parse("0x.8"), 0.5);
+    assert_eq!(parse("0xA."), 10.);
+    assert_eq!(parse("0x00ff.f0p4"), 4095.);
+    assert_eq!(parse("0x1.fffffffffffffp1023"), f64::MAX);
+    assert_eq!(parse("0x0.0000000000001p-1022"), f64::from_bits(1));
+    assert_eq!(parse("0x1p-1075"), 0.);
+    assert_eq!(parse("0x1.0000000000001p-1075"), f64::from_bits(1));
+    assert_eq!(parse("0x1p1024"), f64::INFINITY);
+    assert_eq!(parse("-0x1p-99999999999999999999"), -0.);
+    assert!(parse("-0x0p0").is_sign_negative());
+    for s in [
+        "", "0x", "0x.", "1.5", "0xp3", "0x1p", "0x1.2.3", "0x1g", "x1",
+    ] {
+        assert!(FP64::from_hex_str(s).is_err(), "{}", s);
+    }
+
+    let x = FP32::from_hex_str("-0x1.fffffep127").unwrap();
+    assert_eq!(x.as_f32(), f32::MIN);
+    let zeros = "0".repeat(27);
+    let x = FP128::from_hex_str(&format!("0x1.{}08p0", zeros)).unwrap();
+    assert_eq!(x, FP128::one(false));
+    let x = FP128::from_hex_str(&format!("0x1.{}18p0", zeros)).unwrap();
+    let y = FP128::from_hex_str(&format!("0x1.{}2p0", zeros)).unwrap();
+    assert_eq!(x, y);
+
+    // Rounding of digits that are below the precision, with a sticky digit.
+    let x = FP16::from_hex_str("0x1.00200000000000000000000001").unwrap();
+    assert_eq!(x.as_f64(), 1. + 2f64.powi(-10));
+    let x = FP16::from_hex_str_with_rm("0x1.001ff", Positive).unwrap();
+    assert_eq!(x.as_f64(), 1. + 2f64.powi(-10));
+    let x = FP16::from_hex_str_with_rm("-0x1.003ff", Zero).unwrap();
+    assert_eq!(x.as_f64(), -1.);
+
+    // Printed f64 numbers are parsed back exactly.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..1000 {
+        let v = f64::from_bits(lfsr.get64());
+        if !v.is_finite() {
+            continue;
+        }
+        let bits = v.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = bits & ((1 << 52) - 1);
+        let sign = if v < 0. { "-" } else { "" };
+        let s = if exp == 0 {
+            format!("{}0x0.{:013x}p-1022", sign, fraction)
+        } else {
+            format!("{}0x1.{:013x}p{}", sign, fraction, exp - 1023)
+        };
+        assert_eq!(parse(&s).to_bits(), bits, "{}", s);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_from_str_radix() {
+    use crate::utils::Lfsr;
+    use crate::{FP16, FP64};
+    use RoundingMode::*;
+
+    let rm = NearestTiesToEven;
+    let parse = |s: &str, radix| FP64::from_str_radix(s, radix, rm);
+    let parse = |s: &str, radix| parse(s, radix).unwrap().as_f64();
+    assert_eq!(parse("-1.1e5", 2), -48.);
+    assert_eq!(parse("z.8@1", 36), 1268.);
+    assert_eq!(parse("Z.8@+1", 36), 1268.);
+    assert_eq!(parse("ff.8", 16), 255.5);
+    assert_eq!(parse(".2@-1", 16), 2. / 256.);
+    assert_eq!(parse("12e1", 3), 15.);
+    assert_eq!(parse("12@1", 3), 15.);
+    assert_eq!(parse("1.5e-7", 10), 1.5e-7);
+    assert_eq!(parse("inf", 36), 24171.);
+    assert!(parse("-Infinity", 16).is_infinite());
+    assert!(parse("@NaN@", 36).is_nan());
+    assert!(parse("-0.000", 7).is_sign_negative());
+    assert_eq!(parse("1@99999999999999999999", 3), f64::INFINITY);
+    assert_eq!(parse("1@-99999999999999999999", 35), 0.);
+    for (s, radix) in [("2", 2), ("1p1", 16), ("", 8), ("1@", 4), ("z", 35)] {
+        assert!(FP64::from_str_radix(s, radix, rm).is_err(), "{}", s);
+    }
+
+    // Halfway cases, and digits below the precision.
+    let s = String::from("1") + &"0".repeat(52) + "1";
+    assert_eq!(parse(&s, 2), 2f64.powi(53));
+    assert_eq!(parse(&(s.clone() + ".0000000001"), 2), 2f64.powi(53) + 2.);
+    let s = String::from("1") + &"0".repeat(3000) + "1@-3001";
+    assert_eq!(parse(&s, 2), 1.);
+    assert_eq!(
+        FP64::from_str_radix(&s, 2, Positive).unwrap().as_f64(),
+        1. + f64::EPSILON
+    );
+    let x = FP16::from_str_radix(&s, 3, Positive).unwrap();
+    assert_eq!(x.as_f64(), 1. + 2f64.powi(-10));
+    for radix in [3, 34, 35] {
+        let e = 3000 + 1040 * 65536 / log2_fixed(radix as u64);
+        let s = String::from("1") + &"2".repeat(3000) + &format!("@-{}", e);
+        let x = parse(&s, radix);
+        assert!(x > 0. && x < 2f64.powi(-1000));
+    }
+
+    // Fractions in the odd bases are rounded like the divisions.
+    for radix in [3, 7, 9, 25, 35] {
+        let a = FP64::one(false);
+        let b = FP64::from_u64(radix as u64);
+        for rm in [NearestTiesToEven, Zero, Positive, Negative] {
+            let x = FP64::from_str_radix("0.1", radix, rm).unwrap();
+            assert_eq!(x, FP64::div_with_rm(a, b, rm));
+        }
+    }
+
+    // Hex numbers match the C99 syntax, and printed numbers in all the bases
+    // are parsed back to the same number.
+    let mut lfsr = Lfsr::new();
+    for i in 0..300 {
+        let v = f64::from_bits(lfsr.get64());
+        if !v.is_finite() {
+            continue;
+        }
+        let x = FP64::from_f64(v);
+        let e = (lfsr.get64() % 600) as i64 - 300;
+        let s = format!("{:x}.{:x}", lfsr.get64(), lfsr.get64());
+        let y = FP64::from_str_radix(&format!("{}@{}", s, e), 16, rm);
+        let z = FP64::from_hex_str(&format!("0x{}p{}", s, 4 * e));
+        assert_eq!(y.unwrap(), z.unwrap());
+        let radix = 2 + i % 35;
+        let digits = 54 * 65536 / log2_fixed(radix as u64) as usize + 2;
+        let s = x.to_string_radix(radix, digits);
+        assert_eq!(parse(&s, radix).to_bits(), v.to_bits(), "{}", s);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parse_special_values() {
+    use crate::{BigInt, FP16, FP64};
+    let payload = |x: FP64| x.get_nan_payload().unwrap().as_u64();
+    let parse = |s: &str| s.parse::<FP64>().unwrap();
+
+    assert_eq!(parse("inf"), FP64::inf(false));
+    assert_eq!(parse("-infinity"), FP64::inf(true));
+    assert_eq!(parse("+INF"), FP64::inf(false));
+    assert!(parse("-nan").is_nan() && parse("-nan").get_sign());
+    assert_eq!(payload(parse("nan")), 0);
+    assert_eq!(payload(parse("nan()")), 0);
+    assert_eq!(payload(parse("NaN(0x123)")), 0x123);
+    assert_eq!(payload(parse("nan(291)")), 0x123);
+    assert_eq!(payload(parse("sNaN(0x123)")), 0x123);
+    assert_eq!(payload(parse("-snan")), 0);
+    let quiet = 0x7ff8_0000_0000_0000_u64;
+    assert_eq!(parse("snan(0x123)").as_f64().to_bits(), quiet | 0x123);
+
+    // The payload keeps the bits below the quiet bit.
+    let x = parse("nan(0xfffffffffffffffff)");
+    assert_eq!(payload(x), (1 << 51) - 1);
+    let x: FP16 = "nan(0x3ff)".parse().unwrap();
+    assert_eq!(x.get_nan_payload().unwrap().as_u64(), 0x1ff);
+
+    let rm = RoundingMode::NearestTiesToEven;
+    let x = FP64::from_str_radix("@sNaN@(0x5)", 36, rm).unwrap();
+    assert_eq!(payload(x), 5);
+    let x = FP64::from_str_radix("nan(5)", 16, rm).unwrap();
+    assert_eq!(payload(x), 5);
+    for s in [
+        "nan(",
+        "nan)",
+        "nan(0x)",
+        "nan(12a)",
+        "nan(-1)",
+        "nan(1)x",
+        "snan1",
+        "qnan",
+        "infinity(1)",
+    ] {
+        assert!(s.parse::<FP64>().is_err(), "{}", s);
+    }
+
+    // The special values round-trip through strings.
+    let vals = [
+        FP64::inf(true),
+        FP64::inf(false),
+        FP64::nan(true),
+        FP64::nan_with_payload(false, BigInt::from_u64(0xabc)),
+        FP64::nan_with_payload(true, BigInt::from_u64(1)),
+    ];
+    for x in vals {
+        let s = x.to_string();
+        let y = parse(&s);
+        assert_eq!(x.get_sign(), y.get_sign(), "{}", s);
+        assert_eq!(x.get_nan_payload(), y.get_nan_payload(), "{}", s);
+        assert_eq!(x.is_inf(), y.is_inf(), "{}", s);
+    }
+    let x = FP64::nan_with_payload(true, BigInt::from_u64(0xabc));
+    assert_eq!(x.to_string(), "-NaN(0xabc)");
+    assert_eq!(format!("{:e}", x), "-NaN(0xabc)");
+}
+
+#[test]
+fn test_parse_bytes() {
+    use crate::{FP128, FP16, FP64};
+    let parse = |s: &[u8]| FP64::parse_bytes(s).unwrap().as_f64();
+    assert_eq!(parse(b"3.25"), 3.25);
+    assert_eq!(parse(b"-1e-3"), -1e-3);
+    assert_eq!(parse(b"1.7976931348623157e308"), f64::MAX);
+    assert!(parse(b"-inf").is_infinite());
+    assert!(parse(b"nan(0x12)").is_nan());
+    for s in [&b""[..], b"1.5\xff", b"\xc3\xa9", b"1 ", b"0x10"] {
+        assert!(FP64::parse_bytes(s).is_err());
+    }
+
+    // The results match the parsing of strings.
+    for s in ["0.1", "65519.99", "1e-8", "123456789012345678901234567890"] {
+        let x = FP16::parse_bytes(s.as_bytes()).unwrap();
+        assert_eq!(x, s.parse::<FP16>().unwrap());
+        let x = FP128::parse_bytes(s.as_bytes()).unwrap();
+        assert_eq!(x, s.parse::<FP128>().unwrap());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parse_bytes_without_allocation() {
+    use crate::utils::count_allocations;
+    use crate::{FP128, FP16, FP256, FP32, FP64};
+    use std::string::String;
+    use std::vec::Vec;
+
+    // Long significands, numbers near the ends of the range, and errors.
+    let digits = "1234567890".repeat(90);
+    let inputs: Vec<String> = [
+        "0.1",
+        "-1.7976931348623157e308",
+        "2.4703282292062328e-324",
+        "1e400",
+        "1e-400",
+        "nan(0x12)",
+        "-inf",
+        "1.2.3",
+        &(String::from("0.") + &"0".repeat(300) + &digits + "e-20"),
+        &(digits.clone() + "e-1200"),
+        &(digits.clone() + "e-500"),
+        &(String::from("9").repeat(900) + "e-600"),
+    ]
+    .iter()
+    .map(|s| String::from(*s))
+    .collect();
+
+    macro_rules! check_type {
+        ($t:ty) => {
+            let expected: Vec<_> =
+                inputs.iter().map(|s| s.parse::<$t>().ok()).collect();
+            let n = count_allocations(|| {
+                for (s, e) in inputs.iter().zip(expected.iter()) {
+                    let x = <$t>::parse_bytes(s.as_bytes()).ok();
+                    assert!(x == *e || x.is_some_and(|x| x.is_nan()));
+                }
+            });
+            assert_eq!(n, 0);
+        };
+    }
+    check_type!(FP16);
+    check_type!(FP32);
+    check_type!(FP64);
+
+    // The wide formats convert the large numbers on the heap.
+    for s in inputs.iter().filter(|s| !s.contains("nan")) {
+        let x = FP128::parse_bytes(s.as_bytes()).ok();
+        assert_eq!(x, s.parse::<FP128>().ok());
+        let x = FP256::parse_bytes(s.as_bytes()).ok();
+        assert_eq!(x, s.parse::<FP256>().ok());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parse_errors() {
+    use crate::{FP128, FP64};
+    use std::string::ToString;
+    use ParseFloatError::*;
+    let err = |s: &str| s.parse::<FP64>().unwrap_err();
+    let at = |position| InvalidDigit { position };
+    assert_eq!(err(""), EmptyMantissa);
+    assert_eq!(err("-"), EmptyMantissa);
+    assert_eq!(err("-.e5"), EmptyMantissa);
+    assert_eq!(err("1e"), EmptyExponent);
+    assert_eq!(err("1e+"), EmptyExponent);
+    assert_eq!(err("1f"), at(1));
+    assert_eq!(err(" 1"), at(0));
+    assert_eq!(err("-1.2.3"), at(4));
+    assert_eq!(err("1e5x"), at(3));
+    assert_eq!(err("1e+-5"), at(3));
+    assert_eq!(err("+12é3"), at(3));
+    assert_eq!(err("nan(0x)"), at(6));
+    assert_eq!(err("-nan(0x1g)"), at(8));
+    assert_eq!(err("nan("), at(0));
+
+    let rm = RoundingMode::NearestTiesToEven;
+    let x = FP64::from_str_radix("1.21", 2, rm);
+    assert_eq!(x.unwrap_err(), at(2));
+    assert_eq!(FP64::from_hex_str("0x1.8p").unwrap_err(), EmptyExponent);
+    assert_eq!(FP64::from_hex_str("-0x").unwrap_err(), EmptyMantissa);
+    assert_eq!(FP64::from_hex_str("-0x1.g").unwrap_err(), at(5));
+    assert_eq!(FP64::from_hex_str("-1.5").unwrap_err(), at(1));
+    assert_eq!(FP64::parse_bytes(b"1.5\xff").unwrap_err(), at(3));
+
+    // Numbers in the range of the type are not errors.
+    assert!("1e4000".parse::<FP128>().unwrap().is_normal());
+    assert!("1e-4000".parse::<FP128>().unwrap().is_normal());
+
+    let msg = err("12x").to_string();
+    assert_eq!(msg, "invalid digit at position 2 in float literal");
+    assert_eq!(err("").to_string(), "no digits in float literal");
+}
+
 #[test]
 fn test_decimal_accuracy_for_type() {
     use crate::{FP128, FP16, FP256, FP32, FP64};
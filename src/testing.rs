@@ -0,0 +1,5 @@
+//! Utilities for testing code that uses this crate. The `proptest` feature
+//! enables the [`strategies`] module, with proptest strategies that generate
+//! floats of any format.
+
+pub mod strategies;
@@ -0,0 +1,221 @@
+//! Proptest strategies that generate floats of any format. The strategies
+//! build the numbers from random signs, exponents and significand bits, so
+//! every encoding of the requested kind can be generated, including the
+//! extreme exponents and the NaN payloads.
+//!```
+//!    use arpfloat::testing::strategies;
+//!    use arpfloat::FP64;
+//!    use proptest::prelude::*;
+//!    use proptest::test_runner::TestRunner;
+//!
+//!    let pairs = (strategies::finite(), strategies::finite());
+//!    let mut runner = TestRunner::default();
+//!    let res = runner.run(&pairs, |(x, y): (FP64, FP64)| {
+//!        prop_assert_eq!(x * y, y * x);
+//!        Ok(())
+//!    });
+//!    assert!(res.is_ok());
+//!```
+
+use crate::{BigInt, Float};
+use core::num::FpCategory;
+use proptest::prelude::*;
+use proptest::strategy::BoxedStrategy;
+
+/// Returns a strategy for the lowest `MANTISSA` bits of a significand.
+fn fraction<const MANTISSA: usize, const PARTS: usize>(
+) -> impl Strategy<Value = BigInt<PARTS>> {
+    proptest::array::uniform::<_, PARTS>(any::<u64>()).prop_map(|parts| {
+        let mut mantissa = BigInt::from_parts(&parts);
+        mantissa.mask(MANTISSA);
+        mantissa
+    })
+}
+
+/// Returns a strategy for the normal numbers of the format, with any sign and
+/// exponent.
+pub fn normal<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>() -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    let (min_exp, max_exp) =
+        Float::<EXPONENT, MANTISSA, PARTS>::get_exp_bounds();
+    (
+        any::<bool>(),
+        min_exp..=max_exp,
+        fraction::<MANTISSA, PARTS>(),
+    )
+        .prop_map(|(sign, exp, mut mantissa)| {
+            mantissa.flip_bit(MANTISSA);
+            Float::new(sign, exp, mantissa)
+        })
+        .boxed()
+}
+
+/// Returns a strategy for the subnormal numbers of the format.
+pub fn subnormal<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>() -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    let (min_exp, _) = Float::<EXPONENT, MANTISSA, PARTS>::get_exp_bounds();
+    (any::<bool>(), fraction::<MANTISSA, PARTS>())
+        .prop_filter("Zero is not subnormal", |(_, m)| !m.is_zero())
+        .prop_map(move |(sign, mantissa)| Float::new(sign, min_exp, mantissa))
+        .boxed()
+}
+
+/// Returns a strategy for the zeros of both signs.
+pub fn zero<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>() -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    any::<bool>().prop_map(Float::zero).boxed()
+}
+
+/// Returns a strategy for the infinities of both signs.
+pub fn infinity<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>() -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    any::<bool>().prop_map(Float::inf).boxed()
+}
+
+/// Returns a strategy for the NaNs of the format, with any sign and payload.
+pub fn nan<const EXPONENT: usize, const MANTISSA: usize, const PARTS: usize>(
+) -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    (any::<bool>(), fraction::<MANTISSA, PARTS>())
+        .prop_map(|(sign, payload)| Float::nan_with_payload(sign, payload))
+        .boxed()
+}
+
+/// Returns a strategy for the numbers that [`Float::classify`] puts in the
+/// category `class`.
+pub fn category<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>(
+    class: FpCategory,
+) -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    match class {
+        FpCategory::Nan => nan(),
+        FpCategory::Infinite => infinity(),
+        FpCategory::Zero => zero(),
+        FpCategory::Subnormal => subnormal(),
+        FpCategory::Normal => normal(),
+    }
+}
+
+/// Returns a strategy for the finite numbers of the format. Most of the
+/// numbers are normal, and subnormals and zeros are generated often enough
+/// to exercise the edge cases.
+pub fn finite<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>() -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    prop_oneof![6 => normal(), 2 => subnormal(), 1 => zero()].boxed()
+}
+
+/// Returns a strategy for all the numbers of the format, including the
+/// infinities and the NaNs.
+pub fn any_float<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>() -> BoxedStrategy<Float<EXPONENT, MANTISSA, PARTS>> {
+    prop_oneof![8 => finite(), 1 => infinity(), 1 => nan()].boxed()
+}
+
+/// Returns a strategy for pairs of normal numbers whose exponents differ by
+/// at most `max_exp_delta`. Operands with random exponents rarely overlap, so
+/// this is the strategy to test the alignment, cancellation and rounding
+/// paths of addition and subtraction. The exponent of the second number is
+/// clamped to the range of the format.
+pub fn operand_pair<
+    const EXPONENT: usize,
+    const MANTISSA: usize,
+    const PARTS: usize,
+>(
+    max_exp_delta: u32,
+) -> BoxedStrategy<(
+    Float<EXPONENT, MANTISSA, PARTS>,
+    Float<EXPONENT, MANTISSA, PARTS>,
+)> {
+    let (min_exp, max_exp) =
+        Float::<EXPONENT, MANTISSA, PARTS>::get_exp_bounds();
+    let delta = max_exp_delta as i64;
+    (
+        normal(),
+        -delta..=delta,
+        any::<bool>(),
+        fraction::<MANTISSA, PARTS>(),
+    )
+        .prop_map(move |(a, delta, sign, mut mantissa)| {
+            let exp = (a.get_exp() + delta).clamp(min_exp, max_exp);
+            mantissa.flip_bit(MANTISSA);
+            (a, Float::new(sign, exp, mantissa))
+        })
+        .boxed()
+}
+
+#[test]
+fn test_strategies() {
+    use crate::{FP16, FP64};
+    use proptest::test_runner::TestRunner;
+    let mut runner = TestRunner::deterministic();
+
+    // Every strategy generates the numbers of its category.
+    let classes = [
+        FpCategory::Nan,
+        FpCategory::Infinite,
+        FpCategory::Zero,
+        FpCategory::Subnormal,
+        FpCategory::Normal,
+    ];
+    for class in classes {
+        let check = |x: FP16| {
+            prop_assert_eq!(x.classify(), class);
+            Ok(())
+        };
+        runner.run(&category(class), check).unwrap();
+        let check = |x: FP64| {
+            prop_assert_eq!(x.as_f64().classify(), class);
+            Ok(())
+        };
+        runner.run(&category(class), check).unwrap();
+    }
+
+    // The encodings of the generated numbers are valid, and survive the
+    // round trip through the native type.
+    let check = |x: FP64| {
+        prop_assert!(x.is_finite());
+        prop_assert_eq!(FP64::from_f64(x.as_f64()), x);
+        Ok(())
+    };
+    runner.run(&finite(), check).unwrap();
+    let check = |x: FP16| {
+        prop_assert_eq!(FP16::from_bits(x.to_bits()).to_bits(), x.to_bits());
+        Ok(())
+    };
+    runner.run(&any_float(), check).unwrap();
+
+    // The exponents of the pairs are close.
+    let check = |(a, b): (FP64, FP64)| {
+        prop_assert!(a.is_normal() && b.is_normal());
+        prop_assert!((a.get_exp() - b.get_exp()).abs() <= 3);
+        Ok(())
+    };
+    runner.run(&operand_pair(3), check).unwrap();
+    let check = |(a, b): (FP64, FP64)| {
+        let sum = (a.as_f64() + b.as_f64()).to_bits();
+        prop_assert_eq!((a + b).as_f64().to_bits(), sum);
+        prop_assert_eq!((a - b).as_f64(), a.as_f64() - b.as_f64());
+        Ok(())
+    };
+    runner.run(&operand_pair(60), check).unwrap();
+}
@@ -38,6 +38,79 @@ pub fn get_special_test_values() -> [f64; 20] {
     ]
 }
 
+/// Returns true if `a` and `b` are at most `ulps` representable values apart.
+/// This is used for comparing against the system libm, which is not always
+/// correctly rounded.
+#[cfg(test)]
+pub fn is_close(a: f64, b: f64, ulps: u64) -> bool {
+    if a.is_nan() || b.is_nan() {
+        return a.is_nan() && b.is_nan();
+    }
+    // Map the bit patterns to a monotonic integer sequence.
+    fn ordered(x: f64) -> i64 {
+        let bits = x.to_bits() as i64;
+        if bits < 0 {
+            i64::MIN - bits
+        } else {
+            bits
+        }
+    }
+    ordered(a).abs_diff(ordered(b)) <= ulps
+}
+
+#[test]
+fn test_is_close() {
+    assert!(is_close(1.0, 1.0, 0));
+    assert!(is_close(0.0, -0.0, 0));
+    assert!(is_close(1.0, f64::from_bits(1.0f64.to_bits() + 1), 1));
+    assert!(!is_close(1.0, f64::from_bits(1.0f64.to_bits() + 2), 1));
+    assert!(is_close(f64::from_bits(1), -f64::from_bits(1), 2));
+    assert!(!is_close(f64::NAN, 1.0, 1));
+}
+
+/// A global allocator for the tests, that counts the allocations of each
+/// thread, so that tests can check that code does not allocate memory.
+#[cfg(all(test, feature = "std"))]
+struct CountingAllocator;
+
+#[cfg(all(test, feature = "std"))]
+std::thread_local! {
+    static ALLOCATIONS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+#[cfg(all(test, feature = "std"))]
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the number of memory allocations of the current thread while
+/// running `f`.
+#[cfg(all(test, feature = "std"))]
+pub fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(|n| n.get());
+    f();
+    ALLOCATIONS.with(|n| n.get()) - before
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_count_allocations() {
+    assert_eq!(count_allocations(|| ()), 0);
+    let n = count_allocations(|| drop(std::vec![1u8; 10]));
+    assert_eq!(n, 1);
+}
+
 // Linear-feedback shift register.
 pub struct Lfsr {
     state: u32,
@@ -0,0 +1,232 @@
+//! The VAX floating point formats F, D and G. The numbers have a sign bit, a
+//! biased exponent, and a normalized fraction with a hidden bit, and their
+//! value is 0.1fraction * 2^(exponent - bias). F and D have an 8-bit exponent
+//! with a bias of 128, and G has an 11-bit exponent with a bias of 1024.
+//! There are no infinities, NaNs or subnormals. A zero exponent is zero when
+//! the sign is clear, and otherwise it is a reserved operand, which traps on
+//! the VAX, and is decoded as NaN.
+//!
+//! The encodings are VAX longwords and quadwords, where the 16-bit words are in
+//! the PDP-11 order: the first word holds the sign, the exponent and the high
+//! bits of the fraction. The conversions to and from Float are exact, unless
+//! the target format is narrower.
+//!```
+//!    use arpfloat::{VaxF, FP32, RoundingMode};
+//!    let rm = RoundingMode::NearestTiesToEven;
+//!    let x = VaxF::from_float(&FP32::from_f64(1.0), rm);
+//!    assert_eq!(x.to_bits(), 0x4080);
+//!    assert_eq!(x.to_float::<8, 23, 1>().as_f32(), 1.0);
+//!```
+
+use super::bigint::BigInt;
+use super::float::{Float, RoundingMode};
+
+/// A format with the precision of F, and a wider exponent range.
+type VaxFWide = Float<10, 23, 1>;
+/// A format with the precision of D, and a wider exponent range.
+type VaxDWide = Float<10, 55, 2>;
+/// A format with the precision of G, and a wider exponent range.
+type VaxGWide = Float<13, 52, 2>;
+
+/// Reverses the order of the lowest `words` 16-bit words in `bits`. This
+/// converts between the VAX order of the words and the order of their
+/// significance.
+const fn swap_words(bits: u64, words: usize) -> u64 {
+    let mut res = 0;
+    let mut i = 0;
+    while i < words {
+        res = res << 16 | ((bits >> (16 * i)) & 0xffff);
+        i += 1;
+    }
+    res
+}
+
+macro_rules! define_vax_format {
+    ($(#[$attr:meta])* $name:ident, $ty:ty, $wide:ty, $exp_bits:expr, $man_bits:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name($ty);
+
+        impl $name {
+            /// The number of bits in the encoding.
+            pub const BITS: usize = 1 + $exp_bits + $man_bits;
+            /// The largest finite number.
+            pub const MAX: Self = $name(swap_words(
+                (1 << (Self::BITS - 1)) - 1,
+                Self::BITS / 16,
+            ) as $ty);
+            /// The reserved operand.
+            pub const RESERVED: Self =
+                $name(swap_words(1 << (Self::BITS - 1), Self::BITS / 16) as $ty);
+
+            /// Creates a number from its encoding.
+            pub fn from_bits(bits: $ty) -> Self {
+                $name(bits)
+            }
+
+            /// Returns the encoding of the number.
+            pub fn to_bits(&self) -> $ty {
+                self.0
+            }
+
+            /// Returns true if the number is a reserved operand.
+            pub fn is_reserved(&self) -> bool {
+                let bits = swap_words(self.0 as u64, Self::BITS / 16);
+                bits >> $man_bits == 1 << $exp_bits
+            }
+
+            /// Converts `val` to this format, with the rounding mode `rm`.
+            /// Infinities and numbers that overflow are converted to the
+            /// largest number, numbers below the smallest normal number are
+            /// flushed to zero, and NaN is converted to the reserved operand.
+            pub fn from_float<const E: usize, const M: usize, const P: usize>(
+                val: &Float<E, M, P>,
+                rm: RoundingMode,
+            ) -> Self {
+                let x: $wide = val.cast_with_rm(rm);
+                let sign = (x.get_sign() as u64) << (Self::BITS - 1);
+                let max_exp: i64 = (1 << $exp_bits) - 1;
+                let bias: i64 = 1 << ($exp_bits - 1);
+                let bits = if x.is_nan() {
+                    1 << (Self::BITS - 1)
+                } else if x.is_zero() {
+                    0
+                } else if x.is_inf() || x.get_exp() + bias + 1 > max_exp {
+                    sign | ((1 << (Self::BITS - 1)) - 1)
+                } else if x.get_exp() + bias + 1 < 1 {
+                    0
+                } else {
+                    let exp = (x.get_exp() + bias + 1) as u64;
+                    let mantissa = x.get_mantissa().as_u64();
+                    let fraction = mantissa & ((1 << $man_bits) - 1);
+                    sign | exp << $man_bits | fraction
+                };
+                $name(swap_words(bits, Self::BITS / 16) as $ty)
+            }
+
+            /// Converts the number to a Float. The conversion is exact, unless
+            /// the target format is narrower than this format.
+            pub fn to_float<const E: usize, const M: usize, const P: usize>(
+                &self,
+            ) -> Float<E, M, P> {
+                let bits = swap_words(self.0 as u64, Self::BITS / 16);
+                let sign = bits >> (Self::BITS - 1) != 0;
+                let exp = (bits >> $man_bits) as i64 & ((1 << $exp_bits) - 1);
+                if exp == 0 {
+                    return if sign { Float::nan(false) } else { Float::zero(false) };
+                }
+                let bias: i64 = 1 << ($exp_bits - 1);
+                let fraction = bits & ((1 << $man_bits) - 1);
+                let mantissa = BigInt::from_u64(fraction | 1 << $man_bits);
+                <$wide>::new(sign, exp - bias - 1, mantissa).cast()
+            }
+        }
+    };
+}
+
+define_vax_format!(
+    /// The 32-bit VAX F format, with 24 bits of precision, and the range of
+    /// 2.9e-39 to 1.7e38.
+    VaxF,
+    u32,
+    VaxFWide,
+    8,
+    23
+);
+
+define_vax_format!(
+    /// The 64-bit VAX D format, with 56 bits of precision, and the range of the
+    /// F format.
+    VaxD,
+    u64,
+    VaxDWide,
+    8,
+    55
+);
+
+define_vax_format!(
+    /// The 64-bit VAX G format, with 53 bits of precision, and the range of
+    /// 5.6e-309 to 9.0e307.
+    VaxG,
+    u64,
+    VaxGWide,
+    11,
+    52
+);
+
+#[test]
+fn test_vax_decode() {
+    use crate::{FP128, FP64};
+
+    // Known values of the encodings.
+    assert_eq!(VaxF::from_bits(0x4080).to_float::<11, 52, 2>().as_f64(), 1.);
+    assert_eq!(
+        VaxF::from_bits(0xc140).to_float::<11, 52, 2>().as_f64(),
+        -3.
+    );
+    assert_eq!(VaxD::from_bits(0x4080).to_float::<11, 52, 2>().as_f64(), 1.);
+    assert_eq!(VaxG::from_bits(0x4010).to_float::<11, 52, 2>().as_f64(), 1.);
+    let x = VaxF::from_bits(0x0080).to_float::<11, 52, 2>();
+    assert_eq!(x.as_f64(), 2f64.powi(-128));
+    let x = VaxF::MAX.to_float::<11, 52, 2>();
+    assert_eq!(x.as_f64(), f32::MAX as f64 / 2.);
+    let x = VaxG::from_bits(0x0010).to_float::<15, 112, 4>();
+    assert_eq!(x, FP128::from_u64(1).scale(-1024, RoundingMode::Zero));
+    let x: FP64 = VaxG::MAX.to_float();
+    assert_eq!(x.as_f64(), f64::MAX / 2.);
+
+    // Zeros and reserved operands.
+    assert!(VaxF::from_bits(0x0000).to_float::<11, 52, 2>().is_zero());
+    assert!(VaxF::from_bits(0x12340012)
+        .to_float::<11, 52, 2>()
+        .is_zero());
+    assert!(VaxF::RESERVED.is_reserved());
+    assert!(VaxF::from_bits(0x8000).to_float::<11, 52, 2>().is_nan());
+    assert!(VaxD::from_bits(0x8000).is_reserved());
+    assert!(VaxG::from_bits(0x800f).to_float::<11, 52, 2>().is_nan());
+    assert!(!VaxG::from_bits(0x4010).is_reserved());
+}
+
+#[test]
+fn test_vax_encode() {
+    use super::utils::Lfsr;
+    use crate::{FP128, FP32, FP64};
+    let rm = RoundingMode::NearestTiesToEven;
+
+    let f = |x: f64| VaxF::from_float(&FP64::from_f64(x), rm).to_bits();
+    assert_eq!(f(1.), 0x4080);
+    assert_eq!(f(-0.), 0);
+    assert_eq!(f(1e39), VaxF::MAX.to_bits());
+    assert_eq!(f(-1e39), VaxF::MAX.to_bits() | 0x8000);
+    assert_eq!(f(f64::INFINITY), VaxF::MAX.to_bits());
+    assert_eq!(f(f64::NAN), VaxF::RESERVED.to_bits());
+    assert_eq!(f(1e-39), 0);
+    assert_eq!(f(2f64.powi(-128) * (1. - 2f64.powi(-30))), 0x0080);
+    let x = FP64::from_f64(0.1);
+    let d = VaxD::from_float(&x, rm);
+    assert_eq!(d.to_float::<11, 52, 2>().as_f64(), 0.1);
+    assert_eq!(VaxG::from_float(&x, rm).to_bits(), 0x999a_9999_9999_3fd9);
+
+    // F and G have the precision of f32 and f64, with a bias that is larger
+    // by two, and D holds the f32 values exactly.
+    let mut lfsr = Lfsr::new();
+    for _ in 0..2000 {
+        let x = f32::from_bits(lfsr.get64() as u32);
+        if x.is_normal() && x.abs() < 2f32.powi(126) {
+            let bits = swap_words(x.to_bits() as u64 + (2 << 23), 2);
+            let v = VaxF::from_float(&FP32::from_f32(x), rm);
+            assert_eq!(v.to_bits() as u64, bits);
+            assert_eq!(v.to_float::<8, 23, 1>().as_f32(), x);
+            let d = VaxD::from_float(&FP32::from_f32(x), rm);
+            assert_eq!(d.to_float::<8, 23, 1>().as_f32(), x);
+        }
+
+        let x = f64::from_bits(lfsr.get64());
+        if x.is_normal() && x.abs() < 2f64.powi(1022) {
+            let bits = swap_words(x.to_bits() + (2 << 52), 4);
+            let v = VaxG::from_float(&FP64::from_f64(x), rm);
+            assert_eq!(v.to_bits(), bits);
+            assert_eq!(v.to_float::<15, 112, 4>(), FP128::from_f64(x));
+        }
+    }
+}